@@ -0,0 +1,256 @@
+//! オーディオシステム - BGM・SE・ボイスの再生制御
+//!
+//! # 責務
+//! - BGM・SE・ボイスのチャンネル別再生と個別音量設定
+//! - ダイアログ表示に合わせたボイス再生（指定がなければセリフIDのボイス、送り時の停止・継続）
+//! - ボイス再生中のBGM音量の抑制（ダッキング）
+
+use bevy::prelude::*;
+use bevy::audio::Volume;
+use bevy::ecs::system::SystemParam;
+use std::path::Path;
+use crate::application::scenario_system::MarkdownScenarioState;
+use crate::domain::scenario::DialogueBlock;
+
+/// ボイスファイルの配置ディレクトリ（assets からの相対パス）
+pub const VOICE_DIRECTORY: &str = "sounds/voice";
+
+/// オーディオ設定（Bevy Resource、プレイヤー設定）
+#[derive(Resource, Debug, Clone)]
+pub struct AudioSettings {
+    /// BGM音量（0.0〜1.0）
+    pub bgm_volume: f32,
+    /// SE音量（0.0〜1.0）
+    pub se_volume: f32,
+    /// ボイス音量（0.0〜1.0）
+    pub voice_volume: f32,
+    /// ダイアログを送ったときに再生中のボイスを止めるか
+    pub stop_voice_on_advance: bool,
+    /// ボイス再生中のBGM音量の倍率
+    pub bgm_duck_ratio: f32,
+    /// ダッキングの音量変化にかける時間（秒）
+    pub duck_fade_seconds: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            bgm_volume: 0.8,
+            se_volume: 0.8,
+            voice_volume: 1.0,
+            stop_voice_on_advance: true,
+            bgm_duck_ratio: 0.4,
+            duck_fade_seconds: 0.3,
+        }
+    }
+}
+
+/// BGMチャンネル（同時に1つだけ再生）
+#[derive(Component, Debug)]
+pub struct BgmChannel {
+    /// `[bgm volume=...]` で指定された音量
+    pub volume: f32,
+    /// 現在のダッキング倍率（1.0 = 抑制なし）
+    pub duck: f32,
+}
+
+/// SEチャンネル
+#[derive(Component, Debug)]
+pub struct SeChannel;
+
+/// ボイスチャンネル（同時に1つだけ再生、再生終了で自動削除）
+#[derive(Component, Debug)]
+pub struct VoiceChannel;
+
+/// ボイスファイルのパス（`[voice storage=...]` の値から）
+pub fn voice_path(storage: &str) -> String {
+    format!("{}/{}", VOICE_DIRECTORY, storage)
+}
+
+/// BGM再生用のコンポーネント一式
+pub fn bgm_bundle(
+    source: Handle<AudioSource>,
+    volume: f32,
+    looped: bool,
+    settings: &AudioSettings,
+) -> (AudioPlayer, PlaybackSettings, BgmChannel) {
+    let playback = if looped { PlaybackSettings::LOOP } else { PlaybackSettings::DESPAWN };
+    (
+        AudioPlayer(source),
+        playback.with_volume(Volume::new(volume * settings.bgm_volume)),
+        BgmChannel { volume, duck: 1.0 },
+    )
+}
+
+/// SE再生用のコンポーネント一式
+pub fn se_bundle(source: Handle<AudioSource>, volume: f32, settings: &AudioSettings) -> (AudioPlayer, PlaybackSettings, SeChannel) {
+    (
+        AudioPlayer(source),
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(volume * settings.se_volume)),
+        SeChannel,
+    )
+}
+
+/// ボイス再生用のコンポーネント一式
+pub fn voice_bundle(source: Handle<AudioSource>, settings: &AudioSettings) -> (AudioPlayer, PlaybackSettings, VoiceChannel) {
+    (
+        AudioPlayer(source),
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(settings.voice_volume)),
+        VoiceChannel,
+    )
+}
+
+/// 再生中のボイスを止めて新しいボイスを再生
+pub fn play_voice(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    settings: &AudioSettings,
+    playing: impl IntoIterator<Item = Entity>,
+    storage: &str,
+) {
+    for entity in playing {
+        commands.entity(entity).despawn();
+    }
+
+    let path = voice_path(storage);
+    println!("🗣️ ボイス再生: {}", path);
+    commands.spawn(voice_bundle(asset_server.load(path), settings));
+}
+
+/// ボイスの再生に使うリソース（再生中のボイスの差し替えまで行う）
+#[derive(SystemParam)]
+pub struct VoicePlayer<'w, 's> {
+    asset_server: Res<'w, AssetServer>,
+    settings: Res<'w, AudioSettings>,
+    playing: Query<'w, 's, Entity, With<VoiceChannel>>,
+}
+
+impl VoicePlayer<'_, '_> {
+    /// 再生中のボイスを止めて新しいボイスを再生
+    pub fn play(&self, commands: &mut Commands, storage: &str) {
+        play_voice(commands, &self.asset_server, &self.settings, self.playing.iter(), storage);
+    }
+}
+
+/// ダイアログで再生するボイス（`voice_path` に渡す値）
+///
+/// `[voice]` 指定を優先し、なければ `{キャラクターID}/{セリフID}.ogg` が収録済み（`is_recorded` が true）なら使う
+pub fn line_voice(dialogue: &DialogueBlock, is_recorded: impl Fn(&str) -> bool) -> Option<String> {
+    if dialogue.voice.is_some() {
+        return dialogue.voice.clone();
+    }
+    dialogue.expected_voice_file().filter(|storage| is_recorded(storage))
+}
+
+/// ダイアログ表示に合わせてボイスを再生するシステム
+///
+/// 表示中のダイアログが切り替わったとき、`[voice]` 指定か収録済みの `{キャラクターID}/{セリフID}.ogg` があれば再生する。
+/// どちらもない場合は設定に従い、前のボイスを止めるか鳴らし続ける。
+pub fn voice_line_system(
+    mut commands: Commands,
+    scenario_state: Res<MarkdownScenarioState>,
+    settings: Res<AudioSettings>,
+    asset_server: Res<AssetServer>,
+    voice_query: Query<Entity, With<VoiceChannel>>,
+    mut last_line: Local<Option<(usize, usize)>>,
+) {
+    let Some(dialogue) = scenario_state.get_current_dialogue() else {
+        *last_line = None;
+        return;
+    };
+
+    let line = (scenario_state.current_scene_index, scenario_state.current_dialogue_index);
+    if *last_line == Some(line) {
+        return;
+    }
+    *last_line = Some(line);
+
+    let voice = line_voice(dialogue, |storage| Path::new("assets").join(voice_path(storage)).exists());
+    match &voice {
+        Some(storage) => play_voice(&mut commands, &asset_server, &settings, voice_query.iter(), storage),
+        None if settings.stop_voice_on_advance => {
+            for entity in voice_query.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        None => {}
+    }
+}
+
+/// ボイス再生中にBGMの音量を下げるシステム（ダッキング）
+pub fn bgm_ducking_system(
+    time: Res<Time>,
+    settings: Res<AudioSettings>,
+    voice_query: Query<(), With<VoiceChannel>>,
+    mut bgm_query: Query<(&mut BgmChannel, &AudioSink)>,
+) {
+    let target = if voice_query.is_empty() { 1.0 } else { settings.bgm_duck_ratio };
+    let step = if settings.duck_fade_seconds > 0.0 {
+        time.delta_secs() * (1.0 - settings.bgm_duck_ratio) / settings.duck_fade_seconds
+    } else {
+        1.0
+    };
+
+    for (mut channel, sink) in bgm_query.iter_mut() {
+        if channel.duck != target {
+            channel.duck = duck_toward(channel.duck, target, step);
+        }
+
+        let volume = channel.volume * settings.bgm_volume * channel.duck;
+        if sink.volume() != volume {
+            sink.set_volume(volume);
+        }
+    }
+}
+
+/// ダッキング倍率を目標値へ `step` だけ近づける
+fn duck_toward(current: f32, target: f32, step: f32) -> f32 {
+    if current < target {
+        (current + step).min(target)
+    } else {
+        (current - step).max(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voice_path() {
+        assert_eq!(voice_path("souma_001.ogg"), "sounds/voice/souma_001.ogg");
+    }
+
+    #[test]
+    fn test_line_voice_falls_back_to_line_id() {
+        let mut dialogue = DialogueBlock {
+            speaker: Some("ソウマ".to_string()),
+            text: "こんにちは".to_string(),
+            character_id: Some("souma".to_string()),
+            voice: None,
+            line_id: "a1b2c3d4".to_string(),
+            source_line: None,
+        };
+
+        // 収録済みならセリフIDのボイス、未収録なら再生しない
+        assert_eq!(line_voice(&dialogue, |storage| storage == "souma/a1b2c3d4.ogg").as_deref(), Some("souma/a1b2c3d4.ogg"));
+        assert_eq!(line_voice(&dialogue, |_| false), None);
+
+        // [voice] 指定があればそちらを優先
+        dialogue.voice = Some("souma_001.ogg".to_string());
+        assert_eq!(line_voice(&dialogue, |_| false).as_deref(), Some("souma_001.ogg"));
+
+        // 地の文（キャラクターIDなし）にはボイスを当てない
+        dialogue.voice = None;
+        dialogue.character_id = None;
+        assert_eq!(line_voice(&dialogue, |_| true), None);
+    }
+
+    #[test]
+    fn test_duck_toward() {
+        assert_eq!(duck_toward(1.0, 0.4, 0.25), 0.75);
+        assert_eq!(duck_toward(0.5, 0.4, 0.25), 0.4);
+        assert_eq!(duck_toward(0.4, 1.0, 0.5), 0.9);
+        assert_eq!(duck_toward(0.9, 1.0, 0.5), 1.0);
+    }
+}
@@ -4,73 +4,550 @@
 //! - SceneCommandの実際の実行
 //! - 背景・キャラクター・音声の制御
 //! - 既存のBevy Componentとの統合
+//! - 組み込みコマンド（bg, chara_show, bgm 等）のハンドラ定義
+//! - ゲームプレイのコマンド（関係値・相関図・プレゼント・エンディング等）のハンドラ定義
 
 use bevy::prelude::*;
-use crate::domain::scenario::{SceneCommand, CharacterPosition};
+use std::collections::HashMap;
+use crate::domain::scenario::{
+    BackgroundCommand, BgmCommand, CharacterHideCommand, CharacterPosition, CharacterShowCommand, ChoiceCommand,
+    ParseError, SceneCommand, SeCommand, VoiceCommand, WaitCommand,
+};
 use crate::domain::character::{CharacterDisplay, CharacterDisplayPosition, CharacterRegistry};
-// use std::collections::HashMap; // 将来使用予定
+use crate::domain::command_registry::{bool_param, required_param, SceneCommandHandler, SceneCommandRegistry};
+use crate::domain::relationship::{CapPhase, RelationshipCapPolicy, RELATIONSHIP_LIMIT};
+use crate::domain::relationship_history::{ChangeCause, ChangeSource};
+use crate::domain::ending::EndingTable;
+use crate::application::services::{GameProgressService, RelationshipService};
+use crate::application::scenario_system::{self, MarkdownScenarioState};
+use crate::application::audio_system::{self, AudioSettings, BgmChannel, VoiceChannel};
+use crate::presentation::ui_components::{GameMode, GameScreen};
 
-/// コマンド実行サービス
-pub struct CommandExecutor;
+/// 組み込みコマンドのハンドラをレジストリに登録
+pub fn register_builtin_command_handlers(registry: &mut SceneCommandRegistry) {
+    registry.register(BackgroundCommandHandler);
+    registry.register(CharacterShowCommandHandler);
+    registry.register(CharacterHideCommandHandler);
+    registry.register(BgmCommandHandler);
+    registry.register(SeCommandHandler);
+    registry.register(WaitCommandHandler);
+    registry.register(ChoiceCommandHandler);
+    registry.register(VoiceCommandHandler);
+}
 
-impl CommandExecutor {
-    /// シーンコマンドを実行
-    pub fn execute_command(
-        command: &SceneCommand,
-        commands: &mut Commands,
-        asset_server: &Res<AssetServer>,
-        character_registry: &Res<CharacterRegistry>,
-        background_query: &mut Query<&mut Sprite, (With<BackgroundImage>, Without<CharacterDisplay>)>,
-        character_query: &mut Query<(Entity, &mut CharacterDisplay, &mut Transform, &mut Sprite)>,
-    ) {
-        match command {
-            SceneCommand::Background { storage, time } => {
-                Self::execute_background_change(commands, asset_server, background_query, storage, *time);
-            }
-            SceneCommand::CharacterShow { name, face, pos } => {
-                Self::execute_character_show(
-                    commands,
-                    asset_server,
-                    character_registry,
-                    character_query,
-                    name,
-                    face.as_deref(),
-                    pos.as_ref(),
-                );
-            }
-            SceneCommand::CharacterHide { name } => {
-                Self::execute_character_hide(character_query, name);
-            }
-            SceneCommand::Bgm { play, volume, loop_audio } => {
-                Self::execute_bgm(commands, asset_server, play, *volume, *loop_audio);
-            }
-            SceneCommand::Se { play, volume } => {
-                Self::execute_se(commands, asset_server, play, *volume);
-            }
-            SceneCommand::Wait { time } => {
-                Self::execute_wait(*time);
-            }
-            SceneCommand::Choice { text } => {
-                Self::execute_choice(commands, text);
-            }
+/// 組み込みコマンドだけを登録したレジストリを作成
+pub fn builtin_command_registry() -> SceneCommandRegistry {
+    let mut registry = SceneCommandRegistry::new();
+    register_builtin_command_handlers(&mut registry);
+    registry
+}
+
+/// ゲームプレイのコマンドのハンドラをレジストリに登録
+pub fn register_game_command_handlers(registry: &mut SceneCommandRegistry) {
+    registry.register(SpeakerFocusCommandHandler);
+    registry.register(RelationshipCapCommandHandler);
+    registry.register(RelationshipChangeCommandHandler);
+    registry.register(RelationshipChartCommandHandler);
+    registry.register(GiftCommandHandler);
+    registry.register(RouteLockCommandHandler);
+    registry.register(EndingCommandHandler);
+    registry.register(JumpCommandHandler);
+}
+
+/// 組み込みコマンドとゲームプレイのコマンドを登録済みのレジストリを作成
+pub fn game_command_registry() -> SceneCommandRegistry {
+    let mut registry = builtin_command_registry();
+    register_game_command_handlers(&mut registry);
+    registry
+}
+
+/// 背景変更 [bg]
+pub struct BackgroundCommandHandler;
+
+impl SceneCommandHandler for BackgroundCommandHandler {
+    fn name(&self) -> &str {
+        "bg"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let storage = required_param(self.name(), params, "storage")?.clone();
+        let time = params.get("time").and_then(|t| t.parse().ok());
+        Ok(SceneCommand::new(self.name(), BackgroundCommand { storage, time }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(BackgroundCommand { storage, time }) = command.payload::<BackgroundCommand>() {
+            let (storage, time) = (storage.clone(), *time);
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_background_change(world, &storage, time);
+            });
+        }
+    }
+}
+
+/// キャラクター表示 [chara_show]
+pub struct CharacterShowCommandHandler;
+
+impl SceneCommandHandler for CharacterShowCommandHandler {
+    fn name(&self) -> &str {
+        "chara_show"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let name = required_param(self.name(), params, "name")?.clone();
+        let face = params.get("face").cloned();
+        let pos = params.get("pos").map(|p| CharacterPosition::from(p.as_str()));
+        Ok(SceneCommand::new(self.name(), CharacterShowCommand { name, face, pos }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(show) = command.payload::<CharacterShowCommand>() {
+            let CharacterShowCommand { name, face, pos } = show.clone();
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_character_show(world, &name, face.as_deref(), pos.as_ref());
+            });
+        }
+    }
+}
+
+/// キャラクター非表示 [chara_hide]
+pub struct CharacterHideCommandHandler;
+
+impl SceneCommandHandler for CharacterHideCommandHandler {
+    fn name(&self) -> &str {
+        "chara_hide"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let name = required_param(self.name(), params, "name")?.clone();
+        Ok(SceneCommand::new(self.name(), CharacterHideCommand { name }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(CharacterHideCommand { name }) = command.payload::<CharacterHideCommand>() {
+            let name = name.clone();
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_character_hide(world, &name);
+            });
+        }
+    }
+}
+
+/// BGM再生 [bgm]
+pub struct BgmCommandHandler;
+
+impl SceneCommandHandler for BgmCommandHandler {
+    fn name(&self) -> &str {
+        "bgm"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let play = required_param(self.name(), params, "play")?.clone();
+        let volume = params.get("volume").and_then(|v| v.parse().ok());
+        let loop_audio = params.get("loop").and_then(|l| l.parse().ok());
+        Ok(SceneCommand::new(self.name(), BgmCommand { play, volume, loop_audio }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(bgm) = command.payload::<BgmCommand>() {
+            let BgmCommand { play, volume, loop_audio } = bgm.clone();
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_bgm(world, &play, volume, loop_audio);
+            });
+        }
+    }
+}
+
+/// SE再生 [se]
+pub struct SeCommandHandler;
+
+impl SceneCommandHandler for SeCommandHandler {
+    fn name(&self) -> &str {
+        "se"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let play = required_param(self.name(), params, "play")?.clone();
+        let volume = params.get("volume").and_then(|v| v.parse().ok());
+        Ok(SceneCommand::new(self.name(), SeCommand { play, volume }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(SeCommand { play, volume }) = command.payload::<SeCommand>() {
+            let (play, volume) = (play.clone(), *volume);
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_se(world, &play, volume);
+            });
+        }
+    }
+}
+
+/// 待機 [wait]
+pub struct WaitCommandHandler;
+
+impl SceneCommandHandler for WaitCommandHandler {
+    fn name(&self) -> &str {
+        "wait"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let time = required_param(self.name(), params, "time")?
+            .parse()
+            .map_err(|_| ParseError {
+                line_number: 0,
+                message: "wait の time パラメータは数値である必要があります".to_string(),
+            })?;
+        Ok(SceneCommand::new(self.name(), WaitCommand { time }))
+    }
+
+    fn execute(&self, command: &SceneCommand, _commands: &mut Commands) {
+        if let Some(&WaitCommand { time }) = command.payload::<WaitCommand>() {
+            CommandExecutor::execute_wait(time);
+        }
+    }
+}
+
+/// 選択肢 [choice]
+pub struct ChoiceCommandHandler;
+
+impl SceneCommandHandler for ChoiceCommandHandler {
+    fn name(&self) -> &str {
+        "choice"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let text = required_param(self.name(), params, "text")?.clone();
+        Ok(SceneCommand::new(self.name(), ChoiceCommand { text }))
+    }
+
+    fn execute(&self, command: &SceneCommand, _commands: &mut Commands) {
+        if let Some(ChoiceCommand { text }) = command.payload::<ChoiceCommand>() {
+            CommandExecutor::execute_choice(text);
+        }
+    }
+}
+
+/// ボイス [voice]
+///
+/// シナリオ読み込み時は直後のダイアログに付与されるため、実行されるのは直接実行された場合のみ
+pub struct VoiceCommandHandler;
+
+impl SceneCommandHandler for VoiceCommandHandler {
+    fn name(&self) -> &str {
+        "voice"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let storage = required_param(self.name(), params, "storage")?.clone();
+        Ok(SceneCommand::new(self.name(), VoiceCommand { storage }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(VoiceCommand { storage }) = command.payload::<VoiceCommand>() {
+            let storage = storage.clone();
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_voice(world, &storage);
+            });
+        }
+    }
+}
+
+/// 話者の強調・非話者の暗転をこのシーンで切り替え [speaker_focus enabled=false]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerFocusCommand {
+    pub enabled: bool,
+}
+
+/// 話者の強調表示の切り替え [speaker_focus]
+pub struct SpeakerFocusCommandHandler;
+
+impl SceneCommandHandler for SpeakerFocusCommandHandler {
+    fn name(&self) -> &str {
+        "speaker_focus"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        required_param(self.name(), params, "enabled")?;
+        let enabled = bool_param(self.name(), params, "enabled")?.unwrap_or(true);
+        Ok(SceneCommand::new(self.name(), SpeakerFocusCommand { enabled }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(&SpeakerFocusCommand { enabled }) = command.payload::<SpeakerFocusCommand>() {
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_speaker_focus(world, enabled);
+            });
+        }
+    }
+}
+
+/// 関係値キャップの変更 [relationship_cap phase=mid apply_banked=true]
+///
+/// `phase` の代わりに `limit=50` で上限を直接指定できる。
+/// `apply_banked` 省略時は現在の方針を引き継ぐ
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipCapCommand {
+    pub limit: i32,
+    pub apply_banked: Option<bool>,
+}
+
+/// 関係値キャップの変更 [relationship_cap]
+pub struct RelationshipCapCommandHandler;
+
+impl SceneCommandHandler for RelationshipCapCommandHandler {
+    fn name(&self) -> &str {
+        "relationship_cap"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let limit = match (params.get("phase"), params.get("limit")) {
+            (Some(phase), _) => CapPhase::from_name(phase)
+                .ok_or_else(|| ParseError {
+                    line_number: 0,
+                    message: format!("relationship_cap の phase は early/mid/late のいずれか: {}", phase),
+                })?
+                .limit(),
+            (None, Some(limit)) => limit.trim_start_matches(['±', '+']).parse::<i32>()
+                .ok()
+                .filter(|limit| (1..=RELATIONSHIP_LIMIT).contains(limit))
+                .ok_or_else(|| ParseError {
+                    line_number: 0,
+                    message: format!("relationship_cap の limit は 1～100 の数値である必要があります: {}", limit),
+                })?,
+            (None, None) => return Err(ParseError {
+                line_number: 0,
+                message: "relationship_cap コマンドには phase または limit パラメータが必要".to_string(),
+            }),
+        };
+        let apply_banked = bool_param(self.name(), params, "apply_banked")?;
+
+        Ok(SceneCommand::new(self.name(), RelationshipCapCommand { limit, apply_banked }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(&RelationshipCapCommand { limit, apply_banked }) = command.payload::<RelationshipCapCommand>() {
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_relationship_cap(world, limit, apply_banked);
+            });
+        }
+    }
+}
+
+/// 関係値の変動 [relationship a=souma b=yuzuki delta=10 source=choice reason=約束 notify=false]
+///
+/// `source` 省略時はイベント扱い。`notify=false` で画面への通知を抑止する
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipChangeCommand {
+    pub a: String,
+    pub b: String,
+    pub delta: i32,
+    pub source: ChangeSource,
+    pub reason: String,
+    pub notify: bool,
+}
+
+/// 関係値の変動 [relationship]
+pub struct RelationshipChangeCommandHandler;
+
+impl SceneCommandHandler for RelationshipChangeCommandHandler {
+    fn name(&self) -> &str {
+        "relationship"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let a = required_param(self.name(), params, "a")?.clone();
+        let b = required_param(self.name(), params, "b")?.clone();
+        let delta = required_param(self.name(), params, "delta")?
+            .trim_start_matches('+')
+            .parse()
+            .map_err(|_| ParseError {
+                line_number: 0,
+                message: "relationship の delta パラメータは数値である必要があります".to_string(),
+            })?;
+        let source = match params.get("source") {
+            Some(name) => ChangeSource::from_name(name).ok_or_else(|| ParseError {
+                line_number: 0,
+                message: format!("relationship の source は battle/choice/event/gift のいずれか: {}", name),
+            })?,
+            None => ChangeSource::Event,
+        };
+        let reason = params.get("reason").cloned().unwrap_or_default();
+        let notify = bool_param(self.name(), params, "notify")?.unwrap_or(true);
+
+        if a == b {
+            return Err(ParseError {
+                line_number: 0,
+                message: format!("relationship の a と b に同じキャラクターは指定できません: {}", a),
+            });
+        }
+
+        Ok(SceneCommand::new(self.name(), RelationshipChangeCommand { a, b, delta, source, reason, notify }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(change) = command.payload::<RelationshipChangeCommand>() {
+            let change = change.clone();
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_relationship_change(world, &change);
+            });
+        }
+    }
+}
+
+/// 相関図を開く [relationship_chart]（拠点でサイトウに話しかけたとき）
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipChartCommand;
+
+/// 相関図を開く [relationship_chart]
+pub struct RelationshipChartCommandHandler;
+
+impl SceneCommandHandler for RelationshipChartCommandHandler {
+    fn name(&self) -> &str {
+        "relationship_chart"
+    }
+
+    fn parse(&self, _params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        Ok(SceneCommand::new(self.name(), RelationshipChartCommand))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if command.payload::<RelationshipChartCommand>().is_some() {
+            commands.queue(|world: &mut World| {
+                CommandExecutor::execute_relationship_chart(world);
+            });
+        }
+    }
+}
+
+/// プレゼント画面を開く [gift]（拠点で仲間にプレゼントを渡す）
+#[derive(Debug, Clone, PartialEq)]
+pub struct GiftCommand;
+
+/// プレゼント画面を開く [gift]
+pub struct GiftCommandHandler;
+
+impl SceneCommandHandler for GiftCommandHandler {
+    fn name(&self) -> &str {
+        "gift"
+    }
+
+    fn parse(&self, _params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        Ok(SceneCommand::new(self.name(), GiftCommand))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if command.payload::<GiftCommand>().is_some() {
+            commands.queue(|world: &mut World| {
+                CommandExecutor::execute_gift(world);
+            });
+        }
+    }
+}
+
+/// ルート確定 [route_lock character=souma]
+///
+/// その時点の主人公と各キャラクターの関係レベルを確定する（`character` 省略時は規則表の主人公）
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteLockCommand {
+    pub character: Option<String>,
+}
+
+/// ルート確定 [route_lock]
+pub struct RouteLockCommandHandler;
+
+impl SceneCommandHandler for RouteLockCommandHandler {
+    fn name(&self) -> &str {
+        "route_lock"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        Ok(SceneCommand::new(self.name(), RouteLockCommand {
+            character: params.get("character").cloned(),
+        }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(RouteLockCommand { character }) = command.payload::<RouteLockCommand>() {
+            let character = character.clone();
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_route_lock(world, character.as_deref());
+            });
+        }
+    }
+}
+
+/// 確定したルートのエンディングを再生 [ending]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndingCommand;
+
+/// エンディング再生 [ending]
+pub struct EndingCommandHandler;
+
+impl SceneCommandHandler for EndingCommandHandler {
+    fn name(&self) -> &str {
+        "ending"
+    }
+
+    fn parse(&self, _params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        Ok(SceneCommand::new(self.name(), EndingCommand))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if command.payload::<EndingCommand>().is_some() {
+            commands.queue(|world: &mut World| {
+                CommandExecutor::execute_ending(world);
+            });
+        }
+    }
+}
+
+/// 別のシナリオへ移動 [jump storage=hub_saitou.md]
+///
+/// `storage` は `assets/scenarios/` からの相対パス。移動先の最初のシーンから再生する
+#[derive(Debug, Clone, PartialEq)]
+pub struct JumpCommand {
+    pub storage: String,
+}
+
+/// シナリオ移動 [jump]
+pub struct JumpCommandHandler;
+
+impl SceneCommandHandler for JumpCommandHandler {
+    fn name(&self) -> &str {
+        "jump"
+    }
+
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        let storage = required_param(self.name(), params, "storage")?.clone();
+        Ok(SceneCommand::new(self.name(), JumpCommand { storage }))
+    }
+
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        if let Some(JumpCommand { storage }) = command.payload::<JumpCommand>() {
+            let storage = storage.clone();
+            commands.queue(move |world: &mut World| {
+                CommandExecutor::execute_jump(world, &storage);
+            });
         }
     }
+}
 
+/// コマンド実行サービス
+///
+/// 各コマンドのハンドラから呼ばれる実処理をまとめたもの
+pub struct CommandExecutor;
+
+impl CommandExecutor {
     /// 背景変更の実行
-    fn execute_background_change(
-        _commands: &mut Commands,
-        asset_server: &Res<AssetServer>,
-        background_query: &mut Query<&mut Sprite, (With<BackgroundImage>, Without<CharacterDisplay>)>,
-        storage: &str,
-        time: Option<u32>,
-    ) {
+    fn execute_background_change(world: &mut World, storage: &str, time: Option<u32>) {
         let image_path = format!("images/backgrounds/{}", storage);
-        let image_handle = asset_server.load(&image_path);
+        let image_handle: Handle<Image> = world.resource::<AssetServer>().load(&image_path);
 
         println!("🖼️ 背景変更: {} (時間: {:?}ms)", image_path, time);
 
         // 既存の背景を更新
-        for mut sprite in background_query.iter_mut() {
+        let mut background_query = world.query_filtered::<&mut Sprite, (With<BackgroundImage>, Without<CharacterDisplay>)>();
+        for mut sprite in background_query.iter_mut(world) {
             sprite.image = image_handle.clone();
             println!("✅ 背景スプライト更新完了");
         }
@@ -83,88 +560,77 @@ impl CommandExecutor {
 
     /// キャラクター表示の実行
     fn execute_character_show(
-        commands: &mut Commands,
-        asset_server: &Res<AssetServer>,
-        character_registry: &Res<CharacterRegistry>,
-        character_query: &mut Query<(Entity, &mut CharacterDisplay, &mut Transform, &mut Sprite)>,
+        world: &mut World,
         name: &str,
         face: Option<&str>,
         pos: Option<&CharacterPosition>,
     ) {
-        if let Some(character_info) = character_registry.get(name) {
-            let display_face = face.unwrap_or(&character_info.default_face);
-            let display_pos = pos.cloned().unwrap_or(CharacterPosition::Center);
-
-            println!("👤 キャラクター表示: {} (表情: {}, 位置: {:?})",
-                character_info.name, display_face, display_pos);
-
-            // 既存のキャラクター表示を検索
-            let mut found_existing = false;
-            for (entity, mut char_display, mut transform, mut sprite) in character_query.iter_mut() {
-                if char_display.character_id == name {
-                    // 既存キャラクターを更新
-                    char_display.current_face = display_face.to_string();
-                    char_display.position = match display_pos {
-                        CharacterPosition::Left => CharacterDisplayPosition::Left,
-                        CharacterPosition::Center => CharacterDisplayPosition::Center,
-                        CharacterPosition::Right => CharacterDisplayPosition::Right,
-                        CharacterPosition::Custom { x, y } => CharacterDisplayPosition::Custom { x, y },
-                    };
-                    char_display.is_visible = true;
-
-                    let (x, y) = char_display.position.to_screen_coords(1920.0);
-                    transform.translation.x = x;
-                    transform.translation.y = y;
-
-                    sprite.color = Color::WHITE; // 表示状態
-                    found_existing = true;
-                    println!("✅ 既存キャラクター更新: {}", character_info.name);
-                    break;
-                }
-            }
+        let Some(character_info) = world.resource::<CharacterRegistry>().get(name).cloned() else {
+            eprintln!("⚠️ 未登録キャラクター: {}", name);
+            return;
+        };
 
-            if !found_existing {
-                // 新規キャラクター作成
-                let image_handle = asset_server.load(&character_info.image_path);
-                let position = match display_pos {
-                    CharacterPosition::Left => CharacterDisplayPosition::Left,
-                    CharacterPosition::Center => CharacterDisplayPosition::Center,
-                    CharacterPosition::Right => CharacterDisplayPosition::Right,
-                    CharacterPosition::Custom { x, y } => CharacterDisplayPosition::Custom { x, y },
-                };
-                let (x, y) = position.to_screen_coords(1920.0);
-
-                commands.spawn((
-                    Sprite {
-                        image: image_handle,
-                        color: Color::WHITE,
-                        ..default()
-                    },
-                    Transform::from_xyz(x, y, -5.0).with_scale(Vec3::splat(0.8)),
-                    CharacterDisplay {
-                        character_id: name.to_string(),
-                        current_face: display_face.to_string(),
-                        position,
-                        is_visible: true,
-                    },
-                    StoryScreenElement,
-                ));
-
-                println!("✅ 新規キャラクター作成: {}", character_info.name);
+        let display_face = face.unwrap_or(&character_info.default_face);
+        let display_pos = pos.cloned().unwrap_or(CharacterPosition::Center);
+
+        println!("👤 キャラクター表示: {} (表情: {}, 位置: {:?})",
+            character_info.name, display_face, display_pos);
+
+        let position = match display_pos {
+            CharacterPosition::Left => CharacterDisplayPosition::Left,
+            CharacterPosition::Center => CharacterDisplayPosition::Center,
+            CharacterPosition::Right => CharacterDisplayPosition::Right,
+            CharacterPosition::Custom { x, y } => CharacterDisplayPosition::Custom { x, y },
+        };
+
+        // 既存のキャラクター表示を検索
+        let mut character_query = world.query::<(&mut CharacterDisplay, &mut Transform, &mut Sprite)>();
+        for (mut char_display, mut transform, mut sprite) in character_query.iter_mut(world) {
+            if char_display.character_id == name {
+                // 既存キャラクターを更新
+                char_display.current_face = display_face.to_string();
+                char_display.position = position.clone();
+                char_display.is_visible = true;
+
+                let (x, y) = char_display.position.to_screen_coords(1920.0);
+                transform.translation.x = x;
+                transform.translation.y = y;
+
+                sprite.color = Color::WHITE; // 表示状態
+                println!("✅ 既存キャラクター更新: {}", character_info.name);
+                return;
             }
-        } else {
-            eprintln!("⚠️ 未登録キャラクター: {}", name);
         }
+
+        // 新規キャラクター作成
+        let image_handle: Handle<Image> = world.resource::<AssetServer>().load(&character_info.image_path);
+        let (x, y) = position.to_screen_coords(1920.0);
+
+        world.spawn((
+            Sprite {
+                image: image_handle,
+                color: Color::WHITE,
+                ..default()
+            },
+            Transform::from_xyz(x, y, -5.0).with_scale(Vec3::splat(0.8)),
+            CharacterDisplay {
+                character_id: name.to_string(),
+                current_face: display_face.to_string(),
+                position,
+                is_visible: true,
+            },
+            StoryScreenElement,
+        ));
+
+        println!("✅ 新規キャラクター作成: {}", character_info.name);
     }
 
     /// キャラクター非表示の実行
-    fn execute_character_hide(
-        character_query: &mut Query<(Entity, &mut CharacterDisplay, &mut Transform, &mut Sprite)>,
-        name: &str,
-    ) {
+    fn execute_character_hide(world: &mut World, name: &str) {
         println!("🫥 キャラクター非表示: {}", name);
 
-        for (entity, mut char_display, mut transform, mut sprite) in character_query.iter_mut() {
+        let mut character_query = world.query::<(&mut CharacterDisplay, &mut Sprite)>();
+        for (mut char_display, mut sprite) in character_query.iter_mut(world) {
             if char_display.character_id == name {
                 char_display.is_visible = false;
                 sprite.color = Color::NONE; // 透明化
@@ -174,46 +640,159 @@ impl CommandExecutor {
         }
     }
 
-    /// BGM再生の実行
-    fn execute_bgm(
-        _commands: &mut Commands,
-        _asset_server: &Res<AssetServer>,
-        play: &str,
-        volume: Option<f32>,
-        loop_audio: Option<bool>,
-    ) {
+    /// 話者の強調表示の切り替え（現在のシーンのみ有効）
+    fn execute_speaker_focus(world: &mut World, enabled: bool) {
+        println!("🔦 話者の強調表示: {}", if enabled { "有効" } else { "無効" });
+
+        if let Some(mut scenario_state) = world.get_resource_mut::<MarkdownScenarioState>() {
+            scenario_state.is_speaker_focus_disabled = !enabled;
+        }
+    }
+
+    /// 関係値キャップの変更
+    fn execute_relationship_cap(world: &mut World, limit: i32, apply_banked: Option<bool>) {
+        println!("🔒 関係値キャップ: ±{} (超過分の反映: {:?})", limit, apply_banked);
+
+        if let Some(mut relationships) = world.get_resource_mut::<RelationshipService>() {
+            let current = relationships.cap_policy().clone();
+            relationships.set_cap_policy(RelationshipCapPolicy {
+                limit,
+                apply_banked_on_raise: apply_banked.unwrap_or(current.apply_banked_on_raise),
+            });
+        }
+    }
+
+    /// 相関図画面を開く（閉じるとストーリーに戻る）
+    fn execute_relationship_chart(world: &mut World) {
+        println!("🕸️ 相関図を表示");
+
+        if let Some(mut game_mode) = world.get_resource_mut::<GameMode>() {
+            game_mode.current_screen = GameScreen::RelationshipChart;
+        }
+    }
+
+    /// プレゼント画面を開く（閉じるとストーリーに戻る）
+    fn execute_gift(world: &mut World) {
+        println!("🎁 プレゼント画面を表示");
+
+        if let Some(mut game_mode) = world.get_resource_mut::<GameMode>() {
+            game_mode.current_screen = GameScreen::Gift;
+        }
+    }
+
+    /// ルート確定の実行（確定済みなら何もしない）
+    fn execute_route_lock(world: &mut World, character: Option<&str>) {
+        let main_character = match character {
+            Some(character) => character.to_string(),
+            None => world.get_resource::<EndingTable>().cloned().unwrap_or_default().main_character,
+        };
+
+        let Some(mut relationships) = world.get_resource_mut::<RelationshipService>() else { return; };
+        match relationships.lock_route(&main_character) {
+            Ok(lock) => {
+                for (pair, level) in lock.levels() {
+                    println!("🔐 ルート確定: {} = {:?}", pair, level);
+                }
+            }
+            Err(e) => eprintln!("⚠️ {}", e),
+        }
+    }
+
+    /// 規則表から選んだエンディングのシナリオを読み込む
+    ///
+    /// ルートが未確定の場合はこの時点の関係レベルで確定する
+    fn execute_ending(world: &mut World) {
+        let table = world.get_resource::<EndingTable>().cloned().unwrap_or_default();
+        let Some(mut relationships) = world.get_resource_mut::<RelationshipService>() else { return; };
+        if relationships.route_lock().is_none() {
+            eprintln!("⚠️ ルート未確定のままエンディングに到達したため、現時点の関係で確定します");
+            let _ = relationships.lock_route(&table.main_character);
+        }
+        let resolution = GameProgressService::new(&mut relationships).determine_ending(&table);
+
+        println!("🎬 エンディング: {} ({:?})", resolution.ending.label(), resolution.pair);
+        let Some(scenario) = resolution.scenario else {
+            eprintln!("❌ {} のシナリオが規則表にありません", resolution.ending.label());
+            return;
+        };
+
+        if let Err(e) = scenario_system::start_scenario_in_world(world, &scenario) {
+            eprintln!("❌ エンディングのシナリオを読み込めません: {}", e);
+        }
+    }
+
+    /// 別のシナリオファイルへ移動
+    fn execute_jump(world: &mut World, storage: &str) {
+        println!("↪️ シナリオ移動: {}", storage);
+
+        if let Err(e) = scenario_system::start_scenario_in_world(world, storage) {
+            eprintln!("❌ 移動先のシナリオを読み込めません: {}", e);
+        }
+    }
+
+    /// 関係値変動の実行（シナリオ上の位置を添えて履歴に記録）
+    fn execute_relationship_change(world: &mut World, change: &RelationshipChangeCommand) {
+        let RelationshipChangeCommand { a, b, delta, source, reason, notify } = change;
+        let (delta, source, notify) = (*delta, *source, *notify);
+        let location = world
+            .get_resource::<MarkdownScenarioState>()
+            .map(MarkdownScenarioState::current_location)
+            .unwrap_or_default();
+
+        let mut cause = ChangeCause::new(source, reason).at(location);
+        if !notify {
+            cause = cause.silent();
+        }
+
+        if let Some(mut relationships) = world.get_resource_mut::<RelationshipService>() {
+            let new_value = relationships.change_relationship(a, b, delta, cause);
+            println!("💞 関係値変動: {}⇔{} {:+} → {} ({})", a, b, delta, new_value, source.label());
+        }
+    }
+
+    /// BGM再生の実行（再生中のBGMは停止して差し替え）
+    fn execute_bgm(world: &mut World, play: &str, volume: Option<f32>, loop_audio: Option<bool>) {
         let audio_path = format!("sounds/bgm/{}", play);
         let final_volume = volume.unwrap_or(1.0);
         let should_loop = loop_audio.unwrap_or(true);
 
         println!("🎵 BGM再生: {} (音量: {}, ループ: {})", audio_path, final_volume, should_loop);
 
-        // TODO: Bevy Audio システムとの統合（future enhancement）
-        // let audio_handle: Handle<AudioSource> = asset_server.load(&audio_path);
-        // commands.spawn(AudioBundle {
-        //     source: audio_handle,
-        //     settings: PlaybackSettings::LOOP.with_volume(Volume::new(final_volume)),
-        // });
+        let playing: Vec<Entity> = world.query_filtered::<Entity, With<BgmChannel>>().iter(world).collect();
+        for entity in playing {
+            world.despawn(entity);
+        }
+
+        let settings = world.get_resource::<AudioSettings>().cloned().unwrap_or_default();
+        let audio_handle: Handle<AudioSource> = world.resource::<AssetServer>().load(audio_path);
+        world.spawn(audio_system::bgm_bundle(audio_handle, final_volume, should_loop, &settings));
     }
 
     /// SE再生の実行
-    fn execute_se(
-        _commands: &mut Commands,
-        _asset_server: &Res<AssetServer>,
-        play: &str,
-        volume: Option<f32>,
-    ) {
+    fn execute_se(world: &mut World, play: &str, volume: Option<f32>) {
         let audio_path = format!("sounds/se/{}", play);
         let final_volume = volume.unwrap_or(1.0);
 
         println!("🔊 SE再生: {} (音量: {})", audio_path, final_volume);
 
-        // TODO: Bevy Audio システムとの統合（future enhancement）
-        // let audio_handle: Handle<AudioSource> = asset_server.load(&audio_path);
-        // commands.spawn(AudioBundle {
-        //     source: audio_handle,
-        //     settings: PlaybackSettings::ONCE.with_volume(Volume::new(final_volume)),
-        // });
+        let settings = world.get_resource::<AudioSettings>().cloned().unwrap_or_default();
+        let audio_handle: Handle<AudioSource> = world.resource::<AssetServer>().load(audio_path);
+        world.spawn(audio_system::se_bundle(audio_handle, final_volume, &settings));
+    }
+
+    /// ボイス再生の実行（再生中のボイスは停止して差し替え）
+    fn execute_voice(world: &mut World, storage: &str) {
+        let playing: Vec<Entity> = world.query_filtered::<Entity, With<VoiceChannel>>().iter(world).collect();
+        for entity in playing {
+            world.despawn(entity);
+        }
+
+        let audio_path = audio_system::voice_path(storage);
+        println!("🗣️ ボイス再生: {}", audio_path);
+
+        let settings = world.get_resource::<AudioSettings>().cloned().unwrap_or_default();
+        let audio_handle: Handle<AudioSource> = world.resource::<AssetServer>().load(audio_path);
+        world.spawn(audio_system::voice_bundle(audio_handle, &settings));
     }
 
     /// 待機の実行
@@ -225,7 +804,7 @@ impl CommandExecutor {
     }
 
     /// 選択肢の実行
-    fn execute_choice(_commands: &mut Commands, text: &str) {
+    fn execute_choice(text: &str) {
         let choices: Vec<&str> = text.split('|').collect();
 
         println!("🎯 選択肢表示: {:?}", choices);
@@ -248,23 +827,263 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_command_execution_logging() {
-        // コマンド実行のログが正しく出力されることをテスト
-        // 実際のBevy環境なしでのテストのため、ログ出力の確認のみ
+    fn test_parse_builtin_commands() {
+        assert_eq!(
+            parse_payload("[bg storage=forest_day.jpg time=500]").ok(),
+            Some(BackgroundCommand { storage: "forest_day.jpg".to_string(), time: Some(500) })
+        );
+        assert_eq!(
+            parse_payload("[chara_show name=souma face=normal pos=left]").ok(),
+            Some(CharacterShowCommand {
+                name: "souma".to_string(),
+                face: Some("normal".to_string()),
+                pos: Some(CharacterPosition::Left),
+            })
+        );
+        assert_eq!(
+            parse_payload("[voice storage=souma_001.ogg]").ok(),
+            Some(VoiceCommand { storage: "souma_001.ogg".to_string() })
+        );
+
+        assert!(parse_payload::<VoiceCommand>("[voice]").is_err());
+        assert!(parse_payload::<WaitCommand>("[wait time=soon]").is_err());
+    }
+
+    /// 背景変更を差し替えるハンドラ（背景ファイル名だけを記録する）
+    struct RecordingBackgroundHandler;
+
+    #[derive(Resource, Default)]
+    struct RecordedBackgrounds(Vec<String>);
+
+    impl SceneCommandHandler for RecordingBackgroundHandler {
+        fn name(&self) -> &str {
+            "bg"
+        }
+
+        fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+            BackgroundCommandHandler.parse(params)
+        }
+
+        fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+            if let Some(BackgroundCommand { storage, .. }) = command.payload::<BackgroundCommand>() {
+                let storage = storage.clone();
+                commands.queue(move |world: &mut World| {
+                    world.resource_mut::<RecordedBackgrounds>().0.push(storage);
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn test_builtin_handler_can_be_replaced() {
+        let mut registry = game_command_registry();
+        registry.register(RecordingBackgroundHandler);
+
+        let mut world = World::new();
+        world.init_resource::<RecordedBackgrounds>();
+
+        let command = registry.parse("[bg storage=ruins.png]").unwrap();
+        registry.execute(&command, &mut world.commands());
+        world.flush();
+
+        assert_eq!(world.resource::<RecordedBackgrounds>().0, vec!["ruins.png".to_string()]);
+    }
+
+    /// 登録済みコマンドを解析して内容を取り出す
+    fn parse_payload<P: Clone + crate::domain::command_registry::CommandPayload>(command_str: &str) -> Result<P, ParseError> {
+        let command = game_command_registry().parse(command_str)?;
+        Ok(command.payload::<P>().expect("コマンドの内容の型が違います").clone())
+    }
+
+    #[test]
+    fn test_parse_speaker_focus() {
+        assert_eq!(parse_payload("[speaker_focus enabled=false]").ok(), Some(SpeakerFocusCommand { enabled: false }));
+        assert!(parse_payload::<SpeakerFocusCommand>("[speaker_focus enabled=maybe]").is_err());
+        assert!(parse_payload::<SpeakerFocusCommand>("[speaker_focus]").is_err());
+    }
+
+    #[test]
+    fn test_parse_relationship_cap() {
+        assert_eq!(
+            parse_payload("[relationship_cap phase=mid apply_banked=true]").ok(),
+            Some(RelationshipCapCommand { limit: 50, apply_banked: Some(true) })
+        );
+        assert_eq!(
+            parse_payload("[relationship_cap limit=±100]").ok(),
+            Some(RelationshipCapCommand { limit: 100, apply_banked: None })
+        );
+
+        assert!(parse_payload::<RelationshipCapCommand>("[relationship_cap phase=final]").is_err());
+        assert!(parse_payload::<RelationshipCapCommand>("[relationship_cap limit=150]").is_err());
+        assert!(parse_payload::<RelationshipCapCommand>("[relationship_cap]").is_err());
+    }
+
+    #[test]
+    fn test_relationship_cap_updates_shared_service() {
+        let registry = game_command_registry();
+        let mut world = World::new();
+        world.insert_resource(RelationshipService::new());
+        world.resource_mut::<RelationshipService>().set_cap_policy(RelationshipCapPolicy::for_phase(CapPhase::Early));
+
+        let command = registry.parse("[relationship_cap limit=60 apply_banked=false]").unwrap();
+        registry.execute(&command, &mut world.commands());
+        world.flush();
+
+        // 関係値の変更が参照するのと同じリソースのキャップが変わる
+        let mut relationships = world.resource_mut::<RelationshipService>();
+        assert_eq!(relationships.cap_policy(), &RelationshipCapPolicy { limit: 60, apply_banked_on_raise: false });
+        assert_eq!(relationships.modify_relationship("souma", "yuzuki", 80), 60);
+    }
+
+    #[test]
+    fn test_parse_relationship_change() {
+        let change: RelationshipChangeCommand =
+            parse_payload("[relationship a=souma b=yuzuki delta=+10 source=choice reason=約束 notify=false]").unwrap();
+        assert_eq!(change, RelationshipChangeCommand {
+            a: "souma".to_string(),
+            b: "yuzuki".to_string(),
+            delta: 10,
+            source: ChangeSource::Choice,
+            reason: "約束".to_string(),
+            notify: false,
+        });
+
+        let change: RelationshipChangeCommand = parse_payload("[relationship a=souma b=kai delta=-5]").unwrap();
+        assert_eq!(change.source, ChangeSource::Event);
+        assert!(change.notify);
+
+        for invalid in [
+            "[relationship a=souma delta=5]",
+            "[relationship a=souma b=souma delta=5]",
+            "[relationship a=souma b=kai delta=many]",
+            "[relationship a=souma b=kai delta=5 source=magic]",
+        ] {
+            assert!(parse_payload::<RelationshipChangeCommand>(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_parse_screen_and_ending_commands() {
+        assert_eq!(parse_payload("[relationship_chart]").ok(), Some(RelationshipChartCommand));
+        assert_eq!(parse_payload("[gift]").ok(), Some(GiftCommand));
+        assert_eq!(
+            parse_payload("[route_lock character=souma]").ok(),
+            Some(RouteLockCommand { character: Some("souma".to_string()) })
+        );
+        assert_eq!(parse_payload("[route_lock]").ok(), Some(RouteLockCommand { character: None }));
+        assert_eq!(parse_payload("[ending]").ok(), Some(EndingCommand));
+        assert_eq!(
+            parse_payload("[jump storage=hub_saitou.md]").ok(),
+            Some(JumpCommand { storage: "hub_saitou.md".to_string() })
+        );
+        assert!(parse_payload::<JumpCommand>("[jump]").is_err());
+    }
+
+    /// シナリオの実行に必要なリソースを持つワールド
+    fn scenario_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(game_command_registry());
+        world.init_resource::<MarkdownScenarioState>();
+        world.insert_resource(RelationshipService::new());
+        world
+    }
+
+    /// 現在のシーンのコマンドを実行
+    fn run_current_scene_commands(world: &mut World) {
+        let scene_commands = world.resource::<MarkdownScenarioState>().get_current_scene_commands();
+        world.resource_scope(|world, registry: Mut<SceneCommandRegistry>| {
+            for command in &scene_commands {
+                registry.execute(command, &mut world.commands());
+            }
+        });
+        world.flush();
+    }
 
-        let bg_command = SceneCommand::Background {
-            storage: "test_bg.jpg".to_string(),
-            time: Some(1000),
+    #[test]
+    fn test_opening_scenario_jumps_to_hub() {
+        let mut world = scenario_world();
+        scenario_system::start_scenario_in_world(&mut world, scenario_system::OPENING_SCENARIO).unwrap();
+
+        // 最後のシーンまで進めると拠点（サイトウ）に移動する
+        let scene_count = world.resource::<MarkdownScenarioState>().current_scenario.as_ref().unwrap().scenes.len();
+        world.resource_mut::<MarkdownScenarioState>().current_scene_index = scene_count - 1;
+        run_current_scene_commands(&mut world);
+
+        let state = world.resource::<MarkdownScenarioState>();
+        assert_eq!(state.current_scenario_path.as_deref(), Some("hub_saitou.md"));
+        assert_eq!(state.current_scene_index, 0);
+        let hub = state.current_scenario.as_ref().unwrap();
+        assert!(hub.scenes.iter().flat_map(|scene| &scene.commands).any(|command| command.name() == "relationship_chart"));
+    }
+
+    #[test]
+    fn test_hub_gift_scene_opens_gift_screen() {
+        let mut world = scenario_world();
+        world.init_resource::<GameMode>();
+        scenario_system::start_scenario_in_world(&mut world, "hub_saitou.md").unwrap();
+
+        let gift_scene = {
+            let hub = world.resource::<MarkdownScenarioState>().current_scenario.clone().unwrap();
+            let index = hub
+                .scenes
+                .iter()
+                .position(|scene| scene.commands.iter().any(|command| command.name() == "gift"))
+                .unwrap();
+            // コマンドはシーンの先頭で実行されるため、サイトウの誘いは画面が開く前のシーンにある
+            assert!(hub.scenes[index - 1].dialogue_blocks.iter().any(|block| block.text.contains("渡してみるかい")));
+            index
         };
 
-        // 実際の実行はBevyコンテキストが必要なため、
-        // ここではコマンドの構造確認のみ行う
-        match bg_command {
-            SceneCommand::Background { storage, time } => {
-                assert_eq!(storage, "test_bg.jpg");
-                assert_eq!(time, Some(1000));
+        world.resource_mut::<MarkdownScenarioState>().current_scene_index = gift_scene;
+        run_current_scene_commands(&mut world);
+        assert_eq!(world.resource::<GameMode>().current_screen, GameScreen::Gift);
+    }
+
+    /// 拠点から最終章へ進み、ルート確定とエンディングまでシーンを順に実行
+    fn play_finale_from_hub(world: &mut World) {
+        scenario_system::start_scenario_in_world(world, "hub_saitou.md").unwrap();
+        let scene_count = world.resource::<MarkdownScenarioState>().current_scenario.as_ref().unwrap().scenes.len();
+        world.resource_mut::<MarkdownScenarioState>().current_scene_index = scene_count - 1;
+        run_current_scene_commands(world);
+
+        let in_finale = |world: &World| {
+            world.resource::<MarkdownScenarioState>().current_scenario_path.as_deref() == Some("finale.md")
+        };
+        while in_finale(world) {
+            run_current_scene_commands(world);
+            if !in_finale(world) || !world.resource_mut::<MarkdownScenarioState>().advance_scene() {
+                break;
             }
-            _ => panic!("期待していたBackgroundコマンドではありません"),
         }
     }
+
+    #[test]
+    fn test_finale_plays_ending_from_table() {
+        let table = crate::infrastructure::relationship_data::load_ending_table(std::path::Path::new(
+            crate::infrastructure::relationship_data::ENDING_TABLE_PATH,
+        ));
+
+        // ソウマ × ユズキが親密 → 個別のトゥルーエンド
+        let mut world = scenario_world();
+        world.insert_resource(table.clone());
+        world.resource_mut::<RelationshipService>().modify_relationship("souma", "yuzuki", 60);
+        play_finale_from_hub(&mut world);
+
+        assert!(world.resource::<RelationshipService>().route_lock().is_some());
+        assert_eq!(
+            world.resource::<MarkdownScenarioState>().current_scenario_path.as_deref(),
+            Some("endings/souma_yuzuki_true.md")
+        );
+
+        // ソウマ × カイが険悪 → 既定のバッドエンド
+        let mut world = scenario_world();
+        world.insert_resource(table);
+        world.resource_mut::<RelationshipService>().modify_relationship("souma", "kai", -60);
+        play_finale_from_hub(&mut world);
+
+        assert_eq!(
+            world.resource::<MarkdownScenarioState>().current_scenario_path.as_deref(),
+            Some("endings/bad.md")
+        );
+    }
 }
@@ -3,8 +3,12 @@
 //! このモジュールには以下が含まれます：
 //! - シナリオ実行システム（scenario_system）
 //! - コマンド実行システム（command_executor）
+//! - オーディオ再生制御（audio_system）
 //! - アプリケーションサービス（services）
+//! - 関係値の変動イベント送出（relationship_system）
 
 pub mod scenario_system;
 pub mod command_executor;
+pub mod audio_system;
 pub mod services;
+pub mod relationship_system;
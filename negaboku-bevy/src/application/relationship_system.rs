@@ -0,0 +1,24 @@
+//! 関係値システム - 関係値サービスとECSの橋渡し
+//!
+//! # 責務
+//! - `RelationshipService` に溜まった変動を `RelationshipChanged` イベントとして送出
+
+use bevy::prelude::*;
+use crate::application::services::{RelationshipChanged, RelationshipService};
+
+/// 関係値の変動イベント送出システム
+pub fn relationship_event_system(
+    mut relationships: ResMut<RelationshipService>,
+    mut changed_events: EventWriter<RelationshipChanged>,
+) {
+    // 変動がないフレームでは変更検知を発生させない
+    if !relationships.has_pending_events() {
+        return;
+    }
+
+    for event in relationships.drain_events() {
+        debug!("関係値変動: {}⇔{} {} → {} ({:?} → {:?})",
+            event.a, event.b, event.old, event.new, event.old_level, event.new_level);
+        changed_events.send(event);
+    }
+}
@@ -3,37 +3,60 @@
 //! # 責務
 //! - シナリオの進行管理
 //! - コマンドとダイアログの順次実行
+//! - シナリオファイルの読み込みと開始（ゲーム開始時・`[jump]`・エンディング）
 //! - 既存のVNシステムとの統合
 
 use bevy::prelude::*;
-use crate::domain::scenario::{ScenarioFile, Scene, DialogueBlock};
-use crate::domain::character::{CharacterDisplay, CharacterRegistry};
-use crate::application::command_executor::CommandExecutor;
+use crate::domain::scenario::{ScenarioFile, Scene, SceneCommand, DialogueBlock};
+use crate::domain::command_registry::SceneCommandRegistry;
+use crate::domain::character::CharacterRegistry;
+use crate::domain::relationship::RelationshipCapPolicy;
+use crate::domain::relationship_history::ScenarioLocation;
+use crate::application::services::RelationshipService;
+use crate::domain::localization::Localization;
 use crate::infrastructure::scenario_loader::ScenarioLoader;
 
 // main.rsの構造体を参照するため
-use crate::{GameMode, VNDialogue, VNCharacterName};
+use crate::{GameMode, GameScreen, VNDialogue, VNCharacterName};
+
+/// シナリオファイルの配置ディレクトリ
+pub const SCENARIO_DIRECTORY: &str = "assets/scenarios";
+
+/// 「はじめから」で読み込むシナリオ（`assets/scenarios/` からの相対パス）
+pub const OPENING_SCENARIO: &str = "test_scene01.md";
+
+/// 再開時に保存位置までのシーンから再実行するコマンド（背景・立ち絵・BGM を保存時の状態に戻す）
+const RESUME_REPLAY_COMMANDS: [&str; 4] = ["bg", "chara_show", "chara_hide", "bgm"];
 
 /// マークダウンベースのシナリオ進行状態
 #[derive(Resource, Default)]
 pub struct MarkdownScenarioState {
     pub current_scenario: Option<ScenarioFile>,
+    /// 現在のシナリオのファイル（`assets/scenarios/` からの相対パス、ファイル以外から読み込んだ場合は None）
+    pub current_scenario_path: Option<String>,
     pub current_scene_index: usize,
     pub current_dialogue_index: usize,
     pub is_scene_commands_executed: bool,
     pub is_waiting_for_input: bool,
     pub has_attempted_load: bool,  // 読み込み試行済みフラグ
+    /// フロントマターの preload 指定で読み込んだアセット（シナリオ終了まで保持）
+    pub preloaded_assets: Vec<UntypedHandle>,
+    /// `[speaker_focus enabled=false]` で現在のシーンの話者強調を無効化中（シーン切り替えで解除）
+    pub is_speaker_focus_disabled: bool,
 }
 
 impl MarkdownScenarioState {
     /// 新しいシナリオファイルを読み込み
     pub fn load_scenario(&mut self, scenario_file: ScenarioFile) {
         self.current_scenario = Some(scenario_file);
+        self.current_scenario_path = None;
         self.current_scene_index = 0;
         self.current_dialogue_index = 0;
         self.is_scene_commands_executed = false;
         self.is_waiting_for_input = false;
         self.has_attempted_load = true;  // 読み込み完了をマーク
+        self.preloaded_assets.clear();
+        self.is_speaker_focus_disabled = false;
 
         println!("📖 新しいシナリオを読み込みました");
         if let Some(scenario) = &self.current_scenario {
@@ -42,6 +65,35 @@ impl MarkdownScenarioState {
         }
     }
 
+    /// シナリオファイルから読み込んだシナリオを開始
+    ///
+    /// フロントマターで章の関係値キャップが指定されていれば適用し、アセットの事前読み込みを始める
+    pub fn start_scenario(
+        &mut self,
+        scenario: &str,
+        scenario_file: ScenarioFile,
+        relationships: &mut RelationshipService,
+        asset_server: Option<&AssetServer>,
+    ) {
+        if let Some(cap) = scenario_file.metadata.relationship_cap {
+            println!("🔒 章の関係値キャップ: ±{}", cap);
+            let policy = RelationshipCapPolicy {
+                limit: cap,
+                ..relationships.cap_policy().clone()
+            };
+            relationships.set_cap_policy(policy);
+        }
+
+        let stats = ScenarioLoader::get_scenario_stats(&scenario_file);
+        println!("📊 シナリオ統計: {:?}", stats);
+
+        self.load_scenario(scenario_file);
+        self.current_scenario_path = Some(scenario.to_string());
+        if let Some(asset_server) = asset_server {
+            self.preload_assets(asset_server);
+        }
+    }
+
     /// 現在のシーンを取得
     pub fn get_current_scene(&self) -> Option<&Scene> {
         self.current_scenario
@@ -50,12 +102,93 @@ impl MarkdownScenarioState {
             .get(self.current_scene_index)
     }
 
+    /// 現在のシーンで実行するコマンド一覧
+    ///
+    /// 最初のシーンではフロントマターの既定背景・既定BGMを先頭に加える
+    /// （シーン自身が同種のコマンドを持つ場合はそちらを優先）
+    pub fn get_current_scene_commands(&self) -> Vec<SceneCommand> {
+        let Some(scene) = self.get_current_scene() else {
+            return Vec::new();
+        };
+
+        let mut commands = Vec::new();
+        if self.current_scene_index == 0 {
+            if let Some(scenario) = &self.current_scenario {
+                commands.extend(
+                    scenario.metadata.default_commands()
+                        .into_iter()
+                        .filter(|default| !scene.commands.iter().any(|c| c.name() == default.name())),
+                );
+            }
+        }
+        commands.extend(scene.commands.iter().cloned());
+        commands
+    }
+
+    /// セーブした位置（シーン・ダイアログの番号）から再開
+    ///
+    /// 保存位置のシーンのコマンドは保存前に実行済みのため再実行しない。
+    /// 代わりに表示を戻すためのコマンド（保存位置までの背景・立ち絵・BGM と、保存位置のシーンの話者強調の切り替え）を返す
+    pub fn resume_at(&mut self, scene_index: usize, dialogue_index: usize) -> Result<Vec<SceneCommand>, String> {
+        let scenario = self.current_scenario.as_ref().ok_or("シナリオが読み込まれていません")?;
+        let scene = scenario
+            .scenes
+            .get(scene_index)
+            .ok_or_else(|| format!("保存位置のシーンがありません: {}", scene_index))?;
+        if dialogue_index > 0 && dialogue_index >= scene.dialogue_blocks.len() {
+            return Err(format!("保存位置のダイアログがありません: {}-{}", scene_index, dialogue_index));
+        }
+
+        let replay: Vec<SceneCommand> = scenario
+            .metadata
+            .default_commands()
+            .into_iter()
+            .chain(scenario.scenes[..=scene_index].iter().flat_map(|scene| scene.commands.iter().cloned()))
+            .filter(|command| RESUME_REPLAY_COMMANDS.contains(&command.name()))
+            .chain(scene.commands.iter().filter(|command| command.name() == "speaker_focus").cloned())
+            .collect();
+
+        self.current_scene_index = scene_index;
+        self.current_dialogue_index = dialogue_index;
+        self.is_scene_commands_executed = true;
+        self.is_speaker_focus_disabled = false;
+        Ok(replay)
+    }
+
+    /// フロントマターの preload 指定に従ってアセットを事前読み込み
+    pub fn preload_assets(&mut self, asset_server: &AssetServer) {
+        let Some(scenario) = &self.current_scenario else {
+            return;
+        };
+
+        let metadata = &scenario.metadata;
+        let images = metadata.preload_images.iter()
+            .map(|path| asset_server.load::<Image>(format!("images/{}", path)).untyped());
+        let sounds = metadata.preload_sounds.iter()
+            .map(|path| asset_server.load::<AudioSource>(format!("sounds/{}", path)).untyped());
+        self.preloaded_assets = images.chain(sounds).collect();
+
+        if !self.preloaded_assets.is_empty() {
+            println!("📦 事前読み込み開始: {} 件", self.preloaded_assets.len());
+        }
+    }
+
     /// 現在のダイアログを取得
     pub fn get_current_dialogue(&self) -> Option<&DialogueBlock> {
         let scene = self.get_current_scene()?;
         scene.dialogue_blocks.get(self.current_dialogue_index)
     }
 
+    /// 現在のシナリオ上の位置（章ID・表示中のセリフID）
+    pub fn current_location(&self) -> ScenarioLocation {
+        ScenarioLocation {
+            chapter_id: self.current_scenario.as_ref().and_then(|scenario| scenario.metadata.chapter_id.clone()),
+            line_id: self.get_current_dialogue()
+                .map(|block| block.line_id.clone())
+                .filter(|line_id| !line_id.is_empty()),
+        }
+    }
+
     /// 次のダイアログへ進む
     pub fn advance_dialogue(&mut self) -> bool {
         let scene_dialogue_count = if let Some(scene) = self.get_current_scene() {
@@ -84,6 +217,7 @@ impl MarkdownScenarioState {
                 self.current_scene_index += 1;
                 self.current_dialogue_index = 0;
                 self.is_scene_commands_executed = false;
+                self.is_speaker_focus_disabled = false;
                 println!("🎬 シーン進行: {}/{}",
                     self.current_scene_index + 1,
                     scenario.scenes.len()
@@ -110,10 +244,8 @@ impl MarkdownScenarioState {
 pub fn markdown_scenario_system(
     mut commands: Commands,
     mut scenario_state: ResMut<MarkdownScenarioState>,
-    asset_server: Res<AssetServer>,
-    character_registry: Res<CharacterRegistry>,
-    mut background_query: Query<&mut Sprite, (With<crate::application::command_executor::BackgroundImage>, Without<CharacterDisplay>)>,
-    mut character_query: Query<(Entity, &mut CharacterDisplay, &mut Transform, &mut Sprite)>,
+    command_registry: Res<SceneCommandRegistry>,
+    localization: Res<Localization>,
     mut vn_dialogue_query: Query<&mut VNDialogue>,
     mut character_name_query: Query<&mut VNCharacterName>,
 ) {
@@ -123,18 +255,12 @@ pub fn markdown_scenario_system(
 
     // シーンコマンドの実行（シーン開始時に1回だけ）
     if !scenario_state.is_scene_commands_executed {
-        if let Some(scene) = scenario_state.get_current_scene() {
-            println!("🎬 シーンコマンド実行開始: {} 個", scene.commands.len());
-
-            for command in &scene.commands {
-                CommandExecutor::execute_command(
-                    command,
-                    &mut commands,
-                    &asset_server,
-                    &character_registry,
-                    &mut background_query,
-                    &mut character_query,
-                );
+        if scenario_state.get_current_scene().is_some() {
+            let scene_commands = scenario_state.get_current_scene_commands();
+            println!("🎬 シーンコマンド実行開始: {} 個", scene_commands.len());
+
+            for command in &scene_commands {
+                command_registry.execute(command, &mut commands);
             }
 
             scenario_state.is_scene_commands_executed = true;
@@ -144,18 +270,18 @@ pub fn markdown_scenario_system(
 
     // 現在のダイアログを既存のVNシステムに設定（テキストが変更された場合のみ）
     if let Some(current_dialogue) = scenario_state.get_current_dialogue() {
+        // 表示言語の翻訳（なければ原文）
+        let dialogue_text = localization.line_text(current_dialogue);
+
         // VNDialogue コンポーネントを更新（テキストが異なる場合のみ）
         let mut found_vn_dialogue = false;
         for mut vn_dialogue in vn_dialogue_query.iter_mut() {
             found_vn_dialogue = true;
 
-            if vn_dialogue.full_text != current_dialogue.text {
-                vn_dialogue.full_text = current_dialogue.text.clone();
-                vn_dialogue.current_char = 0;
-                vn_dialogue.is_complete = false;
-                vn_dialogue.timer.reset();
+            if vn_dialogue.full_text != dialogue_text {
+                vn_dialogue.set_text(dialogue_text.to_string());
 
-                println!("💬 マークダウンシナリオ→VNDialogue更新: {}", current_dialogue.text);
+                println!("💬 マークダウンシナリオ→VNDialogue更新: {}", dialogue_text);
                 break;
             }
         }
@@ -167,9 +293,10 @@ pub fn markdown_scenario_system(
         // キャラクター名を更新（名前が異なる場合のみ）
         for mut character_name in character_name_query.iter_mut() {
             let new_name = current_dialogue.speaker.as_deref().unwrap_or("");
-            if character_name.name != new_name {
+            if character_name.name != new_name || character_name.character_id != current_dialogue.character_id {
                 character_name.name = new_name.to_string();
-                println!("👤 スピーカー更新: {}", new_name);
+                character_name.character_id = current_dialogue.character_id.clone();
+                println!("👤 スピーカー更新: {} ({:?})", new_name, current_dialogue.character_id);
                 break;
             }
         }
@@ -184,7 +311,11 @@ pub fn markdown_scenario_input_system(
     mut vn_dialogue_query: Query<&mut VNDialogue>,
     game_mode: Res<GameMode>,
 ) {
-    if !game_mode.is_story_mode || scenario_state.current_scenario.is_none() {
+    // 相関図などストーリーの上に開いた画面の操作中は進めない
+    if !game_mode.is_story_mode
+        || game_mode.current_screen != GameScreen::Story
+        || scenario_state.current_scenario.is_none()
+    {
         return;
     }
 
@@ -200,13 +331,39 @@ pub fn markdown_scenario_input_system(
     let mut found_incomplete = false;
 
     for mut vn_dialogue in vn_dialogue_query.iter_mut() {
+        if vn_dialogue.is_waiting_click {
+            // {p} のクリック待ち中：続きの表示を再開する
+            vn_dialogue.is_waiting_click = false;
+            found_incomplete = true;
+            dialogue_complete = false;
+            println!("▶️ クリック待ち解除");
+            break;
+        }
+
         if !vn_dialogue.is_complete {
-            // タイピング中の場合：即座に完了状態にする
-            vn_dialogue.current_char = vn_dialogue.full_text.len();
-            vn_dialogue.is_complete = true;
+            // タイピング中の場合：次のクリック待ち（なければ末尾）まで即座に表示する
+            let search_from = if vn_dialogue.paused_at == Some(vn_dialogue.current_char) {
+                vn_dialogue.current_char + 1
+            } else {
+                vn_dialogue.current_char
+            };
+            vn_dialogue.pause_timer = None;
+
+            match vn_dialogue.timeline.next_click_wait_from(search_from) {
+                Some(position) => {
+                    vn_dialogue.current_char = position;
+                    vn_dialogue.paused_at = Some(position);
+                    vn_dialogue.is_waiting_click = true;
+                    println!("⏭️ クリック待ち位置まで表示: {}", position);
+                }
+                None => {
+                    vn_dialogue.current_char = vn_dialogue.timeline.len();
+                    vn_dialogue.is_complete = true;
+                    println!("⏭️ ダイアログ表示完了: {}", vn_dialogue.full_text);
+                }
+            }
             found_incomplete = true;
             dialogue_complete = false;
-            println!("⏭️ ダイアログ表示完了: {}", vn_dialogue.full_text);
             break;
         }
     }
@@ -220,20 +377,61 @@ pub fn markdown_scenario_input_system(
     }
 }
 
+/// シナリオファイルを読み込み、話者をキャラクター登録と照合（未登録の話者は警告のみ）
+///
+/// `scenario` は `assets/scenarios/` からの相対パス
+pub fn load_scenario_file(
+    scenario: &str,
+    command_registry: &SceneCommandRegistry,
+    character_registry: Option<&CharacterRegistry>,
+) -> Result<ScenarioFile, String> {
+    let path = format!("{}/{}", SCENARIO_DIRECTORY, scenario);
+    let mut scenario_file = ScenarioLoader::load_from_file(&path, command_registry)
+        .map_err(|e| format!("シナリオを読み込めません {}: {}", path, e))?;
+
+    if let Some(character_registry) = character_registry {
+        for speaker in scenario_file.resolve_speakers(character_registry) {
+            eprintln!("⚠️ 未登録の話者: {}（CharacterRegistry に ID・表示名・別名が見つかりません）", speaker);
+        }
+    }
+    Ok(scenario_file)
+}
+
+/// ワールドのリソースを使ってシナリオファイルを読み込み、開始する（`[jump]`・エンディング用）
+pub fn start_scenario_in_world(world: &mut World, scenario: &str) -> Result<(), String> {
+    let scenario_file = load_scenario_file(
+        scenario,
+        world.resource::<SceneCommandRegistry>(),
+        world.get_resource::<CharacterRegistry>(),
+    )?;
+    let asset_server = world.get_resource::<AssetServer>().cloned();
+
+    world.resource_scope(|world, mut relationships: Mut<RelationshipService>| {
+        world.resource_mut::<MarkdownScenarioState>().start_scenario(
+            scenario,
+            scenario_file,
+            &mut relationships,
+            asset_server.as_ref(),
+        );
+    });
+    Ok(())
+}
+
 /// シナリオファイル読み込みシステム（ゲーム開始時）
 pub fn load_markdown_scenario_system(
     mut scenario_state: ResMut<MarkdownScenarioState>,
+    command_registry: Res<SceneCommandRegistry>,
+    character_registry: Res<CharacterRegistry>,
+    asset_server: Res<AssetServer>,
     game_mode: Res<GameMode>,
+    mut relationships: ResMut<RelationshipService>,
 ) {
     // ストーリーモードに切り替わった瞬間にシナリオを読み込み（毎フレームチェック）
     if game_mode.is_story_mode && scenario_state.current_scenario.is_none() {
         println!("✅ ストーリーモード開始 - シナリオ読み込み開始");
-        match ScenarioLoader::load_from_file("assets/scenarios/test_scene01.md") {
+        match load_scenario_file(OPENING_SCENARIO, &command_registry, Some(&character_registry)) {
             Ok(scenario_file) => {
-                let stats = ScenarioLoader::get_scenario_stats(&scenario_file);
-                println!("📊 シナリオ統計: {:?}", stats);
-
-                scenario_state.load_scenario(scenario_file);
+                scenario_state.start_scenario(OPENING_SCENARIO, scenario_file, &mut relationships, Some(&asset_server));
             }
             Err(error) => {
                 eprintln!("❌ シナリオファイル読み込みエラー: {}", error);
@@ -295,6 +493,75 @@ mod tests {
             state.current_scenario.as_ref().unwrap().scenes.len());
         // シナリオ完了の判定はadvance_dialogueの戻り値で行う
     }
+
+    #[test]
+    fn test_front_matter_default_commands_on_first_scene() {
+        let mut state = MarkdownScenarioState::default();
+        let content = r#"---
+bgm: ruins_theme.mp3
+background: ruins_entrance.png
+---
+
+[bg storage=black.png]
+
+**テスト**「最初のシーン」
+
+---
+
+**テスト**「次のシーン」
+"#;
+
+        state.load_scenario(ScenarioLoader::parse_markdown(content));
+
+        // シーン側の bg が優先され、既定BGMだけが先頭に追加される
+        let first = state.get_current_scene_commands();
+        let names: Vec<&str> = first.iter().map(|command| command.name()).collect();
+        assert_eq!(names, vec!["bgm", "bg"]);
+
+        // 2つ目以降のシーンには既定コマンドを追加しない
+        state.advance_dialogue();
+        assert!(state.get_current_scene_commands().is_empty());
+    }
+
+    #[test]
+    fn test_resume_at_saved_position() {
+        let mut state = MarkdownScenarioState::default();
+        let content = r#"---
+bgm: ruins_theme.mp3
+---
+
+[bg storage=ruins_entrance.png]
+[chara_show name=souma]
+
+**テスト**「最初のシーン」
+
+---
+
+[se play=hit.wav]
+[chara_hide name=souma]
+
+**テスト**「保存前のセリフ」
+
+**テスト**「保存したセリフ」
+
+---
+
+[bg storage=black.png]
+
+**テスト**「保存後のシーン」
+"#;
+        state.load_scenario(ScenarioLoader::parse_markdown(content));
+
+        assert!(state.resume_at(3, 0).is_err());
+        assert!(state.resume_at(1, 2).is_err());
+
+        // 保存位置までの表示系コマンドだけを返し、保存位置のシーンのコマンドは実行済みとする
+        let replay = state.resume_at(1, 1).unwrap();
+        let names: Vec<&str> = replay.iter().map(|command| command.name()).collect();
+        assert_eq!(names, vec!["bgm", "bg", "chara_show", "chara_hide"]);
+        assert!(state.is_scene_commands_executed);
+        assert_eq!(state.get_current_dialogue().unwrap().text, "保存したセリフ");
+    }
 }
 
 /// シナリオ進行管理システム（旧システム用、マークダウンシナリオが無効の場合のみ動作）
@@ -316,10 +583,7 @@ pub fn scenario_progression_system(
         if scenario_state.current_index == 0 && !scenario_state.lines.is_empty() {
             for mut dialogue in vn_dialogue_query.iter_mut() {
                 if dialogue.full_text != scenario_state.lines[0] {
-                    dialogue.full_text = scenario_state.lines[0].clone();
-                    dialogue.current_char = 0;
-                    dialogue.is_complete = false;
-                    dialogue.timer.reset();
+                    dialogue.set_text(scenario_state.lines[0].clone());
                     println!("初回テキスト設定: {}", scenario_state.lines[0]);
                     break;
                 }
@@ -2,26 +2,109 @@
 //!
 //! ドメインロジックを統合し、ユースケースを実現するサービス
 
-use crate::domain::relationship::Relationship;
+use bevy::prelude::*;
+use crate::domain::relationship::{
+    PairDefinition, PairKey, Relationship, RelationshipCapPolicy, RelationshipLevel, RelationshipLevelConfig,
+    RelationshipPairTable,
+};
+use crate::domain::relationship_history::{
+    ChangeCause, ChangeSource, RelationshipChange, RelationshipHistory, ScenarioLocation,
+};
+use crate::domain::ending::{EndingResolution, EndingTable, RouteLock};
+use crate::domain::jealousy::{RelationshipCondition, TriangleRuleTable};
+use crate::domain::gift::{GiftCatalog, GiftItem, GiftPreferences, GiftReaction, PreferenceJournal};
 use crate::domain::battle::{BattleParty, BattleSkill, BattleResult, SkillType};
 use crate::domain::character::{Character, CharacterRegistry};
 
-/// 関係値管理サービス
-#[derive(Debug)]
+/// 関係値管理サービス（Bevy Resource）
+///
+/// ストーリー・戦闘・セーブ・相関図が共有する唯一の関係値。
+/// 各サービスは自前の複製を持たず、このリソースを参照・変更する
+#[derive(Debug, Resource)]
 pub struct RelationshipService {
-    relationships: std::collections::HashMap<String, Relationship>,
+    relationships: std::collections::HashMap<PairKey, Relationship>,
+    /// 章進行による関係値キャップ
+    cap_policy: RelationshipCapPolicy,
+    /// 関係レベルの判定基準
+    level_config: RelationshipLevelConfig,
+    /// ペア定義（初期値・推奨ペアなど）
+    pair_table: RelationshipPairTable,
+    /// 嫉妬の三角関係の規則
+    triangle_rules: TriangleRuleTable,
+    /// 関係値の変動履歴
+    history: RelationshipHistory,
+    /// まだ送出していない変動イベント
+    pending_events: Vec<RelationshipChanged>,
+    /// 確定したルート（一度確定したら変更しない）
+    route_lock: Option<RouteLock>,
 }
 
-/// 戦闘管理サービス
-#[derive(Debug)]
-pub struct BattleService {
-    relationship_service: RelationshipService,
+/// 関係値が変化したことを通知するイベント
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct RelationshipChanged {
+    pub a: String,
+    pub b: String,
+    pub old: i32,
+    pub new: i32,
+    pub old_level: RelationshipLevel,
+    pub new_level: RelationshipLevel,
+    pub reason: String,
+    pub source: ChangeSource,
+    /// 画面に通知するか（シナリオの `notify=false` で抑止）
+    pub notify: bool,
+}
+
+impl RelationshipChanged {
+    /// 実際に反映された変動量
+    pub fn delta(&self) -> i32 {
+        self.new - self.old
+    }
+
+    /// 関係レベルが変わったか（技の解放・封印が起きる）
+    pub fn is_level_changed(&self) -> bool {
+        self.old_level != self.new_level
+    }
+}
+
+/// プレゼント管理サービス（Bevy Resource）
+///
+/// 好みはプレイヤーには見せず、渡したときの反応で判明したものだけを手帳に記録する
+#[derive(Debug, Resource)]
+pub struct GiftService {
+    /// プレゼントを渡す側（主人公）
+    giver: String,
+    catalog: GiftCatalog,
+    preferences: GiftPreferences,
+    /// 判明した好み
+    journal: PreferenceJournal,
 }
 
+/// プレゼントを渡した結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct GiftOutcome {
+    pub receiver: String,
+    pub item: GiftItem,
+    pub reaction: GiftReaction,
+    pub old_value: i32,
+    pub new_value: i32,
+    /// この反応で初めて好みが判明したか
+    pub newly_learned: bool,
+}
+
+/// 戦闘管理サービス
+///
+/// 関係値は持たず、共有の `RelationshipService` を受け取って読み書きする
+#[derive(Debug, Default)]
+pub struct BattleService;
+
 /// ゲーム進行管理サービス
+///
+/// 関係値は持たず、共有の `RelationshipService` を借りて読み書きする。
+/// 戦闘も同じ `relationship_service` を参照するため、ストーリーと戦闘の関係値は常に一致する。
+/// `[ending]` コマンドはこのサービスでエンディングを判定する
 #[derive(Debug)]
-pub struct GameProgressService {
-    pub relationship_service: RelationshipService,
+pub struct GameProgressService<'a> {
+    pub relationship_service: &'a mut RelationshipService,
     pub battle_service: BattleService,
     pub character_registry: CharacterRegistry,
 }
@@ -30,79 +113,463 @@ impl RelationshipService {
     pub fn new() -> Self {
         Self {
             relationships: std::collections::HashMap::new(),
+            cap_policy: RelationshipCapPolicy::default(),
+            level_config: RelationshipLevelConfig::default(),
+            pair_table: RelationshipPairTable::default(),
+            triangle_rules: TriangleRuleTable::default(),
+            history: RelationshipHistory::default(),
+            pending_events: Vec::new(),
+            route_lock: None,
         }
     }
 
-    /// 関係値を取得（存在しない場合は新規作成）
+    /// 関係レベルの判定基準を指定して作成
+    pub fn with_level_config(level_config: RelationshipLevelConfig) -> Self {
+        Self {
+            level_config,
+            ..Self::new()
+        }
+    }
+
+    /// 関係レベルの判定基準を差し替え（既存の関係値にも適用）
+    pub fn set_level_config(&mut self, level_config: RelationshipLevelConfig) {
+        for relationship in self.relationships.values_mut() {
+            let thresholds = level_config.thresholds_for(&relationship.character_a, &relationship.character_b);
+            relationship.set_thresholds(thresholds);
+        }
+        self.level_config = level_config;
+    }
+
+    /// 現在の関係レベルの判定基準
+    pub fn level_config(&self) -> &RelationshipLevelConfig {
+        &self.level_config
+    }
+
+    /// ペア定義を差し替え（作成済みの関係値は初期値を変更しない）
+    pub fn set_pair_table(&mut self, pair_table: RelationshipPairTable) {
+        self.pair_table = pair_table;
+    }
+
+    /// ペア定義の一覧
+    pub fn pair_table(&self) -> &RelationshipPairTable {
+        &self.pair_table
+    }
+
+    /// 三角関係の規則を差し替え
+    pub fn set_triangle_rules(&mut self, triangle_rules: TriangleRuleTable) {
+        self.triangle_rules = triangle_rules;
+    }
+
+    /// 三角関係の規則の一覧
+    pub fn triangle_rules(&self) -> &TriangleRuleTable {
+        &self.triangle_rules
+    }
+
+    /// ペアの定義（定義されていないペアは None）
+    pub fn pair_definition(&self, character_a: &str, character_b: &str) -> Option<&PairDefinition> {
+        self.pair_table.get(&PairKey::new(character_a, character_b))
+    }
+
+    /// 関係値を取得（存在しない場合はペア定義の初期値で新規作成）
     pub fn get_relationship(&mut self, character_a: &str, character_b: &str) -> &mut Relationship {
         let key = self.create_relationship_key(character_a, character_b);
+        let thresholds = self.level_config.thresholds_for(character_a, character_b);
+        let initial_value = self.pair_table.initial_value(&key);
 
-        self.relationships.entry(key.clone()).or_insert_with(|| {
-            Relationship::new(character_a, character_b)
+        self.relationships.entry(key).or_insert_with(|| {
+            let mut relationship = Relationship::with_thresholds(character_a, character_b, thresholds);
+            relationship.modify(initial_value);
+            relationship
         })
     }
 
-    /// 関係値を変更
+    /// 関係値を変更（理由なしのイベントによる変動として記録）
     pub fn modify_relationship(&mut self, character_a: &str, character_b: &str, delta: i32) -> i32 {
+        self.change_relationship(character_a, character_b, delta, ChangeCause::new(ChangeSource::Event, ""))
+    }
+
+    /// 関係値を変更して履歴に記録（キャップを超えた分は溜めておく）
+    ///
+    /// 固定変動ペアは、変動の向きだけ `delta` に従い大きさはペア定義の値になる。
+    /// 三角関係の規則に当てはまる上昇は、嫉妬する側と恋敵の関係値の低下に連動する
+    /// （連動した低下は規則で決まる量なので、固定変動ペアでも大きさを固定しない）
+    pub fn change_relationship(&mut self, character_a: &str, character_b: &str, delta: i32, cause: ChangeCause) -> i32 {
+        let notify = cause.notify;
+        let location = cause.location.clone();
+        let delta = self.pair_definition(character_a, character_b)
+            .map(|definition| definition.adjust_delta(delta))
+            .unwrap_or(delta);
+        let (old_value, new_value) = self.apply_change(character_a, character_b, delta, cause);
+        self.drive_jealousy(character_a, character_b, new_value - old_value, notify, location);
+        new_value
+    }
+
+    /// 関係値を1組だけ `delta` そのままで変更して履歴に記録（変更前と変更後の値を返す）
+    fn apply_change(&mut self, character_a: &str, character_b: &str, delta: i32, cause: ChangeCause) -> (i32, i32) {
+        let cap = self.cap_policy.limit;
         let relationship = self.get_relationship(character_a, character_b);
-        relationship.modify(delta);
-        relationship.value()
+        let old_value = relationship.value();
+        let old_level = relationship.level();
+        relationship.modify_capped(delta, cap);
+        let new_value = relationship.value();
+        let new_level = relationship.level();
+
+        if new_value != old_value {
+            self.pending_events.push(RelationshipChanged {
+                a: character_a.to_string(),
+                b: character_b.to_string(),
+                old: old_value,
+                new: new_value,
+                old_level,
+                new_level,
+                reason: cause.reason.clone(),
+                source: cause.source,
+                notify: cause.notify,
+            });
+        }
+
+        self.history.record(RelationshipChange {
+            pair: PairKey::new(character_a, character_b),
+            delta,
+            old_value,
+            new_value,
+            cause,
+            timestamp: current_timestamp(),
+        });
+
+        (old_value, new_value)
+    }
+
+    /// 想い人（B）と恋敵（C）の関係値の上昇を、嫉妬する側（A）と C の低下に連動させる
+    ///
+    /// A × B と B × C がともに親密な場合だけ連動する（連動した変動はさらに連動しない）
+    fn drive_jealousy(
+        &mut self,
+        character_a: &str,
+        character_b: &str,
+        applied_delta: i32,
+        notify: bool,
+        location: ScenarioLocation,
+    ) {
+        let pair = PairKey::new(character_a, character_b);
+        let driven: Vec<(String, String, String, i32)> = self.triangle_rules
+            .triggered_by(&pair)
+            .filter(|rule| {
+                self.get_relationship_level(&rule.jealous, &rule.beloved) == RelationshipLevel::Intimate
+                    && self.get_relationship_level(&rule.beloved, &rule.rival) == RelationshipLevel::Intimate
+            })
+            .map(|rule| (rule.jealous.clone(), rule.beloved.clone(), rule.rival.clone(), rule.driven_delta(applied_delta)))
+            .filter(|(_, _, _, delta)| *delta != 0)
+            .collect();
+
+        for (jealous, beloved, rival, delta) in driven {
+            let mut cause = ChangeCause::new(ChangeSource::Event, &format!("嫉妬（{} × {}）", beloved, rival))
+                .at(location.clone());
+            if !notify {
+                cause = cause.silent();
+            }
+            self.apply_change(&jealous, &rival, delta, cause);
+        }
+    }
+
+    /// `jealous` が `rival` に嫉妬しているか（想い人と親密で、想い人と恋敵も親密）
+    pub fn is_jealous(&self, jealous: &str, rival: &str) -> bool {
+        self.triangle_rules.between(jealous, rival).any(|rule| {
+            self.get_relationship_level(&rule.jealous, &rule.beloved) == RelationshipLevel::Intimate
+                && self.get_relationship_level(&rule.beloved, &rule.rival) == RelationshipLevel::Intimate
+        })
+    }
+
+    /// 関係値の条件を満たすか
+    pub fn satisfies(&self, condition: &RelationshipCondition) -> bool {
+        condition.evaluate(
+            &|a: &str, b: &str| self.get_relationship_value(a, b),
+            &|a: &str, b: &str| self.get_relationship_level(a, b),
+            &|jealous: &str, rival: &str| self.is_jealous(jealous, rival),
+        )
+    }
+
+    /// 送出待ちの変動イベントがあるか
+    pub fn has_pending_events(&self) -> bool {
+        !self.pending_events.is_empty()
     }
 
-    /// 現在の関係値を参照
+    /// 送出待ちの変動イベントを取り出す
+    pub fn drain_events(&mut self) -> Vec<RelationshipChanged> {
+        std::mem::take(&mut self.pending_events)
+    }
+
+    /// 関係値の変動履歴
+    pub fn history(&self) -> &RelationshipHistory {
+        &self.history
+    }
+
+    /// セーブデータから関係値を復元（履歴には記録しない）
+    pub fn restore_relationship(
+        &mut self,
+        character_a: &str,
+        character_b: &str,
+        value: i32,
+        banked: i32,
+        saved_level: Option<RelationshipLevel>,
+    ) {
+        self.get_relationship(character_a, character_b).restore(value, banked, saved_level);
+    }
+
+    /// セーブデータから変動履歴を復元
+    pub fn restore_history(&mut self, history: RelationshipHistory) {
+        self.history = history;
+    }
+
+    /// セーブデータからキャップ方針を復元（溜まっている変動量は反映しない）
+    pub fn restore_cap_policy(&mut self, policy: RelationshipCapPolicy) {
+        self.cap_policy = policy;
+    }
+
+    /// 進行状態（関係値・キャップ・履歴・送出待ちイベント・確定ルート）を初期状態に戻す
+    ///
+    /// 判定基準・ペア定義・三角関係の規則はデータファイル由来のため残す
+    pub fn reset(&mut self) {
+        self.relationships.clear();
+        self.cap_policy = RelationshipCapPolicy::default();
+        self.history = RelationshipHistory::default();
+        self.pending_events.clear();
+        self.route_lock = None;
+    }
+
+    /// 主人公と各キャラクターの現在の関係レベル（作成済みのペアとペア定義のペア）
+    pub fn route_snapshot(&self, main_character: &str) -> RouteLock {
+        let pairs = self.relationships.keys()
+            .chain(self.pair_table.definitions().into_iter().map(|definition| &definition.key))
+            .filter(|pair| pair.contains(main_character))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        RouteLock::new(
+            main_character,
+            pairs.into_iter().map(|pair| {
+                let level = self.get_relationship_level(pair.first(), pair.second());
+                (pair, level)
+            }),
+        )
+    }
+
+    /// ルートを確定（確定済みの場合は変更せずエラー）
+    pub fn lock_route(&mut self, main_character: &str) -> Result<&RouteLock, String> {
+        if let Some(lock) = &self.route_lock {
+            return Err(format!("ルートは確定済みです（{}）", lock.main_character()));
+        }
+        let snapshot = self.route_snapshot(main_character);
+        Ok(self.route_lock.insert(snapshot))
+    }
+
+    /// 確定したルート（未確定なら None）
+    pub fn route_lock(&self) -> Option<&RouteLock> {
+        self.route_lock.as_ref()
+    }
+
+    /// セーブデータから確定したルートを復元
+    pub fn restore_route_lock(&mut self, lock: Option<RouteLock>) {
+        self.route_lock = lock;
+    }
+
+    /// 現在のキャップ方針
+    pub fn cap_policy(&self) -> &RelationshipCapPolicy {
+        &self.cap_policy
+    }
+
+    /// キャップ方針を変更
+    ///
+    /// キャップが上がり、方針が超過分の反映を求める場合は溜まっていた分を反映する
+    pub fn set_cap_policy(&mut self, policy: RelationshipCapPolicy) {
+        if policy.limit > self.cap_policy.limit && policy.apply_banked_on_raise {
+            for relationship in self.relationships.values_mut() {
+                relationship.release_banked(policy.limit);
+            }
+        }
+        self.cap_policy = policy;
+    }
+
+    /// キャップを超えて溜まっている変動量を参照
+    pub fn get_banked_value(&self, character_a: &str, character_b: &str) -> i32 {
+        let key = self.create_relationship_key(character_a, character_b);
+        self.relationships.get(&key)
+            .map(|r| r.banked)
+            .unwrap_or(0)
+    }
+
+    /// 現在の関係値を参照（未作成のペアはペア定義の初期値）
     pub fn get_relationship_value(&self, character_a: &str, character_b: &str) -> i32 {
         let key = self.create_relationship_key(character_a, character_b);
         self.relationships.get(&key)
             .map(|r| r.value())
-            .unwrap_or(0)
+            .unwrap_or_else(|| self.pair_table.initial_value(&key))
+    }
+
+    /// 現在の関係レベルを参照（未作成のペアはペア定義の初期値から判定）
+    pub fn get_relationship_level(&self, character_a: &str, character_b: &str) -> RelationshipLevel {
+        let key = self.create_relationship_key(character_a, character_b);
+        self.relationships.get(&key)
+            .map(|r| r.level())
+            .unwrap_or_else(|| {
+                self.level_config
+                    .thresholds_for(character_a, character_b)
+                    .level_for(self.pair_table.initial_value(&key), None)
+            })
+    }
+
+    /// 関係値の複製（未作成のペアはペア定義の初期値と判定基準で作る、サービスには追加しない）
+    pub fn relationship_snapshot(&self, character_a: &str, character_b: &str) -> Relationship {
+        let key = self.create_relationship_key(character_a, character_b);
+        self.relationships.get(&key).cloned().unwrap_or_else(|| {
+            let thresholds = self.level_config.thresholds_for(character_a, character_b);
+            let mut relationship = Relationship::with_thresholds(character_a, character_b, thresholds);
+            relationship.modify(self.pair_table.initial_value(&key));
+            relationship
+        })
     }
 
     /// 関係値キーを生成（順序を正規化）
-    fn create_relationship_key(&self, character_a: &str, character_b: &str) -> String {
-        let mut chars = vec![character_a, character_b];
-        chars.sort();
-        format!("{}_{}", chars[0], chars[1])
+    fn create_relationship_key(&self, character_a: &str, character_b: &str) -> PairKey {
+        PairKey::new(character_a, character_b)
     }
 
     /// 全関係値の状況を取得
-    pub fn get_all_relationships(&self) -> Vec<(String, i32)> {
+    pub fn get_all_relationships(&self) -> Vec<(PairKey, i32)> {
         self.relationships.iter()
             .map(|(key, relationship)| (key.clone(), relationship.value()))
             .collect()
     }
+
+    /// 作成済みの全関係値（セーブデータ用）
+    pub fn relationships(&self) -> impl Iterator<Item = &Relationship> {
+        self.relationships.values()
+    }
 }
 
-impl BattleService {
-    pub fn new(relationship_service: RelationshipService) -> Self {
-        Self { relationship_service }
+/// 現在時刻（UNIX時間・秒）
+fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+impl GiftService {
+    pub fn new(giver: &str, catalog: GiftCatalog, preferences: GiftPreferences) -> Self {
+        Self {
+            giver: giver.to_string(),
+            catalog,
+            preferences,
+            journal: PreferenceJournal::default(),
+        }
     }
 
-    /// 戦闘を開始し、パーティを作成
-    pub fn start_battle(&mut self, character_a: &str, character_b: &str) -> BattleParty {
-        let mut party = BattleParty::new(character_a, character_b);
+    /// プレゼントを渡す側
+    pub fn giver(&self) -> &str {
+        &self.giver
+    }
 
-        // 現在の関係値を取得してパーティに反映
-        let current_value = self.relationship_service.get_relationship_value(character_a, character_b);
-        party.relationship.modify(current_value);
+    /// 渡せるアイテムの一覧
+    pub fn catalog(&self) -> &GiftCatalog {
+        &self.catalog
+    }
 
+    /// プレゼントを渡せる相手（好みが定義されたキャラクター、ID順）
+    pub fn receivers(&self) -> Vec<&str> {
+        self.preferences
+            .characters()
+            .into_iter()
+            .filter(|character_id| *character_id != self.giver)
+            .collect()
+    }
+
+    /// 判明した好みの記録
+    pub fn journal(&self) -> &PreferenceJournal {
+        &self.journal
+    }
+
+    /// セーブデータから判明した好みを復元
+    pub fn restore_journal(&mut self, journal: PreferenceJournal) {
+        self.journal = journal;
+    }
+
+    /// プレゼントを渡して関係値を変動させ、反応から判明した好みを記録
+    pub fn give_gift(
+        &mut self,
+        relationships: &mut RelationshipService,
+        receiver: &str,
+        item_id: &str,
+        location: ScenarioLocation,
+    ) -> Result<GiftOutcome, String> {
+        if receiver == self.giver {
+            return Err(format!("自分自身にはプレゼントを渡せません: {}", receiver));
+        }
+        let item = self.catalog.get(item_id)
+            .cloned()
+            .ok_or_else(|| format!("未定義のアイテムです: {}", item_id))?;
+
+        let reaction = self.preferences.reaction(receiver, &item.category);
+        let old_value = relationships.get_relationship_value(&self.giver, receiver);
+        let new_value = relationships.change_relationship(
+            &self.giver,
+            receiver,
+            reaction.delta(),
+            ChangeCause::new(ChangeSource::Gift, &item.name).at(location),
+        );
+        let newly_learned = self.journal.learn(receiver, &item.category, reaction);
+
+        Ok(GiftOutcome {
+            receiver: receiver.to_string(),
+            item,
+            reaction,
+            old_value,
+            new_value,
+            newly_learned,
+        })
+    }
+}
+
+/// 戦術提案で「レベルの境界が近い」とみなす幅
+const STRATEGY_NEAR_LEVEL_MARGIN: i32 = 25;
+
+impl BattleService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 戦闘を開始し、共有の関係値からパーティを作成
+    pub fn start_battle(&self, relationships: &RelationshipService, character_a: &str, character_b: &str) -> BattleParty {
+        let mut party = BattleParty::new(character_a, character_b);
+        self.refresh_party(relationships, &mut party);
         party
     }
 
-    /// 技の実行と関係値への影響を処理
+    /// 共有の関係値をパーティに反映し直す（戦闘中にストーリー側で変わった分も含む）
+    pub fn refresh_party(&self, relationships: &RelationshipService, party: &mut BattleParty) {
+        // 現在の関係値を判定基準ごとパーティに反映
+        party.relationship = relationships.relationship_snapshot(&party.character_a, &party.character_b);
+        party.jealous = relationships.satisfies(&RelationshipCondition::either_jealous(&party.character_a, &party.character_b));
+    }
+
+    /// 技の実行と関係値への影響を処理（変動は共有の関係値に記録し、パーティにも反映する）
     pub fn execute_skill_with_relationship_impact(
-        &mut self,
-        party: &BattleParty,
+        &self,
+        relationships: &mut RelationshipService,
+        party: &mut BattleParty,
         skill: &BattleSkill
     ) -> Result<BattleResult, String> {
         let result = party.execute_skill(skill)?;
 
         // 協力技の使用は関係値にポジティブな影響
         if result.is_cooperative_attack {
-            self.relationship_service.modify_relationship(
+            relationships.change_relationship(
                 &party.character_a,
                 &party.character_b,
-                5
+                5,
+                ChangeCause::new(ChangeSource::Battle, &format!("協力技「{}」", skill.name)),
             );
+            self.refresh_party(relationships, party);
         }
 
         Ok(result)
@@ -112,6 +579,7 @@ impl BattleService {
     pub fn suggest_battle_strategy(&self, party: &BattleParty) -> Vec<String> {
         let mut suggestions = Vec::new();
         let relationship_value = party.relationship.value();
+        let thresholds = party.relationship.thresholds;
 
         match party.relationship.level() {
             crate::domain::relationship::RelationshipLevel::Intimate => {
@@ -122,12 +590,15 @@ impl BattleService {
                 if party.character_a.to_lowercase().contains("souma") {
                     suggestions.push("ソウマの対立技「怒りの一撃」が使用可能です".to_string());
                 }
+                if party.is_super_conflict_ready() {
+                    suggestions.push("嫉妬が極まり、超対立技「嫉妬の激突」が使用可能です".to_string());
+                }
                 suggestions.push("関係修復を優先した方が良いかもしれません".to_string());
             }
             crate::domain::relationship::RelationshipLevel::Normal => {
-                if relationship_value > 25 {
+                if relationship_value > thresholds.intimate_min - STRATEGY_NEAR_LEVEL_MARGIN {
                     suggestions.push("もう少しで協力技が使えるようになります".to_string());
-                } else if relationship_value < -25 {
+                } else if relationship_value < thresholds.conflict_max + STRATEGY_NEAR_LEVEL_MARGIN {
                     suggestions.push("関係が悪化しています。注意が必要です".to_string());
                 }
                 suggestions.push("基本技で安定した戦闘を心がけましょう".to_string());
@@ -138,11 +609,12 @@ impl BattleService {
     }
 }
 
-impl GameProgressService {
-    pub fn new() -> Self {
+impl<'a> GameProgressService<'a> {
+    /// 共有の関係値を借りて作成
+    pub fn new(relationship_service: &'a mut RelationshipService) -> Self {
         Self {
-            relationship_service: RelationshipService::new(),
-            battle_service: BattleService::new(RelationshipService::new()),
+            relationship_service,
+            battle_service: BattleService::new(),
             character_registry: CharacterRegistry::default(),
         }
     }
@@ -150,7 +622,12 @@ impl GameProgressService {
     /// ゲーム内イベントによる関係値変化を処理
     pub fn process_story_event(&mut self, event_type: &str, characters: (&str, &str), impact: i32) -> String {
         let (char_a, char_b) = characters;
-        let new_value = self.relationship_service.modify_relationship(char_a, char_b, impact);
+        let new_value = self.relationship_service.change_relationship(
+            char_a,
+            char_b,
+            impact,
+            ChangeCause::new(ChangeSource::Event, event_type),
+        );
         let relationship = self.relationship_service.get_relationship(char_a, char_b);
 
         format!(
@@ -170,26 +647,11 @@ impl GameProgressService {
         relationship_value >= 75 // 親密度75以上で親密シーン解放
     }
 
-    /// エンディング判定
-    pub fn determine_ending(&self, main_character: &str) -> String {
-        let all_relationships = self.relationship_service.get_all_relationships();
-
-        // 最も関係値の高いキャラクターを特定
-        let best_relationship = all_relationships.iter()
-            .filter(|(key, _)| key.contains(main_character))
-            .max_by_key(|(_, value)| *value);
-
-        match best_relationship {
-            Some((key, value)) if *value >= 90 => {
-                format!("True Ending - {}との深い絆エンディング", key)
-            }
-            Some((key, value)) if *value >= 50 => {
-                format!("Good Ending - {}との友情エンディング", key)
-            }
-            Some((_, value)) if *value <= -50 => {
-                "Bad Ending - 孤独エンディング".to_string()
-            }
-            _ => "Normal Ending - 平凡エンディング".to_string()
+    /// エンディング判定（ルート確定前は現在の関係レベルで仮に判定）
+    pub fn determine_ending(&self, table: &EndingTable) -> EndingResolution {
+        match self.relationship_service.route_lock() {
+            Some(lock) => table.resolve(lock),
+            None => table.resolve(&self.relationship_service.route_snapshot(&table.main_character)),
         }
     }
 
@@ -224,8 +686,8 @@ pub struct GameStats {
     pub total_relationships: usize,
     pub positive_relationships: usize,
     pub negative_relationships: usize,
-    pub highest_relationship: Option<(String, i32)>,
-    pub lowest_relationship: Option<(String, i32)>,
+    pub highest_relationship: Option<(PairKey, i32)>,
+    pub lowest_relationship: Option<(PairKey, i32)>,
 }
 
 impl Default for RelationshipService {
@@ -234,15 +696,16 @@ impl Default for RelationshipService {
     }
 }
 
-impl Default for GameProgressService {
+impl Default for GiftService {
     fn default() -> Self {
-        Self::new()
+        Self::new("souma", GiftCatalog::default(), GiftPreferences::default())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::ending::{Ending, EndingRule};
 
     #[test]
     fn relationship_service_basic_operations() {
@@ -260,19 +723,287 @@ mod tests {
         assert_eq!(service.get_relationship_value("yuzuki", "souma"), 25);
     }
 
+    #[test]
+    fn relationship_service_cap_phases() {
+        use crate::domain::relationship::CapPhase;
+
+        let mut service = RelationshipService::new();
+
+        // 序盤: ±30
+        service.set_cap_policy(RelationshipCapPolicy::for_phase(CapPhase::Early));
+        assert_eq!(service.modify_relationship("souma", "yuzuki", 45), 30);
+        assert_eq!(service.modify_relationship("souma", "kai", -45), -30);
+        assert_eq!(service.get_banked_value("souma", "yuzuki"), 15);
+
+        // 中盤: ±50（超過分は反映しない方針）
+        service.set_cap_policy(RelationshipCapPolicy::for_phase(CapPhase::Mid));
+        assert_eq!(service.get_relationship_value("souma", "yuzuki"), 30);
+        assert_eq!(service.modify_relationship("souma", "yuzuki", 30), 50);
+
+        // 終盤: ±100（溜まっていた超過分を反映）
+        service.set_cap_policy(RelationshipCapPolicy {
+            apply_banked_on_raise: true,
+            ..RelationshipCapPolicy::for_phase(CapPhase::Late)
+        });
+        assert_eq!(service.get_relationship_value("souma", "yuzuki"), 75);
+        assert_eq!(service.get_relationship_value("souma", "kai"), -45);
+        assert_eq!(service.get_banked_value("souma", "yuzuki"), 0);
+    }
+
+    #[test]
+    fn relationship_service_pair_table() {
+        let mut table = RelationshipPairTable::default();
+        table.insert(PairDefinition {
+            initial_value: -25,
+            is_recommended: true,
+            is_opposed: true,
+            ..PairDefinition::new("souma", "kai")
+        });
+        table.insert(PairDefinition {
+            fixed_delta: Some(10),
+            ..PairDefinition::new("retsuji", "yuzuki")
+        });
+
+        let mut service = RelationshipService::new();
+        service.set_pair_table(table);
+
+        // 初期値はペア定義から
+        assert_eq!(service.get_relationship_value("kai", "souma"), -25);
+        assert_eq!(service.modify_relationship("souma", "kai", 5), -20);
+        assert!(service.pair_definition("kai", "souma").unwrap().is_opposed);
+        assert!(service.pair_definition("souma", "yuzuki").is_none());
+
+        // 固定変動ペアは指定の大きさによらず ±10
+        assert_eq!(service.modify_relationship("yuzuki", "retsuji", 40), 10);
+        assert_eq!(service.modify_relationship("yuzuki", "retsuji", -1), 0);
+    }
+
+    #[test]
+    fn relationship_service_records_history() {
+        use crate::domain::relationship::CapPhase;
+        use crate::domain::relationship_history::ScenarioLocation;
+
+        let mut service = RelationshipService::new();
+        service.set_cap_policy(RelationshipCapPolicy::for_phase(CapPhase::Early));
+
+        let chapter01 = ScenarioLocation::chapter("chapter01");
+        service.change_relationship("souma", "yuzuki", 5, ChangeCause::new(ChangeSource::Battle, "掛け合い成功").at(chapter01.clone()));
+        service.change_relationship("souma", "yuzuki", 40, ChangeCause::new(ChangeSource::Choice, "一緒に行く").at(chapter01.clone()));
+        service.change_relationship("souma", "kai", -10, ChangeCause::new(ChangeSource::Choice, "口論").at(chapter01));
+
+        let history = service.history();
+        assert_eq!(history.len(), 3);
+
+        // キャップで止まった分は反映量に含めない
+        let biggest = history.biggest_change_in_chapter("chapter01").unwrap();
+        assert_eq!(biggest.delta, 40);
+        assert_eq!(biggest.applied_delta(), 25);
+        assert_eq!(biggest.cause.reason, "一緒に行く");
+        assert_eq!(history.net_change_from(ChangeSource::Battle, None), 5);
+    }
+
+    #[test]
+    fn relationship_service_emits_change_events() {
+        let mut service = RelationshipService::new();
+
+        service.change_relationship("souma", "yuzuki", 45, ChangeCause::new(ChangeSource::Choice, "約束"));
+        service.change_relationship("souma", "yuzuki", 10, ChangeCause::new(ChangeSource::Event, "再会").silent());
+        assert!(service.has_pending_events());
+
+        let events = service.drain_events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].delta(), 45);
+        assert!(!events[0].is_level_changed());
+        assert!(events[0].notify);
+
+        // 親密への変化と通知の抑止
+        assert_eq!(events[1].old_level, RelationshipLevel::Normal);
+        assert_eq!(events[1].new_level, RelationshipLevel::Intimate);
+        assert_eq!(events[1].reason, "再会");
+        assert!(!events[1].notify);
+
+        // 値が変わらない変動（キャップで止まった場合など）はイベントにしない
+        service.set_cap_policy(RelationshipCapPolicy { limit: 55, apply_banked_on_raise: false });
+        service.modify_relationship("souma", "yuzuki", 5);
+        assert!(!service.has_pending_events());
+        assert!(service.drain_events().is_empty());
+    }
+
+    #[test]
+    fn gift_service_changes_relationship_and_learns_preferences() {
+        let mut catalog = GiftCatalog::default();
+        catalog.insert(GiftItem::new("rose", "バラの花束", "flowers"));
+        catalog.insert(GiftItem::new("dagger", "短剣", "weapons"));
+        catalog.insert(GiftItem::new("novel", "冒険小説", "books"));
+        let mut preferences = GiftPreferences::default();
+        preferences.set("yuzuki", "flowers", GiftReaction::Favorite);
+        preferences.set("yuzuki", "weapons", GiftReaction::Disliked);
+
+        let mut gifts = GiftService::new("souma", catalog, preferences);
+        let mut relationships = RelationshipService::new();
+        assert_eq!(gifts.receivers(), vec!["yuzuki"]);
+
+        let outcome = gifts.give_gift(&mut relationships, "yuzuki", "rose", ScenarioLocation::chapter("hub")).unwrap();
+        assert_eq!(outcome.reaction, GiftReaction::Favorite);
+        assert_eq!((outcome.old_value, outcome.new_value), (0, 10));
+        assert!(outcome.newly_learned);
+
+        // 同じ種類を渡しても新しくは判明しない
+        let outcome = gifts.give_gift(&mut relationships, "yuzuki", "rose", ScenarioLocation::default()).unwrap();
+        assert!(!outcome.newly_learned);
+        assert_eq!(gifts.give_gift(&mut relationships, "yuzuki", "dagger", ScenarioLocation::default()).unwrap().new_value, 15);
+        assert_eq!(gifts.give_gift(&mut relationships, "yuzuki", "novel", ScenarioLocation::default()).unwrap().new_value, 20);
+
+        assert_eq!(gifts.journal().known("yuzuki", "weapons"), Some(GiftReaction::Disliked));
+        assert_eq!(gifts.journal().len(), 3);
+
+        // 変動は履歴にプレゼントとして残る
+        let first = &relationships.history().entries()[0];
+        assert_eq!(first.cause.source, ChangeSource::Gift);
+        assert_eq!(first.cause.reason, "バラの花束");
+        assert_eq!(relationships.history().net_change_from(ChangeSource::Gift, None), 20);
+
+        assert!(gifts.give_gift(&mut relationships, "yuzuki", "unknown", ScenarioLocation::default()).is_err());
+        assert!(gifts.give_gift(&mut relationships, "souma", "rose", ScenarioLocation::default()).is_err());
+    }
+
+    #[test]
+    fn relationship_service_drives_jealousy_triangles() {
+        use crate::domain::jealousy::TriangleRule;
+
+        let mut triangles = TriangleRuleTable::default();
+        triangles.insert(TriangleRule::new("yuzuki", "souma", "celine"));
+        let mut service = RelationshipService::new();
+        service.set_triangle_rules(triangles);
+
+        // ソウマ × セリーヌが親密でなければ連動しない
+        service.modify_relationship("souma", "yuzuki", 60);
+        service.modify_relationship("souma", "celine", 40);
+        assert_eq!(service.get_relationship_value("yuzuki", "celine"), 0);
+        assert!(!service.is_jealous("yuzuki", "celine"));
+
+        // 親密になってからの上昇の半分だけユズキ × セリーヌが下がる
+        service.change_relationship("souma", "celine", 20, ChangeCause::new(ChangeSource::Choice, "共闘").silent());
+        assert_eq!(service.get_relationship_value("yuzuki", "celine"), -10);
+        assert!(service.is_jealous("yuzuki", "celine"));
+        assert!(!service.is_jealous("celine", "yuzuki"));
+
+        let driven = service.history().entries().last().unwrap();
+        assert_eq!(driven.pair, PairKey::new("yuzuki", "celine"));
+        assert_eq!(driven.cause.reason, "嫉妬（souma × celine）");
+        assert!(!driven.cause.notify);
+
+        // 下がる向きの変動は連動しない
+        service.modify_relationship("souma", "celine", -5);
+        assert_eq!(service.get_relationship_value("yuzuki", "celine"), -10);
+
+        // 嫉妬していて -100 なら超対立技が使える
+        service.modify_relationship("yuzuki", "celine", -90);
+        assert!(service.satisfies(&RelationshipCondition::All(vec![
+            RelationshipCondition::either_jealous("celine", "yuzuki"),
+            RelationshipCondition::at_most("celine", "yuzuki", -100),
+        ])));
+        let battle = BattleService::new();
+        let party = battle.start_battle(&service, "yuzuki", "celine");
+        assert!(party.jealous && party.is_super_conflict_ready());
+        assert!(party.available_skills().iter().any(|s| s.skill_type == SkillType::SuperConflict));
+        assert!(battle.suggest_battle_strategy(&party).iter().any(|s| s.contains("嫉妬の激突")));
+    }
+
+    #[test]
+    fn jealousy_ignores_fixed_delta_of_driven_pair() {
+        use crate::domain::jealousy::TriangleRule;
+
+        let mut triangles = TriangleRuleTable::default();
+        triangles.insert(TriangleRule::new("yuzuki", "souma", "celine"));
+        let mut table = RelationshipPairTable::default();
+        table.insert(PairDefinition {
+            fixed_delta: Some(10),
+            ..PairDefinition::new("yuzuki", "celine")
+        });
+        let mut service = RelationshipService::new();
+        service.set_pair_table(table);
+        service.set_triangle_rules(triangles);
+
+        service.modify_relationship("souma", "yuzuki", 60);
+        service.modify_relationship("souma", "celine", 40);
+        service.modify_relationship("souma", "celine", 30);
+
+        // 連動した低下は規則どおり上昇の半分（固定変動の 10 にはならない）
+        assert_eq!(service.get_relationship_value("yuzuki", "celine"), -15);
+        assert_eq!(service.history().entries().last().unwrap().delta, -15);
+
+        // 直接の変動には固定変動が効く
+        service.modify_relationship("yuzuki", "celine", -3);
+        assert_eq!(service.get_relationship_value("yuzuki", "celine"), -25);
+    }
+
     #[test]
     fn battle_service_integration() {
-        let mut service = BattleService::new(RelationshipService::new());
-        let party = service.start_battle("souma", "yuzuki");
+        let service = BattleService::new();
+        let party = service.start_battle(&RelationshipService::new(), "souma", "yuzuki");
 
         assert_eq!(party.character_a, "souma");
         assert_eq!(party.character_b, "yuzuki");
         assert_eq!(party.relationship.value(), 0);
     }
 
+    #[test]
+    fn story_and_battle_share_relationships() {
+        let mut relationships = RelationshipService::new();
+        let mut game = GameProgressService::new(&mut relationships);
+
+        // ストーリーでの変動が戦闘に反映される
+        game.process_story_event("共同作業イベント", ("souma", "yuzuki"), 50);
+        let mut party = game.battle_service.start_battle(game.relationship_service, "souma", "yuzuki");
+        assert_eq!(party.relationship.value(), 50);
+
+        // 戦闘での変動がストーリーに反映される
+        let skill = party.available_skills().into_iter().find(|s| s.skill_type == SkillType::Cooperation).unwrap();
+        game.battle_service
+            .execute_skill_with_relationship_impact(game.relationship_service, &mut party, &skill)
+            .unwrap();
+        assert_eq!(game.relationship_service.get_relationship_value("yuzuki", "souma"), 55);
+        assert_eq!(party.relationship.value(), 55);
+        assert_eq!(game.relationship_service.history().net_change_from(ChangeSource::Battle, None), 5);
+
+        // 戦闘の開始で未作成のペアは増えない
+        game.battle_service.start_battle(game.relationship_service, "souma", "kai");
+        assert_eq!(game.relationship_service.get_all_relationships().len(), 1);
+    }
+
+    #[test]
+    fn battle_service_uses_pair_thresholds() {
+        use crate::domain::relationship::{RelationshipLevel, RelationshipThresholds};
+
+        let mut config = RelationshipLevelConfig::default();
+        config.set_pair_override("souma", "kai", RelationshipThresholds {
+            conflict_max: -25,
+            ..Default::default()
+        });
+
+        let mut relationships = RelationshipService::with_level_config(config);
+        relationships.modify_relationship("souma", "kai", -25);
+        relationships.modify_relationship("souma", "yuzuki", -25);
+
+        let service = BattleService::new();
+
+        // 上書きのあるペアは -25 で対立技が使える
+        let party = service.start_battle(&relationships, "souma", "kai");
+        assert_eq!(party.relationship.level(), RelationshipLevel::Conflict);
+        assert!(party.available_skills().iter().any(|s| s.skill_type == SkillType::Conflict));
+        assert!(service.suggest_battle_strategy(&party).iter().any(|s| s.contains("怒りの一撃")));
+
+        // 既定の基準では -25 は通常
+        let party = service.start_battle(&relationships, "souma", "yuzuki");
+        assert_eq!(party.relationship.level(), RelationshipLevel::Normal);
+        assert!(party.available_skills().iter().all(|s| s.skill_type == SkillType::Normal));
+    }
+
     #[test]
     fn game_progress_service_story_events() {
-        let mut service = GameProgressService::new();
+        let mut relationships = RelationshipService::new();
+        let mut service = GameProgressService::new(&mut relationships);
 
         let result = service.process_story_event(
             "共同作業イベント",
@@ -288,7 +1019,8 @@ mod tests {
 
     #[test]
     fn intimate_scene_unlock_condition() {
-        let mut service = GameProgressService::new();
+        let mut relationships = RelationshipService::new();
+        let mut service = GameProgressService::new(&mut relationships);
 
         // 親密度が足りない場合
         assert!(!service.should_unlock_intimate_scene("souma", "yuzuki"));
@@ -300,22 +1032,41 @@ mod tests {
 
     #[test]
     fn ending_determination() {
-        let mut service = GameProgressService::new();
+        let mut table = EndingTable::default();
+        table.add_rule(EndingRule {
+            pair: PairKey::new("souma", "yuzuki"),
+            level: RelationshipLevel::Intimate,
+            ending: Ending::True,
+            scenario: Some("endings/souma_yuzuki_true.md".to_string()),
+        });
+
+        let mut relationships = RelationshipService::new();
+        let mut service = GameProgressService::new(&mut relationships);
 
         // 高い関係値を設定
         service.relationship_service.modify_relationship("souma", "yuzuki", 95);
-        let ending = service.determine_ending("souma");
-        assert!(ending.contains("True Ending"));
-
-        // 低い関係値を設定
         service.relationship_service.modify_relationship("souma", "retsuji", -60);
-        let bad_ending = service.determine_ending("retsuji");
-        assert!(bad_ending.contains("Bad Ending"));
+        let ending = service.determine_ending(&table);
+        assert_eq!(ending.ending, Ending::True);
+        assert!(ending.pair_endings.contains(&(PairKey::new("souma", "retsuji"), Ending::Bad)));
+
+        // 確定後は関係値が変わってもエンディングは変わらない
+        service.relationship_service.lock_route("souma").unwrap();
+        service.relationship_service.modify_relationship("souma", "yuzuki", -200);
+        assert_eq!(service.determine_ending(&table).ending, Ending::True);
+        assert!(service.relationship_service.lock_route("souma").is_err());
+
+        // 対立しかなければバッドエンド
+        let mut lonely_relationships = RelationshipService::new();
+        let mut lonely = GameProgressService::new(&mut lonely_relationships);
+        lonely.relationship_service.modify_relationship("souma", "retsuji", -60);
+        assert_eq!(lonely.determine_ending(&table).ending, Ending::Bad);
     }
 
     #[test]
     fn game_stats_calculation() {
-        let mut service = GameProgressService::new();
+        let mut relationships = RelationshipService::new();
+        let mut service = GameProgressService::new(&mut relationships);
 
         service.relationship_service.modify_relationship("souma", "yuzuki", 50);
         service.relationship_service.modify_relationship("souma", "kai", -30);
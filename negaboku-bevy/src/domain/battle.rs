@@ -3,7 +3,7 @@
 //! 関係値に基づく技・効果の計算やダメージ処理など、
 //! 戦闘の核となるビジネスロジックを定義
 
-use crate::domain::relationship::{Relationship, RelationshipLevel};
+use crate::domain::relationship::{Relationship, RelationshipLevel, RELATIONSHIP_LIMIT};
 
 /// 戦闘技の種類
 #[derive(Debug, Clone, PartialEq)]
@@ -14,6 +14,8 @@ pub enum SkillType {
     Cooperation,
     /// 対立技（対立状態でのみ使用可能、主にソウマ限定）
     Conflict,
+    /// 超対立技（嫉妬の三角関係にあり、関係値が -100 のときのみ使用可能）
+    SuperConflict,
 }
 
 /// 戦闘技
@@ -33,6 +35,8 @@ pub struct BattleParty {
     pub character_a: String,
     pub character_b: String,
     pub relationship: Relationship,
+    /// どちらかが相手に嫉妬している（三角関係の規則による）
+    pub jealous: bool,
 }
 
 /// 戦闘計算結果
@@ -49,7 +53,7 @@ impl BattleSkill {
         let required_level = match skill_type {
             SkillType::Normal => None,
             SkillType::Cooperation => Some(RelationshipLevel::Intimate),
-            SkillType::Conflict => Some(RelationshipLevel::Conflict),
+            SkillType::Conflict | SkillType::SuperConflict => Some(RelationshipLevel::Conflict),
         };
 
         Self {
@@ -61,7 +65,9 @@ impl BattleSkill {
         }
     }
 
-    /// この技が現在の関係値で使用可能かチェック
+    /// この技が現在の関係レベルで使用可能かチェック
+    ///
+    /// レベルはペアの判定基準で判定したもの（`Relationship::level`）を渡す
     pub fn can_use(&self, relationship_level: RelationshipLevel) -> bool {
         match self.required_level {
             None => true,
@@ -76,9 +82,15 @@ impl BattleParty {
             character_a: character_a.to_string(),
             character_b: character_b.to_string(),
             relationship: Relationship::new(character_a, character_b),
+            jealous: false,
         }
     }
 
+    /// 超対立技を使える状態か（嫉妬していて関係値が -100）
+    pub fn is_super_conflict_ready(&self) -> bool {
+        self.jealous && self.relationship.value() <= -RELATIONSHIP_LIMIT
+    }
+
     /// 現在の関係値で使用可能な技一覧を取得
     pub fn available_skills(&self) -> Vec<BattleSkill> {
         let mut skills = vec![
@@ -114,6 +126,15 @@ impl BattleParty {
                         "怒りの力で繰り出す強力な攻撃"
                     ));
                 }
+                // 嫉妬の三角関係が極まったペアだけの超対立技
+                if self.is_super_conflict_ready() {
+                    skills.push(BattleSkill::new(
+                        "嫉妬の激突",
+                        SkillType::SuperConflict,
+                        300,
+                        "嫉妬と敵意をぶつけ合う捨て身の攻撃"
+                    ));
+                }
             }
             RelationshipLevel::Normal => {
                 // 通常状態では追加技なし
@@ -133,6 +154,12 @@ impl BattleParty {
                 skill.name, relationship_level
             ));
         }
+        if skill.skill_type == SkillType::SuperConflict && !self.is_super_conflict_ready() {
+            return Err(format!(
+                "技「{}」は嫉妬の三角関係で関係値が -100 のときのみ使用できます",
+                skill.name
+            ));
+        }
 
         // 関係値ボーナスを計算
         let relationship_bonus = self.calculate_relationship_bonus(&skill.skill_type);
@@ -151,13 +178,14 @@ impl BattleParty {
     /// 関係値に基づくダメージボーナスを計算
     fn calculate_relationship_bonus(&self, skill_type: &SkillType) -> f32 {
         let relationship_value = self.relationship.value();
+        let thresholds = self.relationship.thresholds;
 
         match skill_type {
             SkillType::Cooperation => {
                 // 親密度が高いほど協力技のボーナスが大きい
                 if relationship_value >= 75 {
                     0.5 // +50%
-                } else if relationship_value >= 50 {
+                } else if relationship_value >= thresholds.intimate_min {
                     0.3 // +30%
                 } else {
                     0.0
@@ -167,12 +195,16 @@ impl BattleParty {
                 // 対立値が高いほど対立技のボーナスが大きい
                 if relationship_value <= -75 {
                     0.4 // +40%
-                } else if relationship_value <= -50 {
+                } else if relationship_value <= thresholds.conflict_max {
                     0.2 // +20%
                 } else {
                     0.0
                 }
             }
+            SkillType::SuperConflict => {
+                // 条件を満たした時点で対立は極まっている
+                0.5 // +50%
+            }
             SkillType::Normal => {
                 // 通常技は関係値の影響を受けにくい
                 (relationship_value.abs() as f32) * 0.001 // 微弱な影響のみ
@@ -250,6 +282,25 @@ mod tests {
         assert!(result.unwrap_err().contains("使用できません"));
     }
 
+    #[test]
+    fn super_conflict_skill_requires_jealousy_at_minimum() {
+        let mut party = BattleParty::new("yuzuki", "celine");
+        party.relationship.modify(-100);
+        let is_super_conflict = |skill: &BattleSkill| skill.skill_type == SkillType::SuperConflict;
+
+        // 嫉妬していなければ -100 でも使えない
+        assert!(!party.available_skills().iter().any(is_super_conflict));
+
+        party.jealous = true;
+        let skill = party.available_skills().into_iter().find(is_super_conflict).unwrap();
+        assert_eq!(party.execute_skill(&skill).unwrap().final_damage, 450);
+
+        // -99 では使えない
+        party.relationship.modify(1);
+        assert!(!party.available_skills().iter().any(is_super_conflict));
+        assert!(party.execute_skill(&skill).is_err());
+    }
+
     #[test]
     fn relationship_bonus_calculation() {
         let mut party = BattleParty::new("souma", "yuzuki");
@@ -11,6 +11,12 @@ pub struct Character {
     pub default_face: String,
     pub available_faces: Vec<String>,
     pub image_path: String,
+    /// シナリオの話者名として使える別名（例: 愛称やローマ字表記）
+    pub aliases: Vec<String>,
+    /// 名前欄の文字色（None の場合は既定色）
+    pub name_color: Option<Color>,
+    /// 名前欄のフォント（None の場合は既定フォント）
+    pub name_font: Option<String>,
 }
 
 impl Character {
@@ -21,9 +27,32 @@ impl Character {
             default_face: default_face.to_string(),
             available_faces: vec![default_face.to_string()],
             image_path: image_path.to_string(),
+            aliases: Vec::new(),
+            name_color: None,
+            name_font: None,
         }
     }
 
+    /// 別名を追加
+    pub fn with_aliases(mut self, aliases: &[&str]) -> Self {
+        self.aliases.extend(aliases.iter().map(|alias| alias.to_string()));
+        self
+    }
+
+    /// 名前欄の文字色を設定
+    pub fn with_name_color(mut self, color: Color) -> Self {
+        self.name_color = Some(color);
+        self
+    }
+
+    /// 話者名がこのキャラクターを指すか（ID・表示名・別名）
+    pub fn matches_speaker(&self, speaker: &str) -> bool {
+        let speaker = speaker.trim();
+        self.id.eq_ignore_ascii_case(speaker)
+            || self.name == speaker
+            || self.aliases.iter().any(|alias| alias == speaker)
+    }
+
     /// 表情が利用可能かチェック
     pub fn has_face(&self, face: &str) -> bool {
         self.available_faces.contains(&face.to_string())
@@ -84,6 +113,13 @@ impl CharacterRegistry {
         self.characters.get(id)
     }
 
+    /// 話者名（ID・表示名・別名）からキャラクターを検索
+    pub fn resolve(&self, speaker: &str) -> Option<&Character> {
+        self.characters
+            .get(speaker)
+            .or_else(|| self.characters.values().find(|character| character.matches_speaker(speaker)))
+    }
+
     /// 初期キャラクターを一括登録
     pub fn register_default_characters(&mut self) {
         // ソウマ
@@ -92,7 +128,7 @@ impl CharacterRegistry {
             "ソウマ",
             "normal",
             "images/characters/01_souma_kari.png"
-        ));
+        ).with_name_color(Color::srgb(0.6, 0.8, 1.0)));
 
         // ユズキ
         self.register(Character::new(
@@ -100,7 +136,7 @@ impl CharacterRegistry {
             "ユズキ",
             "smile",
             "images/characters/03_yuzuki_kari.jpg"
-        ));
+        ).with_name_color(Color::srgb(1.0, 0.7, 0.8)));
 
         // レツジ
         self.register(Character::new(
@@ -108,7 +144,7 @@ impl CharacterRegistry {
             "レツジ",
             "normal",
             "images/characters/02_retsuji_kari.png"
-        ));
+        ).with_name_color(Color::srgb(1.0, 0.7, 0.4)));
 
         // カイ
         self.register(Character::new(
@@ -116,7 +152,15 @@ impl CharacterRegistry {
             "カイ",
             "normal",
             "images/characters/06_kai_kari.png"
-        ));
+        ).with_name_color(Color::srgb(0.75, 0.6, 1.0)));
+
+        // サイトウ（拠点のメタキャラ、相関図の案内役）
+        self.register(Character::new(
+            "saitou",
+            "サイトウ",
+            "normal",
+            "images/characters/saitou_kari.png"
+        ).with_name_color(Color::srgb(0.7, 0.9, 0.7)));
     }
 }
 
@@ -151,4 +195,16 @@ mod tests {
         assert!(registry.get("test").is_some());
         assert!(registry.get("nonexistent").is_none());
     }
+
+    #[test]
+    fn test_character_registry_resolve_speaker() {
+        let mut registry = CharacterRegistry::new();
+        registry.register(Character::new("souma", "ソウマ", "normal", "souma.png").with_aliases(&["そうま"]));
+
+        assert_eq!(registry.resolve("souma").map(|c| c.id.as_str()), Some("souma"));
+        assert_eq!(registry.resolve("Souma").map(|c| c.id.as_str()), Some("souma"));
+        assert_eq!(registry.resolve("ソウマ").map(|c| c.id.as_str()), Some("souma"));
+        assert_eq!(registry.resolve("そうま").map(|c| c.id.as_str()), Some("souma"));
+        assert!(registry.resolve("？？？").is_none());
+    }
 }
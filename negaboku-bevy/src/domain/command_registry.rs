@@ -0,0 +1,212 @@
+//! コマンドレジストリ - シーンコマンドハンドラの登録と呼び出し
+//!
+//! # 責務
+//! - `[xxx]` 形式コマンドのハンドラ登録（コマンド名で検索）
+//! - 登録済みハンドラを使ったコマンド文字列のパース
+//! - パース済みコマンドの実行委譲
+//!
+//! 組み込みコマンド（bg, chara_show 等）も、関係値・相関図・戦闘・ギャラリー等のゲームプレイモジュールの
+//! コマンドも、`SceneCommandHandler` を実装して起動時にこのレジストリへ登録する。
+//! ハンドラは自分のコマンドのパラメータ解釈と内容の型を持つため、
+//! コマンドを追加しても `SceneCommand` やシナリオの読み込み・実行側を変更する必要はない。
+
+use bevy::prelude::*;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use crate::domain::scenario::{SceneCommand, ParseError};
+
+/// ハンドラが解釈したコマンドの内容
+///
+/// `Debug + PartialEq` な型なら何でもよい（ハンドラごとに専用の型を定義する）
+pub trait CommandPayload: Debug + Send + Sync + 'static {
+    fn as_any(&self) -> &dyn Any;
+
+    /// 同じ型・同じ内容か
+    fn eq_payload(&self, other: &dyn CommandPayload) -> bool;
+}
+
+impl<T: Debug + PartialEq + Send + Sync + 'static> CommandPayload for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_payload(&self, other: &dyn CommandPayload) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
+/// シーンコマンドハンドラ
+///
+/// 1つのコマンド名に対するパースと実行を担当する
+pub trait SceneCommandHandler: Send + Sync {
+    /// 担当するコマンド名（`[name ...]` の name 部分）
+    fn name(&self) -> &str;
+
+    /// パラメータからコマンドを生成（内容の型はハンドラが決める）
+    fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError>;
+
+    /// コマンドを実行
+    ///
+    /// ECSへのアクセスが必要な場合は `commands.queue` でワールド操作を積む
+    fn execute(&self, command: &SceneCommand, commands: &mut Commands);
+}
+
+/// シーンコマンドレジストリ（Bevy Resource）
+#[derive(Resource, Default)]
+pub struct SceneCommandRegistry {
+    handlers: HashMap<String, Box<dyn SceneCommandHandler>>,
+}
+
+impl SceneCommandRegistry {
+    /// ハンドラ未登録の空のレジストリを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ハンドラを登録（同名のハンドラがあれば置き換える）
+    ///
+    /// 組み込みコマンドも同じ扱いのため、後から登録したハンドラで差し替えられる
+    pub fn register<H: SceneCommandHandler + 'static>(&mut self, handler: H) {
+        let name = handler.name().to_string();
+        if self.handlers.insert(name.clone(), Box::new(handler)).is_some() {
+            println!("🔁 コマンドハンドラ置き換え: {}", name);
+        }
+    }
+
+    /// コマンド名に対応するハンドラを取得
+    pub fn get(&self, name: &str) -> Option<&dyn SceneCommandHandler> {
+        self.handlers.get(name).map(|handler| handler.as_ref())
+    }
+
+    /// コマンド名が登録済みかチェック
+    pub fn contains(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
+    /// `[cmd param=value ...]` 形式の文字列をパース
+    pub fn parse(&self, command_str: &str) -> Result<SceneCommand, ParseError> {
+        let (command_name, params) = SceneCommand::split(command_str)?;
+        self.parse_parts(&command_name, &params)
+    }
+
+    /// コマンド名とパラメータから、登録済みハンドラでコマンドを生成
+    pub fn parse_parts(&self, command_name: &str, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+        match self.get(command_name) {
+            Some(handler) => handler.parse(params),
+            None => Err(ParseError {
+                line_number: 0,
+                message: format!("未対応のコマンド: {}", command_name),
+            }),
+        }
+    }
+
+    /// コマンドを対応するハンドラで実行
+    pub fn execute(&self, command: &SceneCommand, commands: &mut Commands) {
+        match self.get(command.name()) {
+            Some(handler) => handler.execute(command, commands),
+            None => eprintln!("⚠️ ハンドラ未登録のコマンド: {}", command.name()),
+        }
+    }
+}
+
+/// 必須パラメータを取得
+pub fn required_param<'a>(
+    command_name: &str,
+    params: &'a HashMap<String, String>,
+    key: &str,
+) -> Result<&'a String, ParseError> {
+    params.get(key).ok_or_else(|| ParseError {
+        line_number: 0,
+        message: format!("{} コマンドには {} パラメータが必要", command_name, key),
+    })
+}
+
+/// 省略可能な true/false のパラメータを取得
+pub fn bool_param(command_name: &str, params: &HashMap<String, String>, key: &str) -> Result<Option<bool>, ParseError> {
+    params
+        .get(key)
+        .map(|value| value.parse())
+        .transpose()
+        .map_err(|_| ParseError {
+            line_number: 0,
+            message: format!("{} の {} パラメータは true/false である必要があります", command_name, key),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テスト用の独自コマンド `[battle enemy=xxx]` の内容
+    #[derive(Debug, PartialEq)]
+    struct BattleCommand {
+        enemy: String,
+    }
+
+    struct BattleCommandHandler;
+
+    impl SceneCommandHandler for BattleCommandHandler {
+        fn name(&self) -> &str {
+            "battle"
+        }
+
+        fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+            let enemy = required_param(self.name(), params, "enemy")?.clone();
+            Ok(SceneCommand::new(self.name(), BattleCommand { enemy }))
+        }
+
+        fn execute(&self, _command: &SceneCommand, _commands: &mut Commands) {}
+    }
+
+    /// 組み込みコマンドと同名のハンドラ（パラメータをそのまま内容にする）
+    struct BackgroundOverrideHandler;
+
+    impl SceneCommandHandler for BackgroundOverrideHandler {
+        fn name(&self) -> &str {
+            "bg"
+        }
+
+        fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+            Ok(SceneCommand::new(self.name(), params.clone()))
+        }
+
+        fn execute(&self, _command: &SceneCommand, _commands: &mut Commands) {}
+    }
+
+    #[test]
+    fn builtin_names_are_plain_handlers() {
+        // 空のレジストリは組み込みコマンドも解釈しない
+        let mut registry = SceneCommandRegistry::new();
+        assert!(!registry.contains("bg"));
+        assert!(registry.parse("[bg storage=forest_day.jpg]").is_err());
+
+        registry.register(BackgroundOverrideHandler);
+        assert!(registry.contains("bg"));
+
+        let cmd = registry.parse("[bg storage=forest_day.jpg time=500]").unwrap();
+        let params = cmd.payload::<HashMap<String, String>>().unwrap();
+        assert_eq!(params.get("storage"), Some(&"forest_day.jpg".to_string()));
+    }
+
+    #[test]
+    fn custom_handler_registration() {
+        let mut registry = SceneCommandRegistry::new();
+
+        // 未登録の間はエラー
+        assert!(registry.parse("[battle enemy=slime]").is_err());
+
+        registry.register(BattleCommandHandler);
+
+        let cmd = registry.parse("[battle enemy=slime]").unwrap();
+        assert_eq!(cmd.name(), "battle");
+        assert_eq!(cmd.payload::<BattleCommand>(), Some(&BattleCommand { enemy: "slime".to_string() }));
+        assert!(cmd.payload::<HashMap<String, String>>().is_none());
+        assert_eq!(cmd, registry.parse("[battle enemy=slime]").unwrap());
+        assert_ne!(cmd, registry.parse("[battle enemy=golem]").unwrap());
+
+        // ハンドラ側のバリデーションが効く
+        let error = registry.parse("[battle]").unwrap_err();
+        assert!(error.message.contains("enemy"));
+    }
+}
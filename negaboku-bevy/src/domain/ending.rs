@@ -0,0 +1,316 @@
+//! エンディング - ルート確定とエンディングの判定
+//!
+//! # 責務
+//! - ルート確定時点の関係レベルのスナップショット（確定後は変更不可）
+//! - ペアごとのエンディング（True / Good / Normal / Bad）の判定規則
+//! - 規則表からの最終的なエンディングと再生するシナリオの決定
+//!
+//! ゲーム後半の特定イベント（`[route_lock]`）で主人公と各キャラクターの関係レベルを確定し、
+//! `[ending]` で規則表に従ってエンディングのシナリオを再生する
+
+use bevy::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use crate::domain::relationship::{PairKey, RelationshipLevel};
+
+/// エンディングの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ending {
+    /// 推奨ペアの専用エンディング
+    True,
+    Good,
+    Normal,
+    Bad,
+}
+
+impl Ending {
+    /// 設定ファイルでの表記
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Ending::True => "true",
+            Ending::Good => "good",
+            Ending::Normal => "normal",
+            Ending::Bad => "bad",
+        }
+    }
+
+    /// 表記から変換
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "true" => Some(Ending::True),
+            "good" => Some(Ending::Good),
+            "normal" => Some(Ending::Normal),
+            "bad" => Some(Ending::Bad),
+            _ => None,
+        }
+    }
+
+    /// 画面表示用の名前
+    pub fn label(self) -> &'static str {
+        match self {
+            Ending::True => "トゥルーエンド",
+            Ending::Good => "グッドエンド",
+            Ending::Normal => "ノーマルエンド",
+            Ending::Bad => "バッドエンド",
+        }
+    }
+
+    /// 優先順位（大きいほど優先して再生する）
+    fn rank(self) -> u8 {
+        match self {
+            Ending::True => 3,
+            Ending::Good => 2,
+            Ending::Normal => 1,
+            Ending::Bad => 0,
+        }
+    }
+}
+
+/// ルート確定時点の関係レベル（作成後は変更できない）
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteLock {
+    main_character: String,
+    levels: BTreeMap<PairKey, RelationshipLevel>,
+}
+
+impl RouteLock {
+    /// 主人公と各キャラクターのペアのレベルから作成（主人公を含まないペアは除く）
+    pub fn new(main_character: &str, levels: impl IntoIterator<Item = (PairKey, RelationshipLevel)>) -> Self {
+        Self {
+            main_character: main_character.to_string(),
+            levels: levels.into_iter().filter(|(pair, _)| pair.contains(main_character)).collect(),
+        }
+    }
+
+    pub fn main_character(&self) -> &str {
+        &self.main_character
+    }
+
+    /// 確定したペアごとのレベル（ペア順）
+    pub fn levels(&self) -> &BTreeMap<PairKey, RelationshipLevel> {
+        &self.levels
+    }
+
+    /// 相手キャラクターとの確定レベル
+    pub fn level_with(&self, partner: &str) -> Option<RelationshipLevel> {
+        self.levels.get(&PairKey::new(&self.main_character, partner)).copied()
+    }
+}
+
+/// ペアと確定レベルに対するエンディングの規則
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndingRule {
+    pub pair: PairKey,
+    pub level: RelationshipLevel,
+    pub ending: Ending,
+    /// 再生するシナリオ（`assets/scenarios/` からの相対パス、省略時はエンディングの既定）
+    pub scenario: Option<String>,
+}
+
+/// エンディングの判定結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndingResolution {
+    /// ペアごとのエンディング（ペア順）
+    pub pair_endings: Vec<(PairKey, Ending)>,
+    /// 再生するエンディング
+    pub ending: Ending,
+    /// エンディングを決めたペア（確定したペアがなければ None）
+    pub pair: Option<PairKey>,
+    /// 再生するシナリオ（規則表に指定がなければ None）
+    pub scenario: Option<String>,
+}
+
+/// エンディングの規則表（Bevy Resource）
+///
+/// 個別の規則がないペアはレベルごとの既定（親密 → Good、通常 → Normal、対立 → Bad）で判定し、
+/// 最も優先順位の高いエンディングを再生する
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct EndingTable {
+    /// ルート確定の対象（主人公）
+    pub main_character: String,
+    defaults: HashMap<RelationshipLevel, Ending>,
+    rules: Vec<EndingRule>,
+    scenarios: HashMap<Ending, String>,
+}
+
+impl Default for EndingTable {
+    fn default() -> Self {
+        Self {
+            main_character: "souma".to_string(),
+            defaults: HashMap::from([
+                (RelationshipLevel::Intimate, Ending::Good),
+                (RelationshipLevel::Normal, Ending::Normal),
+                (RelationshipLevel::Conflict, Ending::Bad),
+            ]),
+            rules: Vec::new(),
+            scenarios: HashMap::new(),
+        }
+    }
+}
+
+impl EndingTable {
+    /// レベルごとの既定のエンディングを変更
+    pub fn set_default(&mut self, level: RelationshipLevel, ending: Ending) {
+        self.defaults.insert(level, ending);
+    }
+
+    /// 個別の規則を追加（同じペア・レベルの規則は置き換える）
+    pub fn add_rule(&mut self, rule: EndingRule) {
+        match self.rules.iter_mut().find(|existing| existing.pair == rule.pair && existing.level == rule.level) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+    }
+
+    pub fn rules(&self) -> &[EndingRule] {
+        &self.rules
+    }
+
+    /// エンディングごとの既定のシナリオを設定
+    pub fn set_scenario(&mut self, ending: Ending, scenario: &str) {
+        self.scenarios.insert(ending, scenario.to_string());
+    }
+
+    /// ペアの確定レベルに対する規則（個別の規則がなければ None）
+    pub fn rule_for(&self, pair: &PairKey, level: RelationshipLevel) -> Option<(usize, &EndingRule)> {
+        self.rules.iter().enumerate().find(|(_, rule)| &rule.pair == pair && rule.level == level)
+    }
+
+    /// ペアの確定レベルに対するエンディング
+    pub fn ending_for(&self, pair: &PairKey, level: RelationshipLevel) -> Ending {
+        self.rule_for(pair, level)
+            .map(|(_, rule)| rule.ending)
+            .or_else(|| self.defaults.get(&level).copied())
+            .unwrap_or(Ending::Normal)
+    }
+
+    /// 確定したルートからエンディングを判定
+    pub fn resolve(&self, lock: &RouteLock) -> EndingResolution {
+        let pair_endings: Vec<(PairKey, Ending)> = lock
+            .levels()
+            .iter()
+            .map(|(pair, level)| (pair.clone(), self.ending_for(pair, *level)))
+            .collect();
+
+        // 優先順位が高く、個別の規則があり、規則表で先にあるもの（最後はペア順で先のもの）を選ぶ
+        let chosen = lock
+            .levels()
+            .iter()
+            .zip(&pair_endings)
+            .enumerate()
+            .max_by_key(|(order, ((pair, level), (_, ending)))| {
+                let rule_order = self.rule_for(pair, **level).map(|(index, _)| index);
+                (
+                    ending.rank(),
+                    rule_order.is_some(),
+                    std::cmp::Reverse(rule_order.unwrap_or(usize::MAX)),
+                    std::cmp::Reverse(*order),
+                )
+            });
+
+        let Some((_, ((pair, level), (_, ending)))) = chosen else {
+            return EndingResolution {
+                pair_endings,
+                ending: Ending::Normal,
+                pair: None,
+                scenario: self.scenarios.get(&Ending::Normal).cloned(),
+            };
+        };
+
+        let scenario = self
+            .rule_for(pair, *level)
+            .and_then(|(_, rule)| rule.scenario.clone())
+            .or_else(|| self.scenarios.get(ending).cloned());
+
+        EndingResolution {
+            ending: *ending,
+            pair: Some(pair.clone()),
+            scenario,
+            pair_endings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_table() -> EndingTable {
+        let mut table = EndingTable::default();
+        table.add_rule(EndingRule {
+            pair: PairKey::new("souma", "yuzuki"),
+            level: RelationshipLevel::Intimate,
+            ending: Ending::True,
+            scenario: Some("endings/souma_yuzuki_true.md".to_string()),
+        });
+        table.add_rule(EndingRule {
+            pair: PairKey::new("souma", "kai"),
+            level: RelationshipLevel::Intimate,
+            ending: Ending::True,
+            scenario: Some("endings/souma_kai_true.md".to_string()),
+        });
+        table.set_scenario(Ending::Good, "endings/good.md");
+        table.set_scenario(Ending::Normal, "endings/normal.md");
+        table.set_scenario(Ending::Bad, "endings/bad.md");
+        table
+    }
+
+    #[test]
+    fn route_lock_keeps_only_main_character_pairs() {
+        let lock = RouteLock::new("souma", [
+            (PairKey::new("souma", "yuzuki"), RelationshipLevel::Intimate),
+            (PairKey::new("yuzuki", "kai"), RelationshipLevel::Conflict),
+            // 部分一致するIDでも別のキャラクターとして扱う
+            (PairKey::new("soumaX", "kai"), RelationshipLevel::Intimate),
+        ]);
+
+        assert_eq!(lock.levels().len(), 1);
+        assert_eq!(lock.level_with("yuzuki"), Some(RelationshipLevel::Intimate));
+        assert_eq!(lock.level_with("kai"), None);
+    }
+
+    #[test]
+    fn resolve_prefers_true_ending_rules() {
+        let table = sample_table();
+        let lock = RouteLock::new("souma", [
+            (PairKey::new("souma", "retsuji"), RelationshipLevel::Intimate),
+            (PairKey::new("souma", "yuzuki"), RelationshipLevel::Intimate),
+            (PairKey::new("souma", "kai"), RelationshipLevel::Intimate),
+        ]);
+
+        let resolution = table.resolve(&lock);
+        assert_eq!(resolution.ending, Ending::True);
+        // 同じ順位なら規則表の先の規則
+        assert_eq!(resolution.pair, Some(PairKey::new("souma", "yuzuki")));
+        assert_eq!(resolution.scenario.as_deref(), Some("endings/souma_yuzuki_true.md"));
+        assert!(resolution.pair_endings.contains(&(PairKey::new("souma", "retsuji"), Ending::Good)));
+    }
+
+    #[test]
+    fn resolve_falls_back_to_level_defaults() {
+        let table = sample_table();
+
+        let lock = RouteLock::new("souma", [
+            (PairKey::new("souma", "yuzuki"), RelationshipLevel::Normal),
+            (PairKey::new("souma", "kai"), RelationshipLevel::Conflict),
+        ]);
+        let resolution = table.resolve(&lock);
+        assert_eq!(resolution.ending, Ending::Normal);
+        assert_eq!(resolution.scenario.as_deref(), Some("endings/normal.md"));
+
+        let lock = RouteLock::new("souma", [(PairKey::new("souma", "kai"), RelationshipLevel::Conflict)]);
+        let resolution = table.resolve(&lock);
+        assert_eq!(resolution.ending, Ending::Bad);
+        assert_eq!(resolution.pair, Some(PairKey::new("souma", "kai")));
+
+        let resolution = table.resolve(&RouteLock::new("souma", []));
+        assert_eq!((resolution.ending, resolution.pair), (Ending::Normal, None));
+    }
+
+    #[test]
+    fn ending_names() {
+        for ending in [Ending::True, Ending::Good, Ending::Normal, Ending::Bad] {
+            assert_eq!(Ending::from_name(ending.as_str()), Some(ending));
+        }
+        assert_eq!(Ending::from_name("secret"), None);
+    }
+}
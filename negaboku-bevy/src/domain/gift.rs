@@ -0,0 +1,218 @@
+//! プレゼント - 拠点で渡すアイテムとキャラクターごとの好み
+//!
+//! # 責務
+//! - プレゼントのアイテム定義（ID・名前・種類）
+//! - キャラクターごとの種類ごとの好み（好物 +10 / 普通 +5 / 苦手 -5、プレイヤーには非公開）
+//! - 渡したときの反応で判明した好みの記録（好みの手帳）
+//!
+//! 関係値の変動は戦闘・イベントが主で、プレゼントは微調整のための補助
+
+use std::collections::{BTreeMap, HashMap};
+
+/// プレゼントへの反応（好みの度合い）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GiftReaction {
+    /// 好物
+    Favorite,
+    /// 普通
+    Normal,
+    /// 苦手
+    Disliked,
+}
+
+impl GiftReaction {
+    /// 関係値の変動量
+    pub fn delta(self) -> i32 {
+        match self {
+            GiftReaction::Favorite => 10,
+            GiftReaction::Normal => 5,
+            GiftReaction::Disliked => -5,
+        }
+    }
+
+    /// 設定ファイル・反応シナリオのファイル名での表記
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GiftReaction::Favorite => "favorite",
+            GiftReaction::Normal => "normal",
+            GiftReaction::Disliked => "disliked",
+        }
+    }
+
+    /// 表記から変換
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "favorite" => Some(GiftReaction::Favorite),
+            "normal" => Some(GiftReaction::Normal),
+            "disliked" => Some(GiftReaction::Disliked),
+            _ => None,
+        }
+    }
+}
+
+/// プレゼントのアイテム
+#[derive(Debug, Clone, PartialEq)]
+pub struct GiftItem {
+    pub id: String,
+    pub name: String,
+    /// 種類（flowers / books など、好みはこの単位で決まる）
+    pub category: String,
+}
+
+impl GiftItem {
+    pub fn new(id: &str, name: &str, category: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            category: category.to_string(),
+        }
+    }
+}
+
+/// アイテム定義の一覧（定義順）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GiftCatalog {
+    items: Vec<GiftItem>,
+}
+
+impl GiftCatalog {
+    /// アイテムを追加（同じIDのアイテムは置き換える）
+    pub fn insert(&mut self, item: GiftItem) {
+        match self.items.iter_mut().find(|existing| existing.id == item.id) {
+            Some(existing) => *existing = item,
+            None => self.items.push(item),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&GiftItem> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    pub fn items(&self) -> &[GiftItem] {
+        &self.items
+    }
+
+    /// アイテムの種類（重複なし・種類名順）
+    pub fn categories(&self) -> Vec<&str> {
+        let mut categories: Vec<&str> = self.items.iter().map(|item| item.category.as_str()).collect();
+        categories.sort_unstable();
+        categories.dedup();
+        categories
+    }
+}
+
+/// キャラクターごとの好み（指定のない種類は普通）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GiftPreferences {
+    preferences: HashMap<String, HashMap<String, GiftReaction>>,
+}
+
+impl GiftPreferences {
+    pub fn set(&mut self, character_id: &str, category: &str, reaction: GiftReaction) {
+        self.preferences
+            .entry(character_id.to_string())
+            .or_default()
+            .insert(category.to_string(), reaction);
+    }
+
+    /// 種類に対する反応
+    pub fn reaction(&self, character_id: &str, category: &str) -> GiftReaction {
+        self.preferences
+            .get(character_id)
+            .and_then(|preferences| preferences.get(category))
+            .copied()
+            .unwrap_or(GiftReaction::Normal)
+    }
+
+    /// 好みが定義されたキャラクター（ID順）
+    pub fn characters(&self) -> Vec<&str> {
+        let mut characters: Vec<&str> = self.preferences.keys().map(String::as_str).collect();
+        characters.sort_unstable();
+        characters
+    }
+}
+
+/// 判明した好みの記録（好みの手帳）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreferenceJournal {
+    known: BTreeMap<(String, String), GiftReaction>,
+}
+
+impl PreferenceJournal {
+    /// 反応から判明した好みを記録（初めて判明した場合は true）
+    pub fn learn(&mut self, character_id: &str, category: &str, reaction: GiftReaction) -> bool {
+        self.known
+            .insert((character_id.to_string(), category.to_string()), reaction)
+            .is_none()
+    }
+
+    /// 判明している好み（まだ知らなければ None）
+    pub fn known(&self, character_id: &str, category: &str) -> Option<GiftReaction> {
+        self.known.get(&(character_id.to_string(), category.to_string())).copied()
+    }
+
+    /// 判明している全ての好み（キャラクターID・種類の順）
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str, GiftReaction)> {
+        self.known
+            .iter()
+            .map(|((character_id, category), reaction)| (character_id.as_str(), category.as_str(), *reaction))
+    }
+
+    pub fn len(&self) -> usize {
+        self.known.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.known.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reaction_deltas() {
+        assert_eq!(GiftReaction::Favorite.delta(), 10);
+        assert_eq!(GiftReaction::Normal.delta(), 5);
+        assert_eq!(GiftReaction::Disliked.delta(), -5);
+
+        for reaction in [GiftReaction::Favorite, GiftReaction::Normal, GiftReaction::Disliked] {
+            assert_eq!(GiftReaction::from_name(reaction.as_str()), Some(reaction));
+        }
+        assert_eq!(GiftReaction::from_name("love"), None);
+    }
+
+    #[test]
+    fn test_preferences_default_to_normal() {
+        let mut preferences = GiftPreferences::default();
+        preferences.set("yuzuki", "flowers", GiftReaction::Favorite);
+
+        assert_eq!(preferences.reaction("yuzuki", "flowers"), GiftReaction::Favorite);
+        assert_eq!(preferences.reaction("yuzuki", "weapons"), GiftReaction::Normal);
+        assert_eq!(preferences.reaction("kai", "flowers"), GiftReaction::Normal);
+    }
+
+    #[test]
+    fn test_journal_learns_once() {
+        let mut journal = PreferenceJournal::default();
+        assert!(journal.learn("yuzuki", "flowers", GiftReaction::Favorite));
+        assert!(!journal.learn("yuzuki", "flowers", GiftReaction::Favorite));
+
+        assert_eq!(journal.known("yuzuki", "flowers"), Some(GiftReaction::Favorite));
+        assert_eq!(journal.known("yuzuki", "books"), None);
+        assert_eq!(journal.len(), 1);
+    }
+
+    #[test]
+    fn test_catalog_categories() {
+        let mut catalog = GiftCatalog::default();
+        catalog.insert(GiftItem::new("rose", "バラの花束", "flowers"));
+        catalog.insert(GiftItem::new("novel", "冒険小説", "books"));
+        catalog.insert(GiftItem::new("lily", "白百合", "flowers"));
+
+        assert_eq!(catalog.categories(), vec!["books", "flowers"]);
+        assert_eq!(catalog.get("novel").unwrap().category, "books");
+        assert_eq!(catalog.items().len(), 3);
+    }
+}
@@ -0,0 +1,220 @@
+//! 嫉妬の三角関係 - ペア同士の関係値の連動と関係値の条件
+//!
+//! # 責務
+//! - 三角関係の規則（A が B を想い、B と C が親密なら A は C に嫉妬する）
+//! - B × C の関係値の上昇を A × C の低下に連動させる割合
+//! - 関係値・関係レベル・嫉妬を判定する条件（超対立技の解放などに使う）
+//!
+//! 条件の評価は関係値を持つ `RelationshipService::satisfies` が行う
+
+use crate::domain::relationship::{PairKey, RelationshipLevel};
+
+/// 嫉妬の連動の既定の割合（B × C の上昇分に対する A × C の低下分、%）
+pub const DEFAULT_DRIVE_PERCENT: i32 = 50;
+
+/// 三角関係の規則
+///
+/// `jealous`（A）と `beloved`（B）が親密な間に `beloved` と `rival`（C）の関係値が上がると、
+/// A × C の関係値が `drive_percent`% 下がる。B × C も親密なら A は C に嫉妬している
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriangleRule {
+    /// 嫉妬する側（A）
+    pub jealous: String,
+    /// 想い人（B）
+    pub beloved: String,
+    /// 恋敵（C）
+    pub rival: String,
+    /// B × C の上昇分に対する A × C の低下分（%）
+    pub drive_percent: i32,
+}
+
+impl TriangleRule {
+    pub fn new(jealous: &str, beloved: &str, rival: &str) -> Self {
+        Self {
+            jealous: jealous.to_string(),
+            beloved: beloved.to_string(),
+            rival: rival.to_string(),
+            drive_percent: DEFAULT_DRIVE_PERCENT,
+        }
+    }
+
+    /// 嫉妬する側と想い人のペア（A × B）
+    pub fn affection_pair(&self) -> PairKey {
+        PairKey::new(&self.jealous, &self.beloved)
+    }
+
+    /// 想い人と恋敵のペア（B × C、このペアの上昇が嫉妬を呼ぶ）
+    pub fn trigger_pair(&self) -> PairKey {
+        PairKey::new(&self.beloved, &self.rival)
+    }
+
+    /// 嫉妬で関係値が下がるペア（A × C）
+    pub fn jealousy_pair(&self) -> PairKey {
+        PairKey::new(&self.jealous, &self.rival)
+    }
+
+    /// B × C の上昇分に対する A × C の変動量（上昇でなければ 0）
+    pub fn driven_delta(&self, applied_delta: i32) -> i32 {
+        if applied_delta <= 0 {
+            return 0;
+        }
+        -(applied_delta * self.drive_percent / 100)
+    }
+}
+
+/// 三角関係の規則の一覧（定義順）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TriangleRuleTable {
+    rules: Vec<TriangleRule>,
+}
+
+impl TriangleRuleTable {
+    /// 規則を追加（同じ A・B・C の規則は置き換える）
+    pub fn insert(&mut self, rule: TriangleRule) {
+        match self.rules.iter_mut().find(|existing| {
+            existing.jealous == rule.jealous && existing.beloved == rule.beloved && existing.rival == rule.rival
+        }) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+    }
+
+    pub fn rules(&self) -> &[TriangleRule] {
+        &self.rules
+    }
+
+    /// ペアの関係値の上昇が嫉妬を呼ぶ規則（そのペアが B × C の規則）
+    pub fn triggered_by<'a>(&'a self, pair: &'a PairKey) -> impl Iterator<Item = &'a TriangleRule> + 'a {
+        self.rules.iter().filter(move |rule| &rule.trigger_pair() == pair)
+    }
+
+    /// A が C に嫉妬しうる規則
+    pub fn between<'a>(&'a self, jealous: &'a str, rival: &'a str) -> impl Iterator<Item = &'a TriangleRule> + 'a {
+        self.rules.iter().filter(move |rule| rule.jealous == jealous && rule.rival == rival)
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// 関係値の条件
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelationshipCondition {
+    /// ペアの関係レベルが一致する
+    Level { a: String, b: String, level: RelationshipLevel },
+    /// ペアの関係値が指定値以上
+    AtLeast { a: String, b: String, value: i32 },
+    /// ペアの関係値が指定値以下
+    AtMost { a: String, b: String, value: i32 },
+    /// `jealous` が `rival` に嫉妬している
+    Jealous { jealous: String, rival: String },
+    /// すべて満たす
+    All(Vec<RelationshipCondition>),
+    /// いずれかを満たす
+    Any(Vec<RelationshipCondition>),
+}
+
+impl RelationshipCondition {
+    /// A が C に嫉妬している
+    pub fn jealous(jealous: &str, rival: &str) -> Self {
+        RelationshipCondition::Jealous {
+            jealous: jealous.to_string(),
+            rival: rival.to_string(),
+        }
+    }
+
+    /// 2人のどちらかが相手に嫉妬している
+    pub fn either_jealous(a: &str, b: &str) -> Self {
+        RelationshipCondition::Any(vec![Self::jealous(a, b), Self::jealous(b, a)])
+    }
+
+    /// ペアの関係値が指定値以下
+    pub fn at_most(a: &str, b: &str, value: i32) -> Self {
+        RelationshipCondition::AtMost {
+            a: a.to_string(),
+            b: b.to_string(),
+            value,
+        }
+    }
+
+    /// 条件を評価（関係値・関係レベル・嫉妬の判定は呼び出し側が渡す）
+    pub fn evaluate(
+        &self,
+        value: &dyn Fn(&str, &str) -> i32,
+        level: &dyn Fn(&str, &str) -> RelationshipLevel,
+        is_jealous: &dyn Fn(&str, &str) -> bool,
+    ) -> bool {
+        match self {
+            RelationshipCondition::Level { a, b, level: expected } => level(a, b) == *expected,
+            RelationshipCondition::AtLeast { a, b, value: min } => value(a, b) >= *min,
+            RelationshipCondition::AtMost { a, b, value: max } => value(a, b) <= *max,
+            RelationshipCondition::Jealous { jealous, rival } => is_jealous(jealous, rival),
+            RelationshipCondition::All(conditions) => {
+                conditions.iter().all(|condition| condition.evaluate(value, level, is_jealous))
+            }
+            RelationshipCondition::Any(conditions) => {
+                conditions.iter().any(|condition| condition.evaluate(value, level, is_jealous))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triangle_rule_pairs_and_drive() {
+        let rule = TriangleRule::new("yuzuki", "souma", "celine");
+
+        assert_eq!(rule.affection_pair(), PairKey::new("souma", "yuzuki"));
+        assert_eq!(rule.trigger_pair(), PairKey::new("celine", "souma"));
+        assert_eq!(rule.jealousy_pair(), PairKey::new("yuzuki", "celine"));
+
+        assert_eq!(rule.driven_delta(10), -5);
+        assert_eq!(rule.driven_delta(-10), 0);
+        assert_eq!(TriangleRule { drive_percent: 100, ..rule }.driven_delta(7), -7);
+    }
+
+    #[test]
+    fn table_lookup() {
+        let mut table = TriangleRuleTable::default();
+        table.insert(TriangleRule::new("yuzuki", "souma", "celine"));
+        table.insert(TriangleRule::new("celine", "souma", "yuzuki"));
+        table.insert(TriangleRule { drive_percent: 30, ..TriangleRule::new("yuzuki", "souma", "celine") });
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.rules()[0].drive_percent, 30);
+        assert_eq!(table.triggered_by(&PairKey::new("souma", "celine")).count(), 1);
+        assert_eq!(table.between("celine", "yuzuki").count(), 1);
+        assert_eq!(table.between("yuzuki", "souma").count(), 0);
+    }
+
+    #[test]
+    fn condition_evaluation() {
+        let value = |a: &str, b: &str| if PairKey::new(a, b) == PairKey::new("yuzuki", "celine") { -100 } else { 0 };
+        let level = |_: &str, _: &str| RelationshipLevel::Normal;
+        let is_jealous = |jealous: &str, rival: &str| jealous == "yuzuki" && rival == "celine";
+
+        let super_conflict = RelationshipCondition::All(vec![
+            RelationshipCondition::either_jealous("celine", "yuzuki"),
+            RelationshipCondition::at_most("celine", "yuzuki", -100),
+        ]);
+        assert!(super_conflict.evaluate(&value, &level, &is_jealous));
+
+        assert!(!RelationshipCondition::jealous("celine", "yuzuki").evaluate(&value, &level, &is_jealous));
+        assert!(!RelationshipCondition::Level {
+            a: "souma".to_string(),
+            b: "kai".to_string(),
+            level: RelationshipLevel::Intimate,
+        }
+        .evaluate(&value, &level, &is_jealous));
+        assert!(RelationshipCondition::AtLeast { a: "souma".to_string(), b: "kai".to_string(), value: 0 }
+            .evaluate(&value, &level, &is_jealous));
+    }
+}
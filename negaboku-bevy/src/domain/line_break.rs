@@ -0,0 +1,298 @@
+//! 改行処理 - 日本語の禁則処理付き折り返し
+//!
+//! # 責務
+//! - テキストボックス幅に合わせた事前折り返し（改行の挿入）
+//! - 禁則処理（行頭禁則・行末禁則・分離禁止）
+//! - ぶら下げ組み（句読点の行末はみ出し）
+//!
+//! 折り返しは表示前に1回だけ行い、タイプライター表示中にレイアウトが
+//! 組み替わらないようにする。文字幅はフォントを参照せず、全角=1em・半角=0.5em で見積もる。
+
+use unicode_segmentation::UnicodeSegmentation;
+use crate::domain::rich_text::{RichText, TextSegment, TextStyle};
+
+/// 行頭禁則文字（行の先頭に来てはいけない文字）
+const LINE_START_PROHIBITED: &str = "、。，．,.・：；:;？！?!…‥ー－～)）］]｝}〕〉》」』】〙〗〟’”ゝゞヽヾ々ぁぃぅぇぉっゃゅょゎゕゖァィゥェォッャュョヮヵヶㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ";
+
+/// 行末禁則文字（行の末尾に来てはいけない文字）
+const LINE_END_PROHIBITED: &str = "(（[［{｛〔〈《「『【〘〖〝‘“";
+
+/// ぶら下げ可能な句読点
+const HANGING_PUNCTUATION: &str = "、。，．,.";
+
+/// 分離禁止文字（同じ文字が続く場合は間で改行しない）
+const INSEPARABLE: &str = "…‥―";
+
+/// ルビの文字サイズ（親文字に対する比率）
+const RUBY_SCALE: f32 = 0.5;
+
+/// 折り返しの設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineBreakRule {
+    /// 1行の最大幅（ピクセル）
+    pub max_width: f32,
+    /// 本文の文字サイズ（ピクセル）
+    pub font_size: f32,
+    /// 句読点のぶら下げを許可するか
+    pub hanging_punctuation: bool,
+}
+
+impl LineBreakRule {
+    pub fn new(max_width: f32, font_size: f32) -> Self {
+        Self {
+            max_width,
+            font_size,
+            hanging_punctuation: true,
+        }
+    }
+
+    /// テキストを折り返し、改行を挿入したテキストを返す
+    pub fn wrap(&self, text: &RichText) -> RichText {
+        let atoms = Self::atoms(text);
+        let breaks = self.find_breaks(&atoms);
+        if breaks.is_empty() {
+            return text.clone();
+        }
+
+        Self::insert_breaks(text, &atoms, &breaks)
+    }
+
+    /// 改行を入れる位置（この単位の前で改行する）を求める
+    fn find_breaks(&self, atoms: &[Atom]) -> Vec<usize> {
+        let max_width = self.max_width / self.font_size.max(1.0);
+        let mut breaks = Vec::new();
+        let mut line_start = 0;
+        let mut width = 0.0;
+        let mut index = 0;
+
+        while index < atoms.len() {
+            let atom = &atoms[index];
+
+            if atom.is_newline {
+                line_start = index + 1;
+                width = 0.0;
+                index += 1;
+                continue;
+            }
+
+            if width + atom.width <= max_width || index == line_start {
+                width += atom.width;
+                index += 1;
+                continue;
+            }
+
+            // ぶら下げ：句読点は行末にはみ出させる
+            if self.hanging_punctuation && HANGING_PUNCTUATION.contains(atom.first) {
+                if index + 1 < atoms.len() && !atoms[index + 1].is_newline {
+                    breaks.push(index + 1);
+                }
+                line_start = index + 1;
+                width = 0.0;
+                index += 1;
+                continue;
+            }
+
+            // 禁則に掛からない位置まで改行位置を前に戻す（追い出し）
+            let mut break_at = index;
+            while break_at > line_start + 1 && !Self::can_break_between(&atoms[break_at - 1], &atoms[break_at]) {
+                break_at -= 1;
+            }
+            if !Self::can_break_between(&atoms[break_at - 1], &atoms[break_at]) {
+                // 行全体が分割不能な場合はその位置で強制改行
+                break_at = index;
+            }
+
+            breaks.push(break_at);
+            line_start = break_at;
+            width = 0.0;
+            index = break_at;
+        }
+
+        breaks
+    }
+
+    /// 2つの単位の間で改行できるか
+    fn can_break_between(prev: &Atom, next: &Atom) -> bool {
+        if LINE_START_PROHIBITED.contains(next.first) || LINE_END_PROHIBITED.contains(prev.last) {
+            return false;
+        }
+        if prev.last == next.first && INSEPARABLE.contains(next.first) {
+            return false;
+        }
+        // 英単語・数字の途中では改行しない
+        !(prev.last.is_ascii_alphanumeric() && next.first.is_ascii_alphanumeric())
+    }
+
+    /// 表示セグメントを折り返し判定の単位（書記素クラスタ・ルビ付き語）に分解
+    fn atoms(text: &RichText) -> Vec<Atom> {
+        let mut atoms = Vec::new();
+
+        for (segment_index, segment) in text.segments.iter().enumerate() {
+            match segment {
+                TextSegment::Plain { text, .. } => {
+                    for (offset, grapheme) in text.grapheme_indices(true) {
+                        let first = grapheme.chars().next().unwrap_or(' ');
+                        atoms.push(Atom {
+                            segment: segment_index,
+                            byte_start: offset,
+                            width: grapheme.chars().map(char_width).sum(),
+                            first,
+                            last: grapheme.chars().last().unwrap_or(first),
+                            is_newline: grapheme == "\n" || grapheme == "\r\n",
+                        });
+                    }
+                }
+                TextSegment::Ruby { base, reading, .. } => {
+                    let base_width: f32 = base.chars().map(char_width).sum();
+                    let reading_width: f32 = reading.chars().map(char_width).sum::<f32>() * RUBY_SCALE;
+                    atoms.push(Atom {
+                        segment: segment_index,
+                        byte_start: 0,
+                        width: base_width.max(reading_width),
+                        first: base.chars().next().unwrap_or(' '),
+                        last: base.chars().last().unwrap_or(' '),
+                        is_newline: false,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        atoms
+    }
+
+    /// 改行位置に `\n` を挿入したテキストを組み立てる
+    fn insert_breaks(text: &RichText, atoms: &[Atom], breaks: &[usize]) -> RichText {
+        let mut segments = Vec::with_capacity(text.segments.len() + breaks.len());
+        let mut pending = breaks.iter().map(|index| &atoms[*index]).peekable();
+
+        for (segment_index, segment) in text.segments.iter().enumerate() {
+            match segment {
+                TextSegment::Plain { text, style } => {
+                    let mut wrapped = String::with_capacity(text.len() + 4);
+                    let mut copied = 0;
+                    while let Some(atom) = pending.next_if(|atom| atom.segment == segment_index) {
+                        wrapped.push_str(&text[copied..atom.byte_start]);
+                        wrapped.push('\n');
+                        copied = atom.byte_start;
+                    }
+                    wrapped.push_str(&text[copied..]);
+                    segments.push(TextSegment::Plain { text: wrapped, style: style.clone() });
+                }
+                TextSegment::Ruby { style, .. } => {
+                    if pending.next_if(|atom| atom.segment == segment_index).is_some() {
+                        segments.push(TextSegment::Plain {
+                            text: "\n".to_string(),
+                            style: TextStyle { color: style.color.clone(), bold: style.bold },
+                        });
+                    }
+                    segments.push(segment.clone());
+                }
+                _ => segments.push(segment.clone()),
+            }
+        }
+
+        RichText { segments }
+    }
+}
+
+/// 折り返し判定の単位
+struct Atom {
+    segment: usize,
+    /// セグメント本文内の開始バイト位置
+    byte_start: usize,
+    /// 幅（em）
+    width: f32,
+    first: char,
+    last: char,
+    is_newline: bool,
+}
+
+/// 文字幅の見積もり（全角=1.0em、半角=0.5em）
+pub fn char_width(c: char) -> f32 {
+    let code = c as u32;
+    let is_wide = matches!(code,
+        0x1100..=0x115F
+        | 0x2010..=0x206F   // 一般句読点（…、―、“” 等）
+        | 0x2190..=0x2BFF   // 矢印・記号・図形（★、※ 等）
+        | 0x2E80..=0xA4CF   // CJK記号・かな・漢字
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60   // 全角英数・記号
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1FAFF // 絵文字
+        | 0x20000..=0x3FFFD
+    );
+
+    match c {
+        '\n' | '\r' => 0.0,
+        '\u{0300}'..='\u{036F}' | '\u{3099}' | '\u{309A}' | '\u{FE00}'..='\u{FE0F}' | '\u{200D}' => 0.0,
+        _ if is_wide => 1.0,
+        _ => 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1行 `columns` 文字（全角）で折り返す
+    fn wrap(text: &str, columns: f32, hanging: bool) -> String {
+        let rule = LineBreakRule {
+            max_width: columns * 20.0,
+            font_size: 20.0,
+            hanging_punctuation: hanging,
+        };
+        rule.wrap(&RichText::parse(text)).plain_text()
+    }
+
+    #[test]
+    fn wraps_at_box_width() {
+        assert_eq!(wrap("あいうえおかきくけこ", 4.0, false), "あいうえ\nおかきく\nけこ");
+        assert_eq!(wrap("短い", 10.0, false), "短い");
+    }
+
+    #[test]
+    fn line_start_prohibited_characters_are_pushed_back() {
+        // 「。」「」」が行頭に来ないよう前の文字ごと次の行へ送る
+        assert_eq!(wrap("あいうえ。かき", 4.0, false), "あいう\nえ。かき");
+        assert_eq!(wrap("あいう「えお」", 4.0, false), "あいう\n「えお」");
+        assert_eq!(wrap("あいうえっと", 4.0, false), "あいう\nえっと");
+        assert_eq!(wrap("あいうえーと", 4.0, false), "あいう\nえーと");
+    }
+
+    #[test]
+    fn line_end_prohibited_characters_move_to_next_line() {
+        assert_eq!(wrap("あいう「えお", 4.0, false), "あいう\n「えお");
+        assert_eq!(wrap("あいう（えお）", 4.0, false), "あいう\n（えお）");
+    }
+
+    #[test]
+    fn hanging_punctuation() {
+        assert_eq!(wrap("あいうえ。かき", 4.0, true), "あいうえ。\nかき");
+        assert_eq!(wrap("あいうえ、", 4.0, true), "あいうえ、");
+    }
+
+    #[test]
+    fn ruby_and_words_are_not_split() {
+        assert_eq!(wrap("あいう｜召喚術師《しょうかんじゅつし》だ", 4.0, false), "あいう\n召喚術師\nだ");
+        assert_eq!(wrap("あい Bevy", 4.0, false), "あい \nBevy");
+        assert_eq!(wrap("あいう……だ", 4.0, false), "あい\nう……だ");
+    }
+
+    #[test]
+    fn styles_are_kept_across_breaks() {
+        let rule = LineBreakRule::new(80.0, 20.0);
+        let wrapped = rule.wrap(&RichText::parse("{color=red}あいうえおか{/color}"));
+
+        assert_eq!(wrapped.plain_text(), "あいうえ\nおか");
+        assert_eq!(wrapped.segments.len(), 1);
+        assert_eq!(wrapped.segments[0].style().unwrap().color.as_deref(), Some("red"));
+    }
+
+    #[test]
+    fn explicit_newlines_reset_line_width() {
+        assert_eq!(wrap("あい\nうえおか", 4.0, false), "あい\nうえおか");
+    }
+}
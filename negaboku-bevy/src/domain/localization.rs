@@ -0,0 +1,170 @@
+//! ローカライズ - 表示言語とUI文字列・セリフの翻訳
+//!
+//! # 責務
+//! - 表示言語（ロケール）の保持
+//! - UI文字列テーブル（キー → 表示文字列）の参照
+//! - セリフID単位の翻訳の参照
+//! - 翻訳がない場合の日本語（ソース言語）へのフォールバック
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::domain::scenario::DialogueBlock;
+
+/// ソース言語（シナリオ・UIの原文の言語）
+pub const SOURCE_LOCALE: &str = "ja";
+
+/// 話者名の翻訳キーの接頭辞（`speaker.ソウマ` など、表示名ごとに翻訳する）
+pub const SPEAKER_KEY_PREFIX: &str = "speaker.";
+
+/// ソース言語のUI文字列（翻訳の原文、翻訳がない場合の表示）
+pub const SOURCE_UI_STRINGS: &[(&str, &str)] = &[
+    ("title.main", "願い石と僕たちの絆"),
+    ("title.subtitle", "- Rust + Bevy版 -"),
+    ("title.menu_shortcuts", "1:はじめから 2:つづきから 3:設定 4:ギャラリー 5:ゲーム終了"),
+    ("title.help", "数字キー1-5で直接選択 / Spaceキー：選択 / Escキー：終了 / F1キー：デバッグ情報"),
+    ("menu.new_game", "はじめから"),
+    ("menu.continue", "つづきから"),
+    ("menu.settings", "設定"),
+    ("menu.gallery", "ギャラリー"),
+    ("menu.exit", "ゲーム終了"),
+    ("story.log", "ログ"),
+    ("story.skip", "スキップ"),
+    ("story.auto", "オート"),
+    ("story.save", "セーブ"),
+    ("story.load", "ロード"),
+    ("story.help", "操作: Spaceキー = 次へ / Lキー or ログボタン = ログ表示 / Escキー = タイトルに戻る"),
+    ("log.title", "会話ログ"),
+    ("log.close", "閉じる"),
+    ("chart.title", "相関図"),
+    ("chart.help", "←→キー or クリック = キャラクター選択 / Escキー = 閉じる"),
+    ("chart.level.intimate", "親密"),
+    ("chart.level.normal", "通常"),
+    ("chart.level.conflict", "対立"),
+    ("chart.special", "特別イベントあり"),
+    ("chart.no_special", "特別な要素なし"),
+    ("gift.title", "プレゼント"),
+    ("gift.help", "←→キー = 渡す相手 / ↑↓キー = アイテム / Enterキー = 渡す / Escキー = 閉じる"),
+    ("gift.receivers", "渡す相手"),
+    ("gift.items", "持ち物"),
+    ("gift.journal", "好みの手帳"),
+    ("gift.unknown", "？？？"),
+    ("gift.learned", "手帳に記録"),
+    ("gift.reaction.favorite", "好物"),
+    ("gift.reaction.normal", "普通"),
+    ("gift.reaction.disliked", "苦手"),
+    ("gift.category.flowers", "花"),
+    ("gift.category.sweets", "お菓子"),
+    ("gift.category.books", "本"),
+    ("gift.category.weapons", "武具"),
+    ("gift.category.accessories", "装飾品"),
+    ("settings.title", "設定"),
+    ("settings.help", "↑↓キー = 項目選択 / Enterキー or ←→キー = 変更 / Escキー = 保存して閉じる"),
+    ("settings.highlight_speaker", "話者の立ち絵を強調する"),
+    ("settings.dim_non_speakers", "話者以外の立ち絵を暗くする"),
+    ("settings.on", "オン"),
+    ("settings.off", "オフ"),
+    ("settings.locale", "表示言語"),
+    ("settings.locale.ja", "日本語"),
+    ("settings.locale.en", "English"),
+];
+
+/// ソース言語のUI文字列を取得
+pub fn source_ui_string(key: &str) -> Option<&'static str> {
+    SOURCE_UI_STRINGS
+        .iter()
+        .find(|(source_key, _)| *source_key == key)
+        .map(|(_, text)| *text)
+}
+
+/// 表示言語と翻訳テーブル（Bevy Resource）
+///
+/// 表示言語を切り替えるときは、その言語の翻訳を読み込んだ新しい値で置き換える
+#[derive(Resource, Debug, Clone)]
+pub struct Localization {
+    /// 表示言語（`ja`, `en` など）
+    pub locale: String,
+    /// UI文字列（キー → 翻訳）
+    ui_strings: HashMap<String, String>,
+    /// セリフの翻訳（セリフID → 翻訳）
+    line_texts: HashMap<String, String>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self::new(SOURCE_LOCALE, HashMap::new(), HashMap::new())
+    }
+}
+
+impl Localization {
+    pub fn new(locale: &str, ui_strings: HashMap<String, String>, line_texts: HashMap<String, String>) -> Self {
+        Self {
+            locale: locale.to_string(),
+            ui_strings,
+            line_texts,
+        }
+    }
+
+    /// UI文字列を取得（翻訳 → 日本語 → キーそのもの の順にフォールバック）
+    pub fn ui<'a>(&'a self, key: &'a str) -> &'a str {
+        self.ui_strings
+            .get(key)
+            .map(String::as_str)
+            .or_else(|| source_ui_string(key))
+            .unwrap_or(key)
+    }
+
+    /// セリフ本文を取得（翻訳がなければ原文）
+    pub fn line_text<'a>(&'a self, block: &'a DialogueBlock) -> &'a str {
+        self.line_texts
+            .get(&block.line_id)
+            .map(String::as_str)
+            .unwrap_or(&block.text)
+    }
+
+    /// 話者の表示名を取得（翻訳がなければ原文）
+    pub fn speaker_name<'a>(&'a self, name: &'a str) -> &'a str {
+        if name.is_empty() {
+            return name;
+        }
+        self.ui_strings
+            .get(&format!("{}{}", SPEAKER_KEY_PREFIX, name))
+            .map(String::as_str)
+            .unwrap_or(name)
+    }
+
+    /// 翻訳済みのUI文字列数とセリフ数
+    pub fn translated_counts(&self) -> (usize, usize) {
+        (self.ui_strings.len(), self.line_texts.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_to_source_locale() {
+        let ui_strings = HashMap::from([("menu.new_game".to_string(), "New Game".to_string())]);
+        let line_texts = HashMap::from([("ch1_go".to_string(), "Let's go".to_string())]);
+        let localization = Localization::new("en", ui_strings, line_texts);
+
+        assert_eq!(localization.ui("menu.new_game"), "New Game");
+        assert_eq!(localization.ui("menu.exit"), "ゲーム終了");
+        assert_eq!(localization.ui("unknown.key"), "unknown.key");
+
+        let mut block = DialogueBlock::parse("**ソウマ**「行こう」{#ch1_go}").unwrap();
+        assert_eq!(localization.line_text(&block), "Let's go");
+        block.line_id = "ch1_other".to_string();
+        assert_eq!(localization.line_text(&block), "行こう");
+    }
+
+    #[test]
+    fn test_speaker_name_translation() {
+        let ui_strings = HashMap::from([("speaker.ソウマ".to_string(), "Souma".to_string())]);
+        let localization = Localization::new("en", ui_strings, HashMap::new());
+
+        assert_eq!(localization.speaker_name("ソウマ"), "Souma");
+        assert_eq!(localization.speaker_name("？？？"), "？？？");
+        assert_eq!(localization.speaker_name(""), "");
+    }
+}
@@ -2,11 +2,29 @@
 //!
 //! このモジュールには以下が含まれます：
 //! - 関係値システム（relationship）
+//! - 関係値の変動履歴（relationship_history）
+//! - 嫉妬の三角関係と関係値の条件（jealousy）
+//! - ルート確定とエンディング判定（ending）
+//! - プレゼントと好みの手帳（gift）
 //! - 戦闘システム（battle）
 //! - シナリオ管理（scenario）
+//! - シナリオマクロ（scenario_macro）
+//! - シーンコマンドハンドラの登録（command_registry）
+//! - セリフ本文のマークアップ（rich_text）
+//! - 禁則処理付きの折り返し（line_break）
 //! - キャラクター定義（character）
+//! - 表示言語と翻訳（localization）
 
 pub mod relationship;
+pub mod relationship_history;
+pub mod jealousy;
+pub mod ending;
+pub mod gift;
 pub mod battle;
 pub mod scenario;
+pub mod scenario_macro;
+pub mod command_registry;
+pub mod rich_text;
+pub mod line_break;
 pub mod character;
+pub mod localization;
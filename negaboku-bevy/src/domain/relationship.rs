@@ -4,40 +4,397 @@
 //! - 関係値の定義と管理
 //! - 関係レベルの判定
 //! - 関係値変動の計算
+//! - 章進行による関係値キャップ（±30 / ±50 / ±100）
+//! - 関係レベルの判定基準（既定値とペアごとの上書き）
+//! - ペア定義（初期値・推奨／対立ペア・専用イベント・固定変動）
 
-// use bevy::prelude::*; // 将来使用予定
+use std::collections::HashMap;
 
-/// 関係値の定義
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// 関係値の絶対的な上限（終盤キャップと同じ）
+pub const RELATIONSHIP_LIMIT: i32 = 100;
+
+/// 関係値の定義（範囲は既定の判定基準の場合）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RelationshipLevel {
-    /// 対立状態 (-100 ~ -1)
+    /// 対立状態 (-100 ~ -50)
     Conflict,
-    /// 通常状態 (0 ~ 49)
+    /// 通常状態 (-49 ~ 49)
     Normal,
     /// 親密状態 (50 ~ 100)
     Intimate,
 }
 
+impl RelationshipLevel {
+    /// セーブデータ・設定ファイルでの表記
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RelationshipLevel::Conflict => "conflict",
+            RelationshipLevel::Normal => "normal",
+            RelationshipLevel::Intimate => "intimate",
+        }
+    }
+
+    /// 表記から変換
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "conflict" => Some(RelationshipLevel::Conflict),
+            "normal" => Some(RelationshipLevel::Normal),
+            "intimate" => Some(RelationshipLevel::Intimate),
+            _ => None,
+        }
+    }
+}
+
+/// キャラクターのペアを表すキー（順序を問わない）
+///
+/// `PairKey::new("souma", "kai")` と `PairKey::new("kai", "souma")` は等しい
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PairKey {
+    first: String,
+    second: String,
+}
+
+impl PairKey {
+    pub fn new(character_a: &str, character_b: &str) -> Self {
+        let (first, second) = if character_a <= character_b {
+            (character_a, character_b)
+        } else {
+            (character_b, character_a)
+        };
+        Self {
+            first: first.to_string(),
+            second: second.to_string(),
+        }
+    }
+
+    /// ID順で前のキャラクター
+    pub fn first(&self) -> &str {
+        &self.first
+    }
+
+    /// ID順で後のキャラクター
+    pub fn second(&self) -> &str {
+        &self.second
+    }
+
+    /// キャラクターがこのペアに含まれるか（IDの完全一致）
+    pub fn contains(&self, character_id: &str) -> bool {
+        self.first == character_id || self.second == character_id
+    }
+
+    /// ペアの相手（キャラクターが含まれない場合は None）
+    pub fn partner_of(&self, character_id: &str) -> Option<&str> {
+        if self.first == character_id {
+            Some(&self.second)
+        } else if self.second == character_id {
+            Some(&self.first)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for PairKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}⇔{}", self.first, self.second)
+    }
+}
+
+/// ペアの定義（データファイルから読み込む）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairDefinition {
+    pub key: PairKey,
+    /// 初期の関係値（例: ソウマ × カイ は -25）
+    pub initial_value: i32,
+    /// 推奨ペア（専用イベント・エンディングCGあり）
+    pub is_recommended: bool,
+    /// 対立ペア（特殊対立技・三角関係など）
+    pub is_opposed: bool,
+    /// このペアの専用イベントID
+    pub special_events: Vec<String>,
+    /// 変動値が固定のペア（仲間同士ペア）。変動はこの大きさで、向きだけ指定に従う
+    pub fixed_delta: Option<i32>,
+}
+
+impl PairDefinition {
+    pub fn new(character_a: &str, character_b: &str) -> Self {
+        Self {
+            key: PairKey::new(character_a, character_b),
+            initial_value: 0,
+            is_recommended: false,
+            is_opposed: false,
+            special_events: Vec::new(),
+            fixed_delta: None,
+        }
+    }
+
+    /// 特別な要素（推奨・対立・専用イベント）があるか（相関図の詳細パネルに表示）
+    pub fn has_special_content(&self) -> bool {
+        self.is_recommended || self.is_opposed || !self.special_events.is_empty()
+    }
+
+    /// 変動量にこのペアの規則を適用（固定変動ペアは大きさを固定）
+    pub fn adjust_delta(&self, delta: i32) -> i32 {
+        match self.fixed_delta {
+            Some(fixed) => delta.signum() * fixed.abs(),
+            None => delta,
+        }
+    }
+}
+
+/// ペア定義の一覧
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelationshipPairTable {
+    pairs: HashMap<PairKey, PairDefinition>,
+}
+
+impl RelationshipPairTable {
+    /// ペア定義を追加（同じペアがあれば置き換える）
+    pub fn insert(&mut self, definition: PairDefinition) {
+        self.pairs.insert(definition.key.clone(), definition);
+    }
+
+    pub fn get(&self, key: &PairKey) -> Option<&PairDefinition> {
+        self.pairs.get(key)
+    }
+
+    /// 全ペア定義（キー順）
+    pub fn definitions(&self) -> Vec<&PairDefinition> {
+        let mut definitions: Vec<&PairDefinition> = self.pairs.values().collect();
+        definitions.sort_by(|a, b| a.key.cmp(&b.key));
+        definitions
+    }
+
+    /// ペアの初期値（定義がなければ 0）
+    pub fn initial_value(&self, key: &PairKey) -> i32 {
+        self.get(key).map(|definition| definition.initial_value).unwrap_or(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+/// 関係レベルの判定基準
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelationshipThresholds {
+    /// この値以下で対立
+    pub conflict_max: i32,
+    /// この値以上で親密
+    pub intimate_min: i32,
+    /// 親密・対立から通常に戻るまでの余裕幅（0 で無効）
+    ///
+    /// 境界付近の ±1 の変動でレベルが行き来しないよう、
+    /// 一度親密になったら `intimate_min - hysteresis` を下回るまで親密のままにする
+    pub hysteresis: i32,
+}
+
+impl Default for RelationshipThresholds {
+    fn default() -> Self {
+        Self {
+            conflict_max: -50,
+            intimate_min: 50,
+            hysteresis: 0,
+        }
+    }
+}
+
+impl RelationshipThresholds {
+    /// 関係値からレベルを判定（`current` は現在のレベル、ヒステリシス用）
+    pub fn level_for(&self, value: i32, current: Option<RelationshipLevel>) -> RelationshipLevel {
+        let level = if value >= self.intimate_min {
+            RelationshipLevel::Intimate
+        } else if value <= self.conflict_max {
+            RelationshipLevel::Conflict
+        } else {
+            RelationshipLevel::Normal
+        };
+
+        match current {
+            Some(RelationshipLevel::Intimate) if value >= self.intimate_min - self.hysteresis => {
+                RelationshipLevel::Intimate
+            }
+            Some(RelationshipLevel::Conflict) if value <= self.conflict_max + self.hysteresis => {
+                RelationshipLevel::Conflict
+            }
+            _ => level,
+        }
+    }
+}
+
+/// 関係レベルの判定基準の設定
+///
+/// 全ペア共通の既定値と、ペアごとの上書き（対立技を早めに解放するペアなど）を持つ
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelationshipLevelConfig {
+    pub defaults: RelationshipThresholds,
+    pair_overrides: HashMap<PairKey, RelationshipThresholds>,
+}
+
+impl RelationshipLevelConfig {
+    pub fn new(defaults: RelationshipThresholds) -> Self {
+        Self {
+            defaults,
+            pair_overrides: HashMap::new(),
+        }
+    }
+
+    /// ペアの判定基準を上書き（順序は問わない）
+    pub fn set_pair_override(&mut self, character_a: &str, character_b: &str, thresholds: RelationshipThresholds) {
+        self.pair_overrides.insert(PairKey::new(character_a, character_b), thresholds);
+    }
+
+    /// ペアに適用する判定基準（上書きがなければ既定値）
+    pub fn thresholds_for(&self, character_a: &str, character_b: &str) -> RelationshipThresholds {
+        self.pair_overrides
+            .get(&PairKey::new(character_a, character_b))
+            .copied()
+            .unwrap_or(self.defaults)
+    }
+}
+
 /// 関係値エンティティ
 #[derive(Debug, Clone)]
 pub struct Relationship {
     pub character_a: String,
     pub character_b: String,
     pub value: i32, // -100 ~ 100
+    /// キャップを超えて反映されなかった変動の累計（キャップ引き上げ時に反映可能）
+    ///
+    /// 正負を相殺した1つの値で持ち、`value + banked` が常に
+    /// キャップがなかった場合の関係値になる（±100 の絶対上限で切れた分は除く）
+    pub banked: i32,
+    /// 関係レベルの判定基準
+    pub thresholds: RelationshipThresholds,
+    /// 現在の関係レベル（ヒステリシスのため変動のたびに更新）
+    level: RelationshipLevel,
+}
+
+/// 章進行による関係値キャップの段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapPhase {
+    /// 序盤 (±30)
+    Early,
+    /// 中盤 (±50)
+    Mid,
+    /// 終盤 (±100、オーブ獲得後)
+    Late,
+}
+
+impl CapPhase {
+    /// この段階での関係値の上限
+    pub fn limit(self) -> i32 {
+        match self {
+            CapPhase::Early => 30,
+            CapPhase::Mid => 50,
+            CapPhase::Late => RELATIONSHIP_LIMIT,
+        }
+    }
+
+    /// シナリオでの表記（early / mid / late）から変換
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "early" => Some(CapPhase::Early),
+            "mid" => Some(CapPhase::Mid),
+            "late" => Some(CapPhase::Late),
+            _ => None,
+        }
+    }
+}
+
+/// 関係値キャップの方針
+///
+/// `[relationship_cap]` コマンドやフロントマターの `relationship_cap` で変更する
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipCapPolicy {
+    /// 関係値の上限（-limit ~ +limit）
+    pub limit: i32,
+    /// キャップ引き上げ時に、溜まっていた超過分を反映するか
+    pub apply_banked_on_raise: bool,
+}
+
+impl RelationshipCapPolicy {
+    pub fn for_phase(phase: CapPhase) -> Self {
+        Self {
+            limit: phase.limit(),
+            apply_banked_on_raise: false,
+        }
+    }
+}
+
+impl Default for RelationshipCapPolicy {
+    /// 未設定時は制限なし（±100）
+    fn default() -> Self {
+        Self::for_phase(CapPhase::Late)
+    }
 }
 
 impl Relationship {
     pub fn new(character_a: &str, character_b: &str) -> Self {
+        Self::with_thresholds(character_a, character_b, RelationshipThresholds::default())
+    }
+
+    /// 判定基準を指定して作成
+    pub fn with_thresholds(character_a: &str, character_b: &str, thresholds: RelationshipThresholds) -> Self {
         Self {
             character_a: character_a.to_string(),
             character_b: character_b.to_string(),
             value: 0, // 初期は通常状態
+            banked: 0,
+            thresholds,
+            level: thresholds.level_for(0, None),
         }
     }
 
+    /// 判定基準を差し替え（レベルは新しい基準で判定し直す）
+    pub fn set_thresholds(&mut self, thresholds: RelationshipThresholds) {
+        self.thresholds = thresholds;
+        self.level = thresholds.level_for(self.value, None);
+    }
+
+    /// セーブデータから値を復元
+    ///
+    /// レベルは現在の判定基準で判定し直すが、保存時のレベルがあれば
+    /// それを起点にヒステリシスを効かせる（境界付近のレベルがロードで変わらない）
+    pub fn restore(&mut self, value: i32, banked: i32, saved_level: Option<RelationshipLevel>) {
+        self.value = value.clamp(-RELATIONSHIP_LIMIT, RELATIONSHIP_LIMIT);
+        self.banked = banked;
+        self.level = self.thresholds.level_for(self.value, saved_level);
+    }
+
     /// 関係値を変更
     pub fn modify(&mut self, delta: i32) {
-        self.value = (self.value + delta).clamp(-100, 100);
+        self.value = (self.value + delta).clamp(-RELATIONSHIP_LIMIT, RELATIONSHIP_LIMIT);
+        self.update_level();
+    }
+
+    /// キャップ付きで関係値を変更
+    ///
+    /// キャップを超えた分は `banked` に積む。逆向きの超過は既に溜まった分と
+    /// 相殺する（+30 超過の後に -30 超過なら溜まりは 0）。キャップ引き下げ前から
+    /// キャップ外にある値は、変動の向きにそれ以上進まないだけで引き戻さない
+    pub fn modify_capped(&mut self, delta: i32, cap: i32) {
+        let cap = cap.clamp(0, RELATIONSHIP_LIMIT);
+        let target = (self.value + delta).clamp(-RELATIONSHIP_LIMIT, RELATIONSHIP_LIMIT);
+        let capped = if delta >= 0 {
+            target.min(cap.max(self.value))
+        } else {
+            target.max((-cap).min(self.value))
+        };
+
+        self.banked += target - capped;
+        self.value = capped;
+        self.update_level();
+    }
+
+    /// 溜まっていた超過分を新しいキャップの範囲で反映（収まらない分は再び溜める）
+    pub fn release_banked(&mut self, cap: i32) {
+        let banked = std::mem::take(&mut self.banked);
+        self.modify_capped(banked, cap);
     }
 
     /// 現在の関係値を取得
@@ -47,12 +404,11 @@ impl Relationship {
 
     /// 現在の関係レベルを取得
     pub fn level(&self) -> RelationshipLevel {
-        match self.value {
-            -100..=-1 => RelationshipLevel::Conflict,
-            0..=49 => RelationshipLevel::Normal,
-            50..=100 => RelationshipLevel::Intimate,
-            _ => RelationshipLevel::Normal, // 範囲外の場合は Normal とする
-        }
+        self.level
+    }
+
+    fn update_level(&mut self) {
+        self.level = self.thresholds.level_for(self.value, Some(self.level));
     }
 }
 
@@ -78,13 +434,171 @@ mod tests {
         assert_eq!(rel.value(), 60);
         assert_eq!(rel.level(), RelationshipLevel::Intimate);
 
-        // 対立に変更
+        // -49 までは通常
         rel.modify(-80);
         assert_eq!(rel.value(), -20);
+        assert_eq!(rel.level(), RelationshipLevel::Normal);
+
+        // 対立に変更
+        rel.modify(-30);
+        assert_eq!(rel.value(), -50);
         assert_eq!(rel.level(), RelationshipLevel::Conflict);
 
         // 範囲外テスト
         rel.modify(-200);
         assert_eq!(rel.value(), -100); // クランプされる
     }
+
+    #[test]
+    fn test_pair_key_is_unordered() {
+        // `_` を含むIDでも別のペアと衝突しない
+        let a = PairKey::new("a_b", "c");
+        let b = PairKey::new("a", "b_c");
+        assert_ne!(a, b);
+
+        let key = PairKey::new("souma", "kai");
+        assert_eq!(key, PairKey::new("kai", "souma"));
+        assert_eq!(key.first(), "kai");
+        assert!(key.contains("souma"));
+        assert!(!key.contains("sou"));
+        assert_eq!(key.partner_of("souma"), Some("kai"));
+        assert_eq!(key.partner_of("yuzuki"), None);
+        assert_eq!(key.to_string(), "kai⇔souma");
+    }
+
+    #[test]
+    fn test_fixed_delta_pair() {
+        let mut definition = PairDefinition::new("retsuji", "yuzuki");
+        assert_eq!(definition.adjust_delta(25), 25);
+
+        definition.fixed_delta = Some(10);
+        assert_eq!(definition.adjust_delta(25), 10);
+        assert_eq!(definition.adjust_delta(-3), -10);
+        assert_eq!(definition.adjust_delta(0), 0);
+        assert!(!definition.has_special_content());
+
+        definition.special_events.push("retsuji_yuzuki_training".to_string());
+        assert!(definition.has_special_content());
+    }
+
+    #[test]
+    fn test_level_hysteresis() {
+        let thresholds = RelationshipThresholds { hysteresis: 5, ..Default::default() };
+        let mut rel = Relationship::with_thresholds("souma", "yuzuki", thresholds);
+
+        rel.modify(50);
+        assert_eq!(rel.level(), RelationshipLevel::Intimate);
+
+        // 境界付近の変動では親密のまま
+        rel.modify(-1);
+        assert_eq!(rel.level(), RelationshipLevel::Intimate);
+        rel.modify(-4);
+        assert_eq!(rel.level(), RelationshipLevel::Intimate);
+
+        // 余裕幅を下回ると通常に戻り、再び +50 に達するまで通常
+        rel.modify(-1);
+        assert_eq!(rel.level(), RelationshipLevel::Normal);
+        rel.modify(4);
+        assert_eq!(rel.level(), RelationshipLevel::Normal);
+
+        // 対立側も同様
+        rel.modify(-99);
+        assert_eq!(rel.level(), RelationshipLevel::Conflict);
+        rel.modify(4);
+        assert_eq!(rel.level(), RelationshipLevel::Conflict);
+    }
+
+    #[test]
+    fn test_pair_threshold_override() {
+        let mut config = RelationshipLevelConfig::default();
+        let early_conflict = RelationshipThresholds { conflict_max: -30, ..Default::default() };
+        config.set_pair_override("souma", "kai", early_conflict);
+
+        assert_eq!(config.thresholds_for("kai", "souma"), early_conflict);
+        assert_eq!(config.thresholds_for("souma", "yuzuki"), RelationshipThresholds::default());
+
+        let mut rel = Relationship::new("souma", "kai");
+        rel.modify(-30);
+        assert_eq!(rel.level(), RelationshipLevel::Normal);
+        rel.set_thresholds(config.thresholds_for("souma", "kai"));
+        assert_eq!(rel.level(), RelationshipLevel::Conflict);
+    }
+
+    #[test]
+    fn test_cap_phases() {
+        for (phase, limit) in [(CapPhase::Early, 30), (CapPhase::Mid, 50), (CapPhase::Late, 100)] {
+            let mut rel = Relationship::new("souma", "yuzuki");
+            rel.modify_capped(80, phase.limit());
+            assert_eq!(rel.value(), limit.min(80), "{:?}", phase);
+            assert_eq!(rel.banked, 80 - limit.min(80), "{:?}", phase);
+
+            rel.modify_capped(-200, phase.limit());
+            assert_eq!(rel.value(), -limit, "{:?}", phase);
+        }
+
+        assert_eq!(CapPhase::from_name("MID"), Some(CapPhase::Mid));
+        assert_eq!(CapPhase::from_name("final"), None);
+        assert_eq!(RelationshipCapPolicy::default().limit, 100);
+    }
+
+    #[test]
+    fn test_banked_overflow_release() {
+        let mut rel = Relationship::new("souma", "yuzuki");
+
+        // 序盤キャップで超過分が溜まる
+        rel.modify_capped(25, CapPhase::Early.limit());
+        rel.modify_capped(25, CapPhase::Early.limit());
+        assert_eq!(rel.value(), 30);
+        assert_eq!(rel.banked, 20);
+
+        // 中盤キャップに上がると、収まる分だけ反映される
+        rel.release_banked(CapPhase::Mid.limit());
+        assert_eq!(rel.value(), 50);
+        assert_eq!(rel.banked, 0);
+
+        // キャップが下がっても既存の値は引き戻さない
+        rel.modify_capped(-10, CapPhase::Early.limit());
+        assert_eq!(rel.value(), 40);
+        rel.modify_capped(10, CapPhase::Early.limit());
+        assert_eq!(rel.value(), 40);
+        assert_eq!(rel.banked, 10);
+    }
+
+    #[test]
+    fn test_restore_keeps_level_within_hysteresis() {
+        let thresholds = RelationshipThresholds { hysteresis: 3, ..RelationshipThresholds::default() };
+        let near_intimate = thresholds.intimate_min - 2;
+
+        let mut rel = Relationship::with_thresholds("souma", "yuzuki", thresholds);
+        rel.restore(near_intimate, 0, Some(RelationshipLevel::Intimate));
+        assert_eq!(rel.level(), RelationshipLevel::Intimate);
+
+        // 保存時のレベルがない（古いセーブデータ）場合は値だけで判定する
+        rel.restore(near_intimate, 0, None);
+        assert_eq!(rel.level(), RelationshipLevel::Normal);
+    }
+
+    #[test]
+    fn test_banked_overflow_nets_opposite_directions() {
+        let mut rel = Relationship::new("souma", "yuzuki");
+        let cap = CapPhase::Early.limit();
+
+        // +30 の超過の後に -30 の超過が来ると、溜まりは相殺される
+        rel.modify_capped(60, cap);
+        assert_eq!((rel.value(), rel.banked), (30, 30));
+        rel.modify_capped(-90, cap);
+        assert_eq!((rel.value(), rel.banked), (-30, 0));
+
+        // 溜まりは常に「キャップがなかった場合の値」との差
+        let mut uncapped = -30;
+        for delta in [-25, 40, 15, -10, 35] {
+            rel.modify_capped(delta, cap);
+            uncapped += delta;
+            assert_eq!(rel.value() + rel.banked, uncapped, "delta {}", delta);
+        }
+
+        // キャップ解除で、キャップがなかった場合の値に追いつく
+        rel.release_banked(CapPhase::Late.limit());
+        assert_eq!((rel.value(), rel.banked), (uncapped, 0));
+    }
 }
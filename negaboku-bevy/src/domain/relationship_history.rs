@@ -0,0 +1,238 @@
+//! 関係値の変動履歴 - 変動ごとの理由・発生源・シナリオ上の位置の記録
+//!
+//! # 責務
+//! - 関係値の変動1件ごとの記録（変動量・発生源・理由・位置・時刻）
+//! - 章ごとの最大変動や発生源別の累計などの集計
+//!
+//! リザルト画面の「関係値変動表示」やバランス調整、セーブデータで使う
+
+use std::collections::BTreeMap;
+use crate::domain::relationship::PairKey;
+
+/// 関係値の変動の発生源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeSource {
+    /// 戦闘（掛け合い・共闘技など）
+    Battle,
+    /// イベントの選択肢
+    Choice,
+    /// イベント（仲間同士ペアの固定変動など）
+    Event,
+    /// プレゼント
+    Gift,
+}
+
+impl ChangeSource {
+    /// セーブデータ・シナリオでの表記
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeSource::Battle => "battle",
+            ChangeSource::Choice => "choice",
+            ChangeSource::Event => "event",
+            ChangeSource::Gift => "gift",
+        }
+    }
+
+    /// 表記から変換
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "battle" => Some(ChangeSource::Battle),
+            "choice" => Some(ChangeSource::Choice),
+            "event" => Some(ChangeSource::Event),
+            "gift" => Some(ChangeSource::Gift),
+            _ => None,
+        }
+    }
+
+    /// 画面表示用の名前
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeSource::Battle => "戦闘",
+            ChangeSource::Choice => "選択肢",
+            ChangeSource::Event => "イベント",
+            ChangeSource::Gift => "プレゼント",
+        }
+    }
+}
+
+/// 変動が起きたシナリオ上の位置
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScenarioLocation {
+    /// 章ID（フロントマターの chapter）
+    pub chapter_id: Option<String>,
+    /// 変動が起きた時点のセリフID
+    pub line_id: Option<String>,
+}
+
+impl ScenarioLocation {
+    pub fn chapter(chapter_id: &str) -> Self {
+        Self {
+            chapter_id: Some(chapter_id.to_string()),
+            line_id: None,
+        }
+    }
+}
+
+/// 変動の理由（発生源・理由の説明・位置）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeCause {
+    pub source: ChangeSource,
+    pub reason: String,
+    pub location: ScenarioLocation,
+    /// 画面に通知するか（シナリオの `notify=false` で抑止、履歴には常に記録する）
+    pub notify: bool,
+}
+
+impl ChangeCause {
+    pub fn new(source: ChangeSource, reason: &str) -> Self {
+        Self {
+            source,
+            reason: reason.to_string(),
+            location: ScenarioLocation::default(),
+            notify: true,
+        }
+    }
+
+    /// 位置を設定
+    pub fn at(mut self, location: ScenarioLocation) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// 画面への通知を抑止
+    pub fn silent(mut self) -> Self {
+        self.notify = false;
+        self
+    }
+}
+
+/// 関係値の変動1件
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipChange {
+    pub pair: PairKey,
+    /// 指定された変動量（固定変動ペアの補正後）
+    pub delta: i32,
+    pub old_value: i32,
+    pub new_value: i32,
+    pub cause: ChangeCause,
+    /// 記録時刻（UNIX時間・秒）
+    pub timestamp: u64,
+}
+
+impl RelationshipChange {
+    /// 実際に反映された変動量（キャップで止まった分を除く）
+    pub fn applied_delta(&self) -> i32 {
+        self.new_value - self.old_value
+    }
+
+    fn is_in_chapter(&self, chapter_id: &str) -> bool {
+        self.cause.location.chapter_id.as_deref() == Some(chapter_id)
+    }
+}
+
+/// 関係値の変動履歴（記録順）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelationshipHistory {
+    entries: Vec<RelationshipChange>,
+}
+
+impl RelationshipHistory {
+    pub fn record(&mut self, change: RelationshipChange) {
+        self.entries.push(change);
+    }
+
+    /// 全変動（記録順）
+    pub fn entries(&self) -> &[RelationshipChange] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// ペアの変動（記録順）
+    pub fn for_pair<'a>(&'a self, pair: &'a PairKey) -> impl Iterator<Item = &'a RelationshipChange> + 'a {
+        self.entries.iter().filter(move |change| &change.pair == pair)
+    }
+
+    /// 章の中で反映量の絶対値が最も大きい変動（同じ大きさなら先の記録）
+    pub fn biggest_change_in_chapter(&self, chapter_id: &str) -> Option<&RelationshipChange> {
+        self.entries
+            .iter()
+            .filter(|change| change.is_in_chapter(chapter_id))
+            .fold(None, |biggest: Option<&RelationshipChange>, change| match biggest {
+                Some(current) if current.applied_delta().abs() >= change.applied_delta().abs() => Some(current),
+                _ => Some(change),
+            })
+    }
+
+    /// 発生源ごとの反映量の合計（`pair` 指定時はそのペアのみ）
+    pub fn net_change_from(&self, source: ChangeSource, pair: Option<&PairKey>) -> i32 {
+        self.entries
+            .iter()
+            .filter(|change| change.cause.source == source)
+            .filter(|change| pair.map_or(true, |pair| &change.pair == pair))
+            .map(RelationshipChange::applied_delta)
+            .sum()
+    }
+
+    /// 章の中でのペアごとの反映量の合計（リザルト画面の関係値変動表示用、ペア順）
+    pub fn chapter_summary(&self, chapter_id: &str) -> BTreeMap<PairKey, i32> {
+        let mut summary = BTreeMap::new();
+        for change in self.entries.iter().filter(|change| change.is_in_chapter(chapter_id)) {
+            *summary.entry(change.pair.clone()).or_insert(0) += change.applied_delta();
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(pair: (&str, &str), old_value: i32, new_value: i32, source: ChangeSource, chapter: &str) -> RelationshipChange {
+        RelationshipChange {
+            pair: PairKey::new(pair.0, pair.1),
+            delta: new_value - old_value,
+            old_value,
+            new_value,
+            cause: ChangeCause::new(source, "テスト").at(ScenarioLocation::chapter(chapter)),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_history_queries() {
+        let mut history = RelationshipHistory::default();
+        history.record(change(("souma", "yuzuki"), 0, 5, ChangeSource::Battle, "chapter01"));
+        history.record(change(("souma", "kai"), -25, -50, ChangeSource::Choice, "chapter01"));
+        history.record(change(("souma", "yuzuki"), 5, 4, ChangeSource::Battle, "chapter01"));
+        history.record(change(("souma", "yuzuki"), 4, 34, ChangeSource::Event, "chapter02"));
+
+        let biggest = history.biggest_change_in_chapter("chapter01").unwrap();
+        assert_eq!(biggest.pair, PairKey::new("kai", "souma"));
+        assert_eq!(biggest.applied_delta(), -25);
+        assert!(history.biggest_change_in_chapter("chapter03").is_none());
+
+        let souma_yuzuki = PairKey::new("souma", "yuzuki");
+        assert_eq!(history.net_change_from(ChangeSource::Battle, None), 4);
+        assert_eq!(history.net_change_from(ChangeSource::Battle, Some(&souma_yuzuki)), 4);
+        assert_eq!(history.for_pair(&souma_yuzuki).count(), 3);
+
+        let summary = history.chapter_summary("chapter01");
+        assert_eq!(summary.get(&souma_yuzuki), Some(&4));
+        assert_eq!(summary.get(&PairKey::new("souma", "kai")), Some(&-25));
+    }
+
+    #[test]
+    fn test_change_source_names() {
+        for source in [ChangeSource::Battle, ChangeSource::Choice, ChangeSource::Event, ChangeSource::Gift] {
+            assert_eq!(ChangeSource::from_name(source.as_str()), Some(source));
+        }
+        assert_eq!(ChangeSource::from_name("unknown"), None);
+    }
+}
@@ -0,0 +1,492 @@
+//! リッチテキスト - セリフ本文のマークアップ解析
+//!
+//! # 責務
+//! - ルビ記法 `｜漢字《かんじ》` / `漢字《かんじ》` の解析
+//! - インラインタグ（色・強調・表示速度・ウェイト・クリック待ち）の解析
+//! - 表示用の素のテキスト（マークアップ除去後）の生成
+//! - タイプライター表示の単位（書記素クラスタ単位、ルビ付き語は1単位）の管理
+//! - タイピング進行表（単位ごとの速度・一時停止・句読点）の生成
+//!
+//! # 記法
+//! - `｜願い石《ねがいいし》` : `｜` から `《` までを親文字とする
+//! - `遺跡《いせき》` : `《` 直前の連続した漢字を親文字とする
+//! - `｜《` : `《` そのものを表示する（エスケープ）
+//! - `{color=red}…{/color}` : 文字色（色名または `#rrggbb`）
+//! - `**強調**` : 太字
+//! - `{speed=0.5}…{/speed}` : 表示速度の倍率（2.0で2倍速）
+//! - `{w=500}` : 指定ミリ秒の一時停止
+//! - `{p}` : クリック待ち
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// 表示後に追加ウェイトを入れる句読点
+pub const PAUSE_PUNCTUATION: [char; 7] = ['。', '、', '！', '？', '…', '!', '?'];
+
+/// 文字装飾
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextStyle {
+    /// 文字色（色名または `#rrggbb`、Noneは既定色）
+    pub color: Option<String>,
+    pub bold: bool,
+}
+
+/// テキストの構成要素
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextSegment {
+    /// 通常のテキスト
+    Plain { text: String, style: TextStyle },
+    /// ルビ付きテキスト（親文字とふりがな）
+    Ruby { base: String, reading: String, style: TextStyle },
+    /// 表示速度の変更（倍率）
+    Speed(f32),
+    /// 一時停止（ミリ秒）
+    Wait(u32),
+    /// クリック待ち
+    ClickWait,
+}
+
+impl TextSegment {
+    /// 画面上に表示される本文（ルビは親文字のみ、制御タグは空）
+    pub fn base_text(&self) -> &str {
+        match self {
+            TextSegment::Plain { text, .. } => text,
+            TextSegment::Ruby { base, .. } => base,
+            _ => "",
+        }
+    }
+
+    /// 文字装飾（制御タグはNone）
+    pub fn style(&self) -> Option<&TextStyle> {
+        match self {
+            TextSegment::Plain { style, .. } | TextSegment::Ruby { style, .. } => Some(style),
+            _ => None,
+        }
+    }
+}
+
+/// タイプライター表示中の一時停止
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextPause {
+    /// 指定ミリ秒の停止（`{w=...}`）
+    Wait(u32),
+    /// クリック待ち（`{p}`）
+    Click,
+}
+
+/// タイピング表示の1単位（書記素クラスタ、またはルビ付き語）
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypingUnit {
+    /// 表示セグメント（`RichText::display_segments` の並び）のインデックス
+    pub segment: usize,
+    /// この単位まで表示した時の、セグメント本文の末尾バイト位置
+    pub byte_end: usize,
+    /// 表示速度の倍率
+    pub speed: f32,
+    /// この単位の表示前に入る一時停止
+    pub pauses_before: Vec<TextPause>,
+    /// 表示後に追加ウェイトを入れる句読点
+    pub pause_char: Option<char>,
+}
+
+/// 1行分のタイピング進行表
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypingTimeline {
+    pub units: Vec<TypingUnit>,
+    /// 全単位の表示後に入る一時停止
+    pub end_pauses: Vec<TextPause>,
+    /// 表示セグメント番号から `RichText::segments` のインデックスへの対応
+    pub segment_indices: Vec<usize>,
+}
+
+impl TypingTimeline {
+    /// 総単位数
+    pub fn len(&self) -> usize {
+        self.units.len()
+    }
+
+    /// `units` 単位目以降にある最初のクリック待ちの位置（末尾も含む）
+    pub fn next_click_wait_from(&self, units: usize) -> Option<usize> {
+        let in_units = self.units.iter()
+            .enumerate()
+            .skip(units)
+            .find(|(_, unit)| unit.pauses_before.contains(&TextPause::Click))
+            .map(|(index, _)| index);
+
+        in_units.or_else(|| {
+            (units <= self.len() && self.end_pauses.contains(&TextPause::Click)).then(|| self.len())
+        })
+    }
+}
+
+/// 解析済みのセリフ本文
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichText {
+    pub segments: Vec<TextSegment>,
+}
+
+/// 解析中の状態
+struct ParseState {
+    segments: Vec<TextSegment>,
+    plain: String,
+    color_stack: Vec<String>,
+    bold: bool,
+}
+
+impl ParseState {
+    fn style(&self) -> TextStyle {
+        TextStyle {
+            color: self.color_stack.last().cloned(),
+            bold: self.bold,
+        }
+    }
+
+    /// 装飾が変わる前に溜まったテキストを確定
+    fn flush_plain(&mut self) {
+        if !self.plain.is_empty() {
+            let style = self.style();
+            self.segments.push(TextSegment::Plain {
+                text: std::mem::take(&mut self.plain),
+                style,
+            });
+        }
+    }
+
+    fn push_control(&mut self, segment: TextSegment) {
+        self.flush_plain();
+        self.segments.push(segment);
+    }
+}
+
+impl RichText {
+    /// マークアップ付きテキストを解析
+    ///
+    /// 解釈できないタグや閉じられていない記法は文字としてそのまま残す
+    pub fn parse(source: &str) -> Self {
+        let chars: Vec<char> = source.chars().collect();
+        let mut state = ParseState {
+            segments: Vec::new(),
+            plain: String::new(),
+            color_stack: Vec::new(),
+            bold: false,
+        };
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+
+            // ｜親文字《ルビ》
+            if c == '｜' || c == '|' {
+                if chars.get(i + 1) == Some(&'《') {
+                    state.plain.push('《');
+                    i += 2;
+                    continue;
+                }
+
+                if let Some((base, reading, next)) = Self::explicit_ruby(&chars, i + 1) {
+                    state.flush_plain();
+                    let style = state.style();
+                    state.segments.push(TextSegment::Ruby { base, reading, style });
+                    i = next;
+                    continue;
+                }
+            }
+
+            // 漢字《ルビ》（親文字の開始位置を省略した形式）
+            if c == '《' {
+                if let Some((reading, next)) = Self::reading_at(&chars, i) {
+                    let base_start = state.plain
+                        .char_indices()
+                        .rev()
+                        .take_while(|(_, ch)| Self::is_kanji(*ch))
+                        .last()
+                        .map(|(index, _)| index);
+
+                    if let Some(base_start) = base_start {
+                        let base = state.plain.split_off(base_start);
+                        state.flush_plain();
+                        let style = state.style();
+                        state.segments.push(TextSegment::Ruby { base, reading, style });
+                        i = next;
+                        continue;
+                    }
+                }
+            }
+
+            // **強調**（閉じ記号がある場合のみ）
+            if c == '*' && chars.get(i + 1) == Some(&'*') {
+                let has_closing = chars[i + 2..].windows(2).any(|pair| pair == ['*', '*']);
+                if state.bold || has_closing {
+                    state.flush_plain();
+                    state.bold = !state.bold;
+                    i += 2;
+                    continue;
+                }
+            }
+
+            // {tag} / {tag=value}
+            if c == '{' {
+                if let Some(close) = chars[i + 1..].iter().position(|ch| *ch == '}' || *ch == '{') {
+                    let close = i + 1 + close;
+                    let tag: String = chars[i + 1..close].iter().collect();
+                    if chars[close] == '}' && Self::apply_tag(&mut state, &tag) {
+                        i = close + 1;
+                        continue;
+                    }
+                }
+            }
+
+            state.plain.push(c);
+            i += 1;
+        }
+
+        state.flush_plain();
+        Self { segments: state.segments }
+    }
+
+    /// タグを解釈して状態に反映（未対応のタグはfalse）
+    fn apply_tag(state: &mut ParseState, tag: &str) -> bool {
+        let (name, value) = match tag.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim())),
+            None => (tag.trim(), None),
+        };
+
+        match (name, value) {
+            ("color", Some(color)) if !color.is_empty() => {
+                state.flush_plain();
+                state.color_stack.push(color.to_string());
+            }
+            ("/color", None) if !state.color_stack.is_empty() => {
+                state.flush_plain();
+                state.color_stack.pop();
+            }
+            ("speed", Some(speed)) => match speed.parse::<f32>() {
+                Ok(speed) if speed > 0.0 && speed.is_finite() => state.push_control(TextSegment::Speed(speed)),
+                _ => return false,
+            },
+            ("/speed", None) => state.push_control(TextSegment::Speed(1.0)),
+            ("w", Some(ms)) => match ms.parse::<u32>() {
+                Ok(ms) => state.push_control(TextSegment::Wait(ms)),
+                Err(_) => return false,
+            },
+            ("p", None) => state.push_control(TextSegment::ClickWait),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// マークアップを除いた本文
+    pub fn plain_text(&self) -> String {
+        self.segments.iter().map(|segment| segment.base_text()).collect()
+    }
+
+    /// 表示セグメント（制御タグ以外）の一覧
+    pub fn display_segments(&self) -> impl Iterator<Item = &TextSegment> {
+        self.segments.iter().filter(|segment| segment.style().is_some())
+    }
+
+    /// タイピング進行表を生成
+    ///
+    /// 行全体を1回だけ走査し、以降のタイピング処理は単位ごとに定数時間で進められる
+    pub fn timeline(&self) -> TypingTimeline {
+        let mut units: Vec<TypingUnit> = Vec::new();
+        let mut speed = 1.0;
+        let mut pending_pauses = Vec::new();
+        let mut display_index = 0;
+        let mut segment_indices = Vec::new();
+
+        for (segment_index, segment) in self.segments.iter().enumerate() {
+            if segment.style().is_some() {
+                segment_indices.push(segment_index);
+            }
+
+            match segment {
+                TextSegment::Speed(value) => speed = *value,
+                TextSegment::Wait(ms) => pending_pauses.push(TextPause::Wait(*ms)),
+                TextSegment::ClickWait => pending_pauses.push(TextPause::Click),
+                TextSegment::Plain { text, .. } => {
+                    for (offset, grapheme) in text.grapheme_indices(true) {
+                        units.push(TypingUnit {
+                            segment: display_index,
+                            byte_end: offset + grapheme.len(),
+                            speed,
+                            pauses_before: std::mem::take(&mut pending_pauses),
+                            pause_char: grapheme.chars().last().filter(|c| PAUSE_PUNCTUATION.contains(c)),
+                        });
+                    }
+                    display_index += 1;
+                }
+                TextSegment::Ruby { base, .. } => {
+                    units.push(TypingUnit {
+                        segment: display_index,
+                        byte_end: base.len(),
+                        speed,
+                        pauses_before: std::mem::take(&mut pending_pauses),
+                        pause_char: None,
+                    });
+                    display_index += 1;
+                }
+            }
+        }
+
+        // 「……」「！？」のような連続した句読点は最後の1つの後でだけ止める
+        for index in 1..units.len() {
+            let continues_run = units[index].pause_char.is_some() && units[index].pauses_before.is_empty();
+            if continues_run {
+                units[index - 1].pause_char = None;
+            }
+        }
+
+        TypingTimeline {
+            units,
+            end_pauses: pending_pauses,
+            segment_indices,
+        }
+    }
+
+    /// `start` 以降の `親文字《ルビ》` を解析し、（親文字, ルビ, 次の位置）を返す
+    fn explicit_ruby(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+        let open = start + chars[start..]
+            .iter()
+            .position(|c| *c == '《' || *c == '\n')?;
+        if chars[open] != '《' || open == start {
+            return None;
+        }
+
+        let base: String = chars[start..open].iter().collect();
+        let (reading, next) = Self::reading_at(chars, open)?;
+        Some((base, reading, next))
+    }
+
+    /// `《` 位置からルビ文字列を読み取り、（ルビ, `》` の次の位置）を返す
+    fn reading_at(chars: &[char], open: usize) -> Option<(String, usize)> {
+        let close = open + 1 + chars[open + 1..]
+            .iter()
+            .position(|c| *c == '》' || *c == '《' || *c == '\n')?;
+        if chars[close] != '》' || close == open + 1 {
+            return None;
+        }
+
+        let reading: String = chars[open + 1..close].iter().collect();
+        Some((reading, close + 1))
+    }
+
+    /// 親文字の自動判定に使う漢字かどうか
+    fn is_kanji(c: char) -> bool {
+        matches!(c,
+            '\u{4E00}'..='\u{9FFF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '々' | '〆' | 'ヶ'
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> TextSegment {
+        TextSegment::Plain {
+            text: text.to_string(),
+            style: TextStyle::default(),
+        }
+    }
+
+    fn ruby(base: &str, reading: &str) -> TextSegment {
+        TextSegment::Ruby {
+            base: base.to_string(),
+            reading: reading.to_string(),
+            style: TextStyle::default(),
+        }
+    }
+
+    #[test]
+    fn parse_explicit_ruby() {
+        let text = RichText::parse("これが｜願い石《ねがいいし》だ");
+
+        assert_eq!(text.segments, vec![plain("これが"), ruby("願い石", "ねがいいし"), plain("だ")]);
+        assert_eq!(text.plain_text(), "これが願い石だ");
+    }
+
+    #[test]
+    fn parse_implicit_ruby_takes_trailing_kanji() {
+        let text = RichText::parse("古い遺跡《いせき》に入る");
+
+        assert_eq!(text.segments, vec![plain("古い"), ruby("遺跡", "いせき"), plain("に入る")]);
+    }
+
+    #[test]
+    fn invalid_markup_is_kept_as_text() {
+        assert_eq!(RichText::parse("｜《は記号").plain_text(), "《は記号");
+        assert_eq!(RichText::parse("かな《かな》").plain_text(), "かな《かな》");
+        assert_eq!(RichText::parse("閉じない｜遺跡《いせき").plain_text(), "閉じない｜遺跡《いせき");
+        assert_eq!(RichText::parse("選択肢1|選択肢2").plain_text(), "選択肢1|選択肢2");
+        assert_eq!(RichText::parse("{unknown}と**片側").plain_text(), "{unknown}と**片側");
+        assert_eq!(RichText::parse("{/color}閉じタグのみ").plain_text(), "{/color}閉じタグのみ");
+    }
+
+    #[test]
+    fn ruby_group_is_one_typing_unit() {
+        let text = RichText::parse("僕は｜召喚術師《しょうかんじゅつし》です");
+        let timeline = text.timeline();
+        assert_eq!(timeline.len(), 2 + 1 + 2);
+        assert_eq!(timeline.units[2].segment, 1);
+        assert_eq!(timeline.units[2].byte_end, "召喚術師".len());
+        assert_eq!(timeline.units[3].segment, 2);
+    }
+
+    #[test]
+    fn units_are_grapheme_clusters() {
+        // 結合文字・異体字セレクタ・絵文字ZWJシーケンスはそれぞれ1単位
+        let text = RichText::parse("か\u{3099}葛\u{E0100}👨\u{200D}👩\u{200D}👧");
+        let timeline = text.timeline();
+        assert_eq!(timeline.len(), 3);
+        let source = text.plain_text();
+        assert_eq!(timeline.units.last().unwrap().byte_end, source.len());
+        assert!(timeline.units.iter().all(|unit| source.is_char_boundary(unit.byte_end)));
+    }
+
+    #[test]
+    fn punctuation_pause_once_per_run() {
+        let timeline = RichText::parse("えっ……本当！？うん、。").timeline();
+        let pause_chars: Vec<(usize, char)> = timeline.units.iter()
+            .enumerate()
+            .filter_map(|(index, unit)| unit.pause_char.map(|c| (index, c)))
+            .collect();
+
+        assert_eq!(pause_chars, vec![(3, '…'), (7, '？'), (11, '。')]);
+    }
+
+    #[test]
+    fn parse_color_and_bold() {
+        let text = RichText::parse("{color=red}赤い**石**{/color}だ");
+
+        assert_eq!(text.plain_text(), "赤い石だ");
+        assert_eq!(text.segments[0].style(), Some(&TextStyle { color: Some("red".to_string()), bold: false }));
+        assert_eq!(text.segments[1].style(), Some(&TextStyle { color: Some("red".to_string()), bold: true }));
+        assert_eq!(text.segments[2], plain("だ"));
+    }
+
+    #[test]
+    fn typing_controls_take_no_units() {
+        let text = RichText::parse("待って{w=500}……{speed=0.5}ゆっくり{/speed}{p}続き{w=200}");
+
+        assert_eq!(text.plain_text(), "待って……ゆっくり続き");
+        let timeline = text.timeline();
+        assert_eq!(timeline.len(), 11);
+        assert_eq!(timeline.units[3].pauses_before, vec![TextPause::Wait(500)]);
+        assert_eq!(timeline.units[9].pauses_before, vec![TextPause::Click]);
+        assert!(timeline.units[4].pauses_before.is_empty());
+        assert_eq!(timeline.end_pauses, vec![TextPause::Wait(200)]);
+
+        assert_eq!(timeline.units[0].speed, 1.0);
+        assert_eq!(timeline.units[5].speed, 0.5);
+        assert_eq!(timeline.units[8].speed, 0.5);
+        assert_eq!(timeline.units[9].speed, 1.0);
+
+        assert_eq!(timeline.next_click_wait_from(0), Some(9));
+        assert_eq!(timeline.next_click_wait_from(9), Some(9));
+        assert_eq!(timeline.next_click_wait_from(10), None);
+    }
+}
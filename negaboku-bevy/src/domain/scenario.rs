@@ -7,7 +7,10 @@
 
 use bevy::prelude::*;
 // use serde::{Deserialize, Serialize}; // 将来使用予定
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use crate::domain::character::CharacterRegistry;
+use crate::domain::command_registry::CommandPayload;
 
 /// シナリオファイル全体の構造
 #[derive(Debug, Clone, Resource)]
@@ -15,6 +18,108 @@ pub struct ScenarioFile {
     pub title: String,
     pub scenes: Vec<Scene>,
     pub current_scene_index: usize,
+    /// 解析時に検出したエラー（行番号はマクロ呼び出し元に対応付け済み）
+    pub parse_errors: Vec<ParseError>,
+    /// 先頭のフロントマター（`---` で囲まれたブロック）から読み込んだメタデータ
+    pub metadata: ScenarioMetadata,
+}
+
+/// シナリオのメタデータ（フロントマター）
+///
+/// # 記法
+/// ```text
+/// ---
+/// chapter: chapter01
+/// title: 第1章 遺跡の入口
+/// relationship_cap: 30
+/// bgm: ruins_theme.mp3
+/// background: ruins_entrance.png
+/// preload_images: [characters/01_souma_kari.png, backgrounds/black.png]
+/// preload_sounds:
+///   - se/door_open.mp3
+/// ---
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScenarioMetadata {
+    /// 章ID（セーブデータ・関係値キャップの参照用）
+    pub chapter_id: Option<String>,
+    /// 表示タイトル（指定時は `# ` 見出しより優先）
+    pub display_title: Option<String>,
+    /// この章で必要な関係値キャップ（±30 / ±50 / ±100）
+    pub relationship_cap: Option<i32>,
+    /// 既定BGM（`sounds/bgm/` からの相対パス）
+    pub default_bgm: Option<String>,
+    /// 既定背景（`images/backgrounds/` からの相対パス）
+    pub default_background: Option<String>,
+    /// 事前読み込みする画像（`images/` からの相対パス）
+    pub preload_images: Vec<String>,
+    /// 事前読み込みするサウンド（`sounds/` からの相対パス）
+    pub preload_sounds: Vec<String>,
+}
+
+/// フロントマターの値
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontMatterValue {
+    Scalar(String),
+    List(Vec<String>),
+}
+
+impl ScenarioMetadata {
+    /// フロントマターの1項目を設定
+    ///
+    /// 未知のキーや型の合わない値はエラーを返す
+    pub fn set(&mut self, key: &str, value: FrontMatterValue) -> Result<(), ParseError> {
+        let error = |message: String| ParseError { line_number: 0, message };
+
+        match (key, value) {
+            ("chapter" | "chapter_id", FrontMatterValue::Scalar(v)) => self.chapter_id = Some(v),
+            ("title", FrontMatterValue::Scalar(v)) => self.display_title = Some(v),
+            ("relationship_cap", FrontMatterValue::Scalar(v)) => {
+                let cap = v.trim_start_matches(['±', '+']).parse::<i32>()
+                    .map_err(|_| error(format!("relationship_cap は数値である必要があります: {}", v)))?;
+                if !(1..=100).contains(&cap) {
+                    return Err(error(format!("relationship_cap は 1～100 の範囲で指定してください: {}", cap)));
+                }
+                self.relationship_cap = Some(cap);
+            }
+            ("bgm" | "default_bgm", FrontMatterValue::Scalar(v)) => self.default_bgm = Some(v),
+            ("background" | "default_background", FrontMatterValue::Scalar(v)) => self.default_background = Some(v),
+            ("preload_images", FrontMatterValue::List(items)) => self.preload_images = items,
+            ("preload_sounds", FrontMatterValue::List(items)) => self.preload_sounds = items,
+            // 1件だけの場合はリスト記法を省略可能
+            ("preload_images", FrontMatterValue::Scalar(v)) => self.preload_images = vec![v],
+            ("preload_sounds", FrontMatterValue::Scalar(v)) => self.preload_sounds = vec![v],
+            (
+                "chapter" | "chapter_id" | "title" | "relationship_cap" | "bgm" | "default_bgm"
+                | "background" | "default_background",
+                FrontMatterValue::List(_),
+            ) => return Err(error(format!("{} にリストは指定できません", key))),
+            (unknown, _) => return Err(error(format!("未対応のフロントマター項目: {}", unknown))),
+        }
+
+        Ok(())
+    }
+
+    /// シナリオ開始時に実行する既定コマンド（背景・BGM）
+    pub fn default_commands(&self) -> Vec<SceneCommand> {
+        let mut commands = Vec::new();
+
+        if let Some(storage) = &self.default_background {
+            commands.push(SceneCommand::new("bg", BackgroundCommand {
+                storage: storage.clone(),
+                time: None,
+            }));
+        }
+        if let Some(play) = &self.default_bgm {
+            commands.push(SceneCommand::new("bgm", BgmCommand {
+                play: play.clone(),
+                volume: None,
+                loop_audio: Some(true),
+            }));
+        }
+
+        commands
+    }
 }
 
 impl Default for ScenarioFile {
@@ -23,8 +128,64 @@ impl Default for ScenarioFile {
             title: "デフォルトシナリオ".to_string(),
             scenes: vec![],
             current_scene_index: 0,
+            parse_errors: vec![],
+            metadata: ScenarioMetadata::default(),
+        }
+    }
+}
+
+impl ScenarioFile {
+    /// 全ダイアログの話者をキャラクター登録と照合する
+    ///
+    /// 戻り値は未登録の話者名の一覧（重複なし、出現順）
+    pub fn resolve_speakers(&mut self, registry: &CharacterRegistry) -> Vec<String> {
+        let mut unknown_speakers = Vec::new();
+
+        for block in self.scenes.iter_mut().flat_map(|scene| scene.dialogue_blocks.iter_mut()) {
+            if let Err(speaker) = block.resolve_speaker(registry) {
+                if !unknown_speakers.contains(&speaker) {
+                    unknown_speakers.push(speaker);
+                }
+            }
+        }
+
+        unknown_speakers
+    }
+
+    /// IDが未指定のダイアログにセリフIDを付与する（`{prefix}_{本文のハッシュ}`）
+    ///
+    /// 同じ話者・本文のセリフが複数ある場合は2つ目以降に `_2`, `_3` … を付ける。
+    /// 前後のセリフを編集・挿入しても、本文が変わらない限りIDは変わらない
+    pub fn assign_line_ids(&mut self, prefix: &str) {
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+
+        for block in self.scenes.iter_mut().flat_map(|scene| scene.dialogue_blocks.iter_mut()) {
+            if !block.line_id.is_empty() {
+                continue;
+            }
+            let content = format!("{}\u{1f}{}", block.speaker.as_deref().unwrap_or(""), block.text);
+            let base_id = format!("{}_{:08x}", prefix, stable_hash(&content));
+
+            let count = occurrences.entry(base_id.clone()).or_insert(0);
+            *count += 1;
+            block.line_id = if *count == 1 { base_id } else { format!("{}_{}", base_id, count) };
         }
     }
+
+    /// 重複している明示IDを持つブロック（2つ目以降）を返す
+    pub fn duplicate_line_ids(&self) -> Vec<&DialogueBlock> {
+        let mut seen = HashSet::new();
+        self.scenes
+            .iter()
+            .flat_map(|scene| scene.dialogue_blocks.iter())
+            .filter(|block| !block.line_id.is_empty() && !seen.insert(block.line_id.as_str()))
+            .collect()
+    }
+}
+
+/// 実行環境によらず一定の文字列ハッシュ（FNV-1a 32bit）
+fn stable_hash(text: &str) -> u32 {
+    text.bytes().fold(0x811c_9dc5, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x0100_0193))
 }
 
 /// 1つのシーンの情報
@@ -35,42 +196,69 @@ pub struct Scene {
 }
 
 /// シーンコマンド（[bg], [chara_show]等）
+///
+/// `SceneCommandRegistry` に登録されたハンドラがパラメータを解釈して生成し、実行も同じハンドラが行う。
+/// 内容の型はハンドラごとに異なる（組み込みコマンドは下の `BackgroundCommand` 等）
+#[derive(Debug, Clone)]
+pub struct SceneCommand {
+    name: String,
+    payload: Arc<dyn CommandPayload>,
+}
+
+/// 背景変更 [bg storage=filename time=duration]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackgroundCommand {
+    pub storage: String,
+    pub time: Option<u32>,
+}
+
+/// キャラクター表示 [chara_show name=character face=expression pos=position]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharacterShowCommand {
+    pub name: String,
+    pub face: Option<String>,
+    pub pos: Option<CharacterPosition>,
+}
+
+/// キャラクター非表示 [chara_hide name=character]
 #[derive(Debug, Clone, PartialEq)]
-pub enum SceneCommand {
-    /// 背景変更 [bg storage=filename time=duration]
-    Background {
-        storage: String,
-        time: Option<u32>,
-    },
-    /// キャラクター表示 [chara_show name=character face=expression pos=position]
-    CharacterShow {
-        name: String,
-        face: Option<String>,
-        pos: Option<CharacterPosition>,
-    },
-    /// キャラクター非表示 [chara_hide name=character]
-    CharacterHide {
-        name: String,
-    },
-    /// BGM再生 [bgm play=filename volume=volume loop=bool]
-    Bgm {
-        play: String,
-        volume: Option<f32>,
-        loop_audio: Option<bool>,
-    },
-    /// SE再生 [se play=filename volume=volume]
-    Se {
-        play: String,
-        volume: Option<f32>,
-    },
-    /// 待機 [wait time=duration]
-    Wait {
-        time: u32,
-    },
-    /// 選択肢 [choice text="選択肢1|選択肢2|選択肢3"]
-    Choice {
-        text: String,
-    },
+pub struct CharacterHideCommand {
+    pub name: String,
+}
+
+/// BGM再生 [bgm play=filename volume=volume loop=bool]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BgmCommand {
+    pub play: String,
+    pub volume: Option<f32>,
+    pub loop_audio: Option<bool>,
+}
+
+/// SE再生 [se play=filename volume=volume]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeCommand {
+    pub play: String,
+    pub volume: Option<f32>,
+}
+
+/// 待機 [wait time=duration]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaitCommand {
+    pub time: u32,
+}
+
+/// 選択肢 [choice text="選択肢1|選択肢2|選択肢3"]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChoiceCommand {
+    pub text: String,
+}
+
+/// 次のダイアログのボイス [voice storage=filename]
+///
+/// シーンコマンドではなく、直後のダイアログブロックに付与される
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceCommand {
+    pub storage: String,
 }
 
 /// キャラクター位置
@@ -99,6 +287,14 @@ impl From<&str> for CharacterPosition {
 pub struct DialogueBlock {
     pub speaker: Option<String>, // None = 地の文
     pub text: String,
+    /// 話者のキャラクターID（`**？？？|kai**` の指定、または CharacterRegistry での解決結果）
+    pub character_id: Option<String>,
+    /// 表示時に再生するボイス（直前の `[voice storage=...]`）
+    pub voice: Option<String>,
+    /// 安定したセリフID（行末の `{#id}` 指定、なければ読み込み時に本文のハッシュから付与）
+    pub line_id: String,
+    /// 元ファイルでブロックが終わる行番号（マクロ展開由来なら None）
+    pub source_line: Option<usize>,
 }
 
 /// シナリオ解析結果（パースエラー含む）
@@ -109,20 +305,34 @@ pub struct ParseResult {
 }
 
 /// パースエラー情報
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ParseError {
     pub line_number: usize,
     pub message: String,
 }
 
 impl SceneCommand {
-    /// コマンド文字列をパース
+    pub fn new<P: CommandPayload>(name: &str, payload: P) -> Self {
+        Self {
+            name: name.to_string(),
+            payload: Arc::new(payload),
+        }
+    }
+
+    /// コマンド名を取得（統計・ハンドラ検索用）
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// コマンドの内容（型が違えば None）
+    pub fn payload<P: CommandPayload>(&self) -> Option<&P> {
+        self.payload.as_any().downcast_ref()
+    }
+
+    /// コマンド文字列をコマンド名とパラメータに分割
     ///
-    /// # 例
-    /// ```
-    /// let cmd = SceneCommand::parse("[bg storage=forest_day.jpg time=500]");
-    /// ```
-    pub fn parse(command_str: &str) -> Result<Self, ParseError> {
+    /// `[cmd param1=value1 param2=value2]` → `("cmd", {param1: value1, param2: value2})`
+    pub fn split(command_str: &str) -> Result<(String, HashMap<String, String>), ParseError> {
         let trimmed = command_str.trim();
 
         // [cmd param1=value1 param2=value2] 形式をパース
@@ -143,96 +353,8 @@ impl SceneCommand {
             });
         }
 
-        let command_name = parts[0];
         let params = Self::parse_parameters(&parts[1..])?;
-
-        match command_name {
-            "bg" => {
-                let storage = params.get("storage")
-                    .ok_or_else(|| ParseError {
-                        line_number: 0,
-                        message: "bg コマンドには storage パラメータが必要".to_string(),
-                    })?
-                    .clone();
-                let time = params.get("time")
-                    .and_then(|t| t.parse().ok());
-
-                Ok(SceneCommand::Background { storage, time })
-            }
-            "chara_show" => {
-                let name = params.get("name")
-                    .ok_or_else(|| ParseError {
-                        line_number: 0,
-                        message: "chara_show コマンドには name パラメータが必要".to_string(),
-                    })?
-                    .clone();
-                let face = params.get("face").cloned();
-                let pos = params.get("pos").map(|p| CharacterPosition::from(p.as_str()));
-
-                Ok(SceneCommand::CharacterShow { name, face, pos })
-            }
-            "chara_hide" => {
-                let name = params.get("name")
-                    .ok_or_else(|| ParseError {
-                        line_number: 0,
-                        message: "chara_hide コマンドには name パラメータが必要".to_string(),
-                    })?
-                    .clone();
-
-                Ok(SceneCommand::CharacterHide { name })
-            }
-            "bgm" => {
-                let play = params.get("play")
-                    .ok_or_else(|| ParseError {
-                        line_number: 0,
-                        message: "bgm コマンドには play パラメータが必要".to_string(),
-                    })?
-                    .clone();
-                let volume = params.get("volume").and_then(|v| v.parse().ok());
-                let loop_audio = params.get("loop").and_then(|l| l.parse().ok());
-
-                Ok(SceneCommand::Bgm { play, volume, loop_audio })
-            }
-            "se" => {
-                let play = params.get("play")
-                    .ok_or_else(|| ParseError {
-                        line_number: 0,
-                        message: "se コマンドには play パラメータが必要".to_string(),
-                    })?
-                    .clone();
-                let volume = params.get("volume").and_then(|v| v.parse().ok());
-
-                Ok(SceneCommand::Se { play, volume })
-            }
-            "wait" => {
-                let time = params.get("time")
-                    .ok_or_else(|| ParseError {
-                        line_number: 0,
-                        message: "wait コマンドには time パラメータが必要".to_string(),
-                    })?
-                    .parse()
-                    .map_err(|_| ParseError {
-                        line_number: 0,
-                        message: "wait の time パラメータは数値である必要があります".to_string(),
-                    })?;
-
-                Ok(SceneCommand::Wait { time })
-            }
-            "choice" => {
-                let text = params.get("text")
-                    .ok_or_else(|| ParseError {
-                        line_number: 0,
-                        message: "choice コマンドには text パラメータが必要".to_string(),
-                    })?
-                    .clone();
-
-                Ok(SceneCommand::Choice { text })
-            }
-            _ => Err(ParseError {
-                line_number: 0,
-                message: format!("未対応のコマンド: {}", command_name),
-            }),
-        }
+        Ok((parts[0].to_string(), params))
     }
 
     /// パラメータ部分をHashMapに変換
@@ -257,59 +379,270 @@ impl SceneCommand {
     }
 }
 
+impl PartialEq for SceneCommand {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.payload.eq_payload(other.payload.as_ref())
+    }
+}
+
+/// 段落解析中の1ブロック分の原文
+struct BlockSource {
+    text: String,
+    /// 行末で指定されたセリフID
+    line_id: Option<String>,
+    /// ブロックが終わる行の添字
+    last_line: usize,
+}
+
+/// セリフとして扱う括弧（開き, 閉じ）
+const SPEECH_BRACKETS: [(char, char); 3] = [('「', '」'), ('『', '』'), ('（', '）')];
+
 impl DialogueBlock {
     /// マークダウン行からダイアログブロックをパース
     ///
     /// # 対応形式
-    /// - `**スピーカー名**「セリフ」` → スピーカー付きダイアログ（括弧形式）
+    /// - `**スピーカー名**「セリフ」` → スピーカー付きダイアログ（括弧形式、『』（）も可）
     /// - `**スピーカー名**: セリフ` → スピーカー付きダイアログ（コロン形式）
+    /// - `**？？？|kai**「セリフ」` → 表示名を伏せた話者（キャラクターIDを指定）
     /// - `地の文` → スピーカーなしダイアログ
+    /// - 行末の `{#id}` → セリフIDの明示指定（どの形式にも付けられる）
     pub fn parse(line: &str) -> Option<Self> {
-        let trimmed = line.trim();
+        let (content, explicit_id) = Self::split_line_id(line);
+        let mut block = Self::parse_content(content.trim())?;
+        if let Some(id) = explicit_id {
+            block.line_id = id.to_string();
+        }
+        Some(block)
+    }
 
+    /// セリフID指定を除いた1ブロック分の本文をパース
+    fn parse_content(trimmed: &str) -> Option<Self> {
         if trimmed.is_empty() {
             return None;
         }
 
-        // **スピーカー名**形式を検出
-        if let Some(bold_end) = trimmed.find("**") {
-            if let Some(second_bold_start) = trimmed[bold_end + 2..].find("**") {
-                let speaker_name = trimmed[bold_end + 2..bold_end + 2 + second_bold_start].trim();
-                let after_speaker = &trimmed[bold_end + 4 + second_bold_start..].trim();
-
-                // 「」で囲まれたセリフを抽出（括弧形式）
-                if after_speaker.starts_with('「') && after_speaker.ends_with('」') {
-                    let dialogue_text = &after_speaker[3..after_speaker.len() - 3]; // 「」を除去
-
-                    return Some(DialogueBlock {
-                        speaker: Some(speaker_name.to_string()),
-                        text: dialogue_text.to_string(),
-                    });
-                }
-                // コロン形式の検出（**スピーカー名**: セリフ）
-                else if after_speaker.starts_with(':') {
-                    let dialogue_text = after_speaker[1..].trim(); // コロンを除去
-
-                    return Some(DialogueBlock {
-                        speaker: Some(speaker_name.to_string()),
-                        text: dialogue_text.to_string(),
-                    });
-                } else {
-                    // その他の形式でも、**name**の後のテキストをセリフとして扱う
-                    return Some(DialogueBlock {
-                        speaker: Some(speaker_name.to_string()),
-                        text: after_speaker.to_string(),
-                    });
-                }
+        // **スピーカー名**形式を検出（行頭のみ。文中の **強調** は本文の装飾）
+        if let Some((speaker_name, after_speaker)) = Self::split_speaker(trimmed) {
+            // 括弧で囲まれたセリフを抽出（括弧形式）
+            let dialogue_text = if let Some(inner) = Self::strip_speech_brackets(after_speaker) {
+                inner
             }
+            // コロン形式の検出（**スピーカー名**: セリフ）
+            else if let Some(rest) = after_speaker.strip_prefix(':') {
+                rest.trim()
+            } else {
+                // その他の形式でも、**name**の後のテキストをセリフとして扱う
+                after_speaker
+            };
+
+            // 表示名|キャラクターID 形式（匿名の話者）
+            let (speaker_name, character_id) = match speaker_name.split_once('|') {
+                Some((display_name, id)) => (display_name.trim(), Some(id.trim().to_string())),
+                None => (speaker_name, None),
+            };
+
+            return Some(DialogueBlock {
+                speaker: Some(speaker_name.to_string()),
+                text: dialogue_text.to_string(),
+                character_id,
+                voice: None,
+                line_id: String::new(),
+                source_line: None,
+            });
         }
 
         // 地の文として扱う
         Some(DialogueBlock {
             speaker: None,
             text: trimmed.to_string(),
+            character_id: None,
+            voice: None,
+            line_id: String::new(),
+            source_line: None,
+        })
+    }
+
+    /// 空行で区切られた段落（連続する複数行）からダイアログブロックを作成
+    ///
+    /// # 規則
+    /// - 連続する行は1つのブロックに連結する
+    /// - 行末の半角スペース2つ、または `\` は明示的な改行になる
+    /// - `**スピーカー名**` で始まる行から新しいブロックになる
+    /// - 括弧形式のセリフが閉じた後、括弧で始まる行は同じ話者の新しいブロック、
+    ///   それ以外の行は地の文の新しいブロックになる
+    /// - 行末の `{#id}` はその行を含むブロックのセリフIDになる
+    pub fn parse_paragraph<S: AsRef<str>>(lines: &[S]) -> Vec<Self> {
+        Self::parse_paragraph_spans(lines).into_iter().map(|(block, _)| block).collect()
+    }
+
+    /// 段落をパースし、各ブロックと「ブロックが終わる行の添字」の組を返す
+    pub fn parse_paragraph_spans<S: AsRef<str>>(lines: &[S]) -> Vec<(Self, usize)> {
+        let mut sources: Vec<BlockSource> = Vec::new();
+        let mut hard_break = false;
+
+        for (index, line) in lines.iter().enumerate() {
+            let (line, explicit_id) = Self::split_line_id(line.as_ref());
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let (content, breaks_after) = match trimmed.strip_suffix('\\') {
+                Some(content) => (content.trim_end(), true),
+                None => (trimmed, line.trim_end_matches('\r').ends_with("  ")),
+            };
+
+            let starts_block = match sources.last() {
+                None => true,
+                Some(_) if Self::split_speaker(content).is_some() => true,
+                Some(source) => Self::split_speaker(&source.text)
+                    .is_some_and(|(_, speech)| Self::strip_speech_brackets(speech).is_some()),
+            };
+
+            if starts_block {
+                // 閉じたセリフの直後に括弧で始まる行が続く場合は話者を引き継ぐ
+                let carried_speaker = sources
+                    .last()
+                    .and_then(|source| Self::split_speaker(&source.text))
+                    .map(|(speaker, _)| speaker.to_string())
+                    .filter(|_| Self::split_speaker(content).is_none() && Self::opens_speech(content));
+
+                sources.push(BlockSource {
+                    text: match carried_speaker {
+                        Some(speaker) => format!("**{}**{}", speaker, content),
+                        None => content.to_string(),
+                    },
+                    line_id: None,
+                    last_line: index,
+                });
+            } else if let Some(source) = sources.last_mut() {
+                Self::join_line(&mut source.text, content, hard_break);
+                source.last_line = index;
+            }
+
+            if let (Some(source), Some(id)) = (sources.last_mut(), explicit_id) {
+                source.line_id = Some(id.to_string());
+            }
+            hard_break = breaks_after;
+        }
+
+        sources
+            .into_iter()
+            .filter_map(|source| {
+                let mut block = Self::parse_content(&source.text)?;
+                if let Some(id) = source.line_id {
+                    block.line_id = id;
+                }
+                Some((block, source.last_line))
+            })
+            .collect()
+    }
+
+    /// 行末の `{#id}` を分離し（残りの行, ID）を返す
+    ///
+    /// IDに使えるのは英数字と `_` `-` `.` のみ。それ以外は本文として扱う
+    pub fn split_line_id(line: &str) -> (&str, Option<&str>) {
+        let trimmed = line.trim_end();
+        let Some(start) = trimmed.strip_suffix('}').and_then(|rest| rest.rfind("{#")) else {
+            return (line, None);
+        };
+
+        let id = &trimmed[start + 2..trimmed.len() - 1];
+        let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        if valid {
+            (&trimmed[..start], Some(id))
+        } else {
+            (line, None)
+        }
+    }
+
+    /// 期待するボイスファイル名（`[voice]` 指定、なければ `{キャラクターID}/{セリフID}.ogg`）
+    pub fn expected_voice_file(&self) -> Option<String> {
+        self.voice.clone().or_else(|| {
+            self.character_id
+                .as_ref()
+                .map(|character_id| format!("{}/{}.ogg", character_id, self.line_id))
         })
     }
+
+    /// 話者をキャラクター登録と照合し、キャラクターIDと表示名を確定する
+    ///
+    /// ID・表示名・別名のいずれかで一致したキャラクターに紐付け、表示名は登録名に揃える。
+    /// `？？？|kai` のように明示されたIDは表示名を変えずに紐付ける。
+    /// 照合できなかった場合は話者名（またはID）をエラーとして返す
+    pub fn resolve_speaker(&mut self, registry: &CharacterRegistry) -> Result<(), String> {
+        let Some(speaker) = &self.speaker else {
+            return Ok(());
+        };
+
+        if let Some(id) = &self.character_id {
+            return match registry.resolve(id) {
+                Some(character) => {
+                    self.character_id = Some(character.id.clone());
+                    Ok(())
+                }
+                None => Err(id.clone()),
+            };
+        }
+
+        match registry.resolve(speaker) {
+            Some(character) => {
+                self.character_id = Some(character.id.clone());
+                self.speaker = Some(character.name.clone());
+                Ok(())
+            }
+            None => Err(speaker.clone()),
+        }
+    }
+
+    /// 行頭の `**スピーカー名**` を分離し（名前, 残りの本文）を返す
+    fn split_speaker(text: &str) -> Option<(&str, &str)> {
+        let rest = text.strip_prefix("**")?;
+        let name_end = rest.find("**")?;
+        Some((rest[..name_end].trim(), rest[name_end + 2..].trim()))
+    }
+
+    /// セリフ括弧で始まるか
+    fn opens_speech(text: &str) -> bool {
+        text.chars()
+            .next()
+            .is_some_and(|first| SPEECH_BRACKETS.iter().any(|(open, _)| *open == first))
+    }
+
+    /// 全体が1組の括弧で囲まれている場合に中身を返す
+    ///
+    /// `「a」「b」` のように途中で括弧が閉じるものは囲みとみなさない
+    fn strip_speech_brackets(text: &str) -> Option<&str> {
+        let first = text.chars().next()?;
+        let (open, close) = SPEECH_BRACKETS.iter().find(|(open, _)| *open == first)?;
+
+        let mut depth = 0;
+        for (index, c) in text.char_indices() {
+            if c == *open {
+                depth += 1;
+            } else if c == *close {
+                depth -= 1;
+                if depth == 0 {
+                    let end = index + c.len_utf8();
+                    return (end == text.len()).then(|| &text[open.len_utf8()..index]);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// 継続行を連結（英数字どうしの間のみ空白を補う）
+    fn join_line(text: &mut String, next: &str, hard_break: bool) {
+        if hard_break {
+            text.push('\n');
+        } else if text.ends_with(|c: char| c.is_ascii_graphic())
+            && next.starts_with(|c: char| c.is_ascii_graphic())
+        {
+            text.push(' ');
+        }
+        text.push_str(next);
+    }
 }
 
 #[cfg(test)]
@@ -317,28 +650,36 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_scene_command_parse_bg() {
-        let cmd = SceneCommand::parse("[bg storage=forest_day.jpg time=500]").unwrap();
-        match cmd {
-            SceneCommand::Background { storage, time } => {
-                assert_eq!(storage, "forest_day.jpg");
-                assert_eq!(time, Some(500));
-            }
-            _ => panic!("期待していたBackgroundコマンドではありません"),
-        }
+    fn test_scene_command_split() {
+        let (name, params) = SceneCommand::split("[battle enemy=slime count=3]").unwrap();
+        assert_eq!(name, "battle");
+        assert_eq!(params.get("enemy"), Some(&"slime".to_string()));
+        assert_eq!(params.get("count"), Some(&"3".to_string()));
+
+        assert!(SceneCommand::split("battle enemy=slime").is_err());
+        assert!(SceneCommand::split("[]").is_err());
     }
 
     #[test]
-    fn test_scene_command_parse_chara_show() {
-        let cmd = SceneCommand::parse("[chara_show name=souma face=normal pos=left]").unwrap();
-        match cmd {
-            SceneCommand::CharacterShow { name, face, pos } => {
-                assert_eq!(name, "souma");
-                assert_eq!(face, Some("normal".to_string()));
-                assert_eq!(pos, Some(CharacterPosition::Left));
-            }
-            _ => panic!("期待していたCharacterShowコマンドではありません"),
-        }
+    fn test_scenario_metadata_set() {
+        let mut metadata = ScenarioMetadata::default();
+        metadata.set("chapter", FrontMatterValue::Scalar("chapter01".to_string())).unwrap();
+        metadata.set("relationship_cap", FrontMatterValue::Scalar("±30".to_string())).unwrap();
+        metadata.set("bgm", FrontMatterValue::Scalar("ruins.mp3".to_string())).unwrap();
+        metadata.set("preload_sounds", FrontMatterValue::Scalar("se/door.mp3".to_string())).unwrap();
+
+        assert_eq!(metadata.chapter_id.as_deref(), Some("chapter01"));
+        assert_eq!(metadata.relationship_cap, Some(30));
+        assert_eq!(metadata.preload_sounds, vec!["se/door.mp3".to_string()]);
+        assert_eq!(metadata.default_commands(), vec![SceneCommand::new("bgm", BgmCommand {
+            play: "ruins.mp3".to_string(),
+            volume: None,
+            loop_audio: Some(true),
+        })]);
+
+        assert!(metadata.set("relationship_cap", FrontMatterValue::Scalar("200".to_string())).is_err());
+        assert!(metadata.set("title", FrontMatterValue::List(vec![])).is_err());
+        assert!(metadata.set("unknown_key", FrontMatterValue::Scalar("x".to_string())).is_err());
     }
 
     #[test]
@@ -348,10 +689,168 @@ mod tests {
         assert_eq!(block.text, "こんにちは");
     }
 
+    #[test]
+    fn test_dialogue_block_inline_bold_is_not_speaker() {
+        let block = DialogueBlock::parse("それは**願い石**と呼ばれていた。").unwrap();
+        assert_eq!(block.speaker, None);
+        assert_eq!(block.text, "それは**願い石**と呼ばれていた。");
+
+        let block = DialogueBlock::parse("**ソウマ**「これが**願い石**か」").unwrap();
+        assert_eq!(block.speaker, Some("ソウマ".to_string()));
+        assert_eq!(block.text, "これが**願い石**か");
+    }
+
     #[test]
     fn test_dialogue_block_parse_narration() {
         let block = DialogueBlock::parse("遺跡の古い石造りの扉が、二人の前に立ちはだかっていた。").unwrap();
         assert_eq!(block.speaker, None);
         assert_eq!(block.text, "遺跡の古い石造りの扉が、二人の前に立ちはだかっていた。");
     }
+
+    #[test]
+    fn test_dialogue_block_parse_brackets() {
+        let block = DialogueBlock::parse("**ユズキ**『聞こえる？』").unwrap();
+        assert_eq!(block.text, "聞こえる？");
+
+        let block = DialogueBlock::parse("**ソウマ**（まさか……）").unwrap();
+        assert_eq!(block.text, "まさか……");
+
+        // 途中で閉じる括弧は囲みとみなさない
+        let block = DialogueBlock::parse("**ソウマ**「え」「何？」").unwrap();
+        assert_eq!(block.text, "「え」「何？」");
+
+        // 入れ子の括弧は外側だけを外す
+        let block = DialogueBlock::parse("**ユズキ**「『願い石』って知ってる？」").unwrap();
+        assert_eq!(block.text, "『願い石』って知ってる？");
+    }
+
+    #[test]
+    fn test_dialogue_block_parse_paragraph_continuation() {
+        let blocks = DialogueBlock::parse_paragraph(&[
+            "**ソウマ**「長い話になるけど、",
+            "聞いてくれるかな。」",
+        ]);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].speaker, Some("ソウマ".to_string()));
+        assert_eq!(blocks[0].text, "長い話になるけど、聞いてくれるかな。");
+
+        let blocks = DialogueBlock::parse_paragraph(&["一行目  ", "二行目\\", "三行目", "Rust", "Bevy"]);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].text, "一行目\n二行目\n三行目Rust Bevy");
+    }
+
+    #[test]
+    fn test_dialogue_block_parse_paragraph_speaker_carry_over() {
+        let blocks = DialogueBlock::parse_paragraph(&[
+            "**ソウマ**「行こう」",
+            "「遺跡はすぐそこだ」",
+            "ソウマは歩き出した。",
+            "**ユズキ**「待って！」",
+        ]);
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(blocks[1].speaker, Some("ソウマ".to_string()));
+        assert_eq!(blocks[1].text, "遺跡はすぐそこだ");
+        assert_eq!(blocks[2].speaker, None);
+        assert_eq!(blocks[2].text, "ソウマは歩き出した。");
+        assert_eq!(blocks[3].speaker, Some("ユズキ".to_string()));
+    }
+
+    #[test]
+    fn test_explicit_line_id() {
+        let block = DialogueBlock::parse("**ソウマ**「行こう」 {#ch1_go}").unwrap();
+        assert_eq!(block.line_id, "ch1_go");
+        assert_eq!(block.text, "行こう");
+
+        // IDとして使えない文字を含む場合は本文のまま
+        let block = DialogueBlock::parse("{#色}の話").unwrap();
+        assert_eq!(block.text, "{#色}の話");
+        assert!(block.line_id.is_empty());
+    }
+
+    #[test]
+    fn test_paragraph_line_ids_follow_blocks() {
+        let blocks = DialogueBlock::parse_paragraph_spans(&[
+            "**ソウマ**「行こう。",
+            "みんな」{#go}",
+            "「急ごう」{#hurry}",
+            "風が吹いた。",
+        ]);
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!((blocks[0].0.line_id.as_str(), blocks[0].1), ("go", 1));
+        assert_eq!(blocks[1].0.speaker.as_deref(), Some("ソウマ"));
+        assert_eq!((blocks[1].0.line_id.as_str(), blocks[1].1), ("hurry", 2));
+        assert_eq!((blocks[2].0.line_id.as_str(), blocks[2].1), ("", 3));
+    }
+
+    #[test]
+    fn test_assign_line_ids() {
+        let block = |speaker: Option<&str>, text: &str| {
+            let mut block = DialogueBlock::parse(text).unwrap();
+            block.speaker = speaker.map(str::to_string);
+            block
+        };
+        let mut scenario = ScenarioFile {
+            scenes: vec![Scene {
+                commands: vec![],
+                dialogue_blocks: vec![
+                    block(Some("ソウマ"), "……"),
+                    block(Some("ユズキ"), "……"),
+                    block(Some("ソウマ"), "……"),
+                ],
+            }],
+            ..ScenarioFile::default()
+        };
+
+        scenario.assign_line_ids("chapter01");
+        let ids: Vec<&str> = scenario.scenes[0].dialogue_blocks.iter().map(|b| b.line_id.as_str()).collect();
+
+        assert!(ids[0].starts_with("chapter01_"));
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(ids[2], format!("{}_2", ids[0]));
+
+        // 同じ内容なら再読み込みしても同じID
+        let first_id = ids[0].to_string();
+        scenario.assign_line_ids("chapter01");
+        assert_eq!(scenario.scenes[0].dialogue_blocks[0].line_id, first_id);
+    }
+
+    #[test]
+    fn test_expected_voice_file() {
+        let mut block = DialogueBlock::parse("**ソウマ**「行こう」").unwrap();
+        assert_eq!(block.expected_voice_file(), None);
+
+        block.character_id = Some("souma".to_string());
+        block.line_id = "chapter01_0000abcd".to_string();
+        assert_eq!(block.expected_voice_file(), Some("souma/chapter01_0000abcd.ogg".to_string()));
+
+        block.voice = Some("souma_001.ogg".to_string());
+        assert_eq!(block.expected_voice_file(), Some("souma_001.ogg".to_string()));
+    }
+
+    #[test]
+    fn test_dialogue_block_resolve_speaker() {
+        let mut registry = CharacterRegistry::new();
+        registry.register_default_characters();
+
+        // ID指定でも表示名に揃える
+        let mut block = DialogueBlock::parse("**souma**「行こう」").unwrap();
+        assert!(block.resolve_speaker(&registry).is_ok());
+        assert_eq!(block.speaker, Some("ソウマ".to_string()));
+        assert_eq!(block.character_id, Some("souma".to_string()));
+
+        // 匿名の話者は表示名を伏せたままIDに紐付ける
+        let mut block = DialogueBlock::parse("**？？？|kai**「……誰だ」").unwrap();
+        assert!(block.resolve_speaker(&registry).is_ok());
+        assert_eq!(block.speaker, Some("？？？".to_string()));
+        assert_eq!(block.character_id, Some("kai".to_string()));
+
+        let mut block = DialogueBlock::parse("**謎の声**「……」").unwrap();
+        assert_eq!(block.resolve_speaker(&registry), Err("謎の声".to_string()));
+        assert_eq!(block.character_id, None);
+
+        // 地の文は照合しない
+        let mut block = DialogueBlock::parse("静かな夜だった。").unwrap();
+        assert!(block.resolve_speaker(&registry).is_ok());
+    }
 }
@@ -0,0 +1,372 @@
+//! シナリオマクロ - 再利用可能なコマンド列の定義と展開
+//!
+//! # 責務
+//! - `[macro name=xxx]` ～ `[endmacro]` で囲まれたマクロ定義の抽出
+//! - マクロ呼び出し `[xxx param=value]` のパラメータ置換と展開
+//! - 展開後の行と呼び出し元の行番号の対応付け
+//!
+//! # 記法
+//! ```text
+//! [macro name=enter_pair]
+//! [chara_show name=%left face=%face|normal pos=left]
+//! [chara_show name=%right face=%face|normal pos=right]
+//! [endmacro]
+//!
+//! [enter_pair left=souma right=yuzuki]
+//! ```
+//! - `%param` は呼び出し時のパラメータ値に置換される
+//! - `%param|default` はパラメータ省略時に default を使う
+
+use std::collections::HashMap;
+use crate::domain::scenario::{SceneCommand, ParseError};
+
+/// マクロ展開のネスト上限（自己再帰の検出用）
+const MAX_EXPANSION_DEPTH: usize = 16;
+
+/// マクロ定義
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioMacro {
+    pub name: String,
+    /// 本体の各行（行番号は定義ファイル上の位置）
+    pub body: Vec<SourceLine>,
+    /// `[macro]` 行の行番号（1始まり）
+    pub line_number: usize,
+}
+
+/// マクロ展開元の情報
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroOrigin {
+    pub macro_name: String,
+    /// 展開元となったマクロ本体行の定義位置（1始まり）
+    pub definition_line: usize,
+}
+
+/// ソース上の1行（マクロ展開後も呼び出し元の行番号を保持）
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLine {
+    pub text: String,
+    /// 元ファイルでの行番号（マクロ展開行は呼び出し行）
+    pub line_number: usize,
+    pub origin: Option<MacroOrigin>,
+}
+
+impl SourceLine {
+    pub fn new(text: &str, line_number: usize) -> Self {
+        Self {
+            text: text.to_string(),
+            line_number,
+            origin: None,
+        }
+    }
+
+    /// エラーを呼び出し元の位置に対応付ける
+    pub fn locate_error(&self, error: ParseError) -> ParseError {
+        let message = match &self.origin {
+            Some(origin) => format!(
+                "{} (マクロ {} を展開中: 定義 {} 行目)",
+                error.message, origin.macro_name, origin.definition_line
+            ),
+            None => error.message,
+        };
+
+        ParseError {
+            line_number: self.line_number,
+            message,
+        }
+    }
+}
+
+/// マクロ定義の集合
+#[derive(Debug, Clone, Default)]
+pub struct MacroLibrary {
+    macros: HashMap<String, ScenarioMacro>,
+}
+
+impl MacroLibrary {
+    pub fn new() -> Self {
+        Self {
+            macros: HashMap::new(),
+        }
+    }
+
+    /// マクロを定義（同名のマクロは上書き）
+    pub fn define(&mut self, scenario_macro: ScenarioMacro) {
+        self.macros.insert(scenario_macro.name.clone(), scenario_macro);
+    }
+
+    /// マクロを取得
+    pub fn get(&self, name: &str) -> Option<&ScenarioMacro> {
+        self.macros.get(name)
+    }
+
+    /// マクロを削除
+    pub fn remove(&mut self, name: &str) -> Option<ScenarioMacro> {
+        self.macros.remove(name)
+    }
+
+    /// 定義済みマクロ名一覧（ソート済み）
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.macros.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// 定義済みマクロ数
+    pub fn len(&self) -> usize {
+        self.macros.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.macros.is_empty()
+    }
+
+    /// 別のライブラリの定義を取り込む（同名は取り込む側で上書き）
+    pub fn merge(&mut self, other: MacroLibrary) {
+        self.macros.extend(other.macros);
+    }
+
+    /// コンテンツからマクロ定義を抽出
+    ///
+    /// マクロ定義を取り除いた残りの行を行番号付きで返す
+    pub fn extract_definitions(content: &str) -> (MacroLibrary, Vec<SourceLine>, Vec<ParseError>) {
+        let mut library = MacroLibrary::new();
+        let mut remaining = Vec::new();
+        let mut errors = Vec::new();
+        let mut current: Option<ScenarioMacro> = None;
+
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let trimmed = line.trim();
+
+            if let Some(scenario_macro) = current.as_mut() {
+                if trimmed == "[endmacro]" {
+                    library.define(current.take().unwrap());
+                } else if Self::macro_header_name(trimmed).is_some() {
+                    errors.push(ParseError {
+                        line_number,
+                        message: format!("マクロ {} の定義内で別のマクロは定義できません", scenario_macro.name),
+                    });
+                } else if !trimmed.is_empty() {
+                    scenario_macro.body.push(SourceLine::new(trimmed, line_number));
+                }
+                continue;
+            }
+
+            match Self::macro_header_name(trimmed) {
+                Some(Ok(name)) => {
+                    current = Some(ScenarioMacro {
+                        name,
+                        body: Vec::new(),
+                        line_number,
+                    });
+                }
+                Some(Err(error)) => {
+                    errors.push(ParseError {
+                        line_number,
+                        message: error.message,
+                    });
+                }
+                None if trimmed == "[endmacro]" => {
+                    errors.push(ParseError {
+                        line_number,
+                        message: "対応する [macro] のない [endmacro]".to_string(),
+                    });
+                }
+                None => remaining.push(SourceLine::new(line, line_number)),
+            }
+        }
+
+        if let Some(unclosed) = current {
+            errors.push(ParseError {
+                line_number: unclosed.line_number,
+                message: format!("マクロ {} に [endmacro] がありません", unclosed.name),
+            });
+        }
+
+        (library, remaining, errors)
+    }
+
+    /// `[macro name=xxx]` 行ならマクロ名を返す
+    fn macro_header_name(trimmed: &str) -> Option<Result<String, ParseError>> {
+        if !trimmed.starts_with("[macro ") && trimmed != "[macro]" {
+            return None;
+        }
+
+        let result = SceneCommand::split(trimmed).and_then(|(_, params)| {
+            params.get("name").cloned().ok_or_else(|| ParseError {
+                line_number: 0,
+                message: "macro には name パラメータが必要".to_string(),
+            })
+        });
+        Some(result)
+    }
+
+    /// 1行をマクロ展開
+    ///
+    /// マクロ呼び出しでない行はそのまま返す。展開後の行はすべて呼び出し行の行番号を持つ
+    pub fn expand_line(&self, line: &SourceLine) -> Result<Vec<SourceLine>, ParseError> {
+        let mut expanded = Vec::new();
+        self.expand_into(line, 0, &mut expanded)?;
+        Ok(expanded)
+    }
+
+    fn expand_into(&self, line: &SourceLine, depth: usize, out: &mut Vec<SourceLine>) -> Result<(), ParseError> {
+        let trimmed = line.text.trim();
+        let call = if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            SceneCommand::split(trimmed).ok()
+        } else {
+            None
+        };
+
+        let Some((scenario_macro, params)) = call
+            .and_then(|(name, params)| self.get(&name).map(|m| (m, params)))
+        else {
+            out.push(line.clone());
+            return Ok(());
+        };
+
+        if depth >= MAX_EXPANSION_DEPTH {
+            return Err(line.locate_error(ParseError {
+                line_number: 0,
+                message: format!("マクロ {} の展開が深すぎます（再帰呼び出しの可能性）", scenario_macro.name),
+            }));
+        }
+
+        for body_line in &scenario_macro.body {
+            let mut child = SourceLine {
+                text: String::new(),
+                line_number: line.line_number,
+                origin: Some(MacroOrigin {
+                    macro_name: scenario_macro.name.clone(),
+                    definition_line: body_line.line_number,
+                }),
+            };
+            child.text = Self::substitute(&body_line.text, &params)
+                .map_err(|error| child.locate_error(error))?;
+
+            self.expand_into(&child, depth + 1, out)?;
+        }
+
+        Ok(())
+    }
+
+    /// `%param` / `%param|default` をパラメータ値に置換
+    fn substitute(body_line: &str, params: &HashMap<String, String>) -> Result<String, ParseError> {
+        let mut result = String::with_capacity(body_line.len());
+        let mut chars = body_line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let starts_identifier = chars
+                .peek()
+                .map(|next| next.is_ascii_alphabetic() || *next == '_')
+                .unwrap_or(false);
+
+            if c != '%' || !starts_identifier {
+                result.push(c);
+                continue;
+            }
+
+            let mut param_name = String::new();
+            while let Some(next) = chars.peek() {
+                if next.is_ascii_alphanumeric() || *next == '_' {
+                    param_name.push(*next);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            // %param|default 形式のデフォルト値
+            let mut default_value = None;
+            if let Some('|') = chars.peek() {
+                chars.next();
+                let mut value = String::new();
+                while let Some(next) = chars.peek() {
+                    if next.is_whitespace() || *next == ']' {
+                        break;
+                    }
+                    value.push(*next);
+                    chars.next();
+                }
+                default_value = Some(value);
+            }
+
+            match params.get(&param_name).or(default_value.as_ref()) {
+                Some(value) => result.push_str(value),
+                None => {
+                    return Err(ParseError {
+                        line_number: 0,
+                        message: format!("マクロパラメータ {} が指定されていません", param_name),
+                    });
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MACRO_SOURCE: &str = r#"[macro name=enter_pair]
+[chara_show name=%left face=%face|normal pos=left]
+[chara_show name=%right face=%face|normal pos=right]
+[endmacro]
+
+[enter_pair left=souma right=yuzuki]
+"#;
+
+    #[test]
+    fn extract_and_expand_macro() {
+        let (library, lines, errors) = MacroLibrary::extract_definitions(MACRO_SOURCE);
+        assert!(errors.is_empty());
+        assert_eq!(library.len(), 1);
+
+        let call = lines.iter().find(|line| !line.text.trim().is_empty()).unwrap();
+        assert_eq!(call.line_number, 6);
+
+        let expanded = library.expand_line(call).unwrap();
+        assert_eq!(expanded.len(), 2);
+        assert_eq!(expanded[0].text, "[chara_show name=souma face=normal pos=left]");
+        assert_eq!(expanded[1].text, "[chara_show name=yuzuki face=normal pos=right]");
+        assert!(expanded.iter().all(|line| line.line_number == 6));
+        assert_eq!(expanded[1].origin.as_ref().unwrap().definition_line, 3);
+    }
+
+    #[test]
+    fn missing_parameter_reports_call_site() {
+        let (library, _, _) = MacroLibrary::extract_definitions(MACRO_SOURCE);
+        let call = SourceLine::new("[enter_pair left=souma]", 42);
+
+        let error = library.expand_line(&call).unwrap_err();
+        assert_eq!(error.line_number, 42);
+        assert!(error.message.contains("right"));
+        assert!(error.message.contains("enter_pair"));
+    }
+
+    #[test]
+    fn recursive_macro_is_rejected() {
+        let source = "[macro name=loop]\n[loop]\n[endmacro]\n";
+        let (library, _, _) = MacroLibrary::extract_definitions(source);
+
+        let error = library.expand_line(&SourceLine::new("[loop]", 5)).unwrap_err();
+        assert_eq!(error.line_number, 5);
+        assert!(error.message.contains("深すぎ"));
+    }
+
+    #[test]
+    fn unclosed_macro_is_error() {
+        let (_, _, errors) = MacroLibrary::extract_definitions("[macro name=broken]\n[bg storage=a.png]\n");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 1);
+    }
+
+    #[test]
+    fn percent_in_text_is_kept() {
+        let params = HashMap::new();
+        let text = MacroLibrary::substitute("成功率は100%です", &params).unwrap();
+        assert_eq!(text, "成功率は100%です");
+    }
+}
@@ -0,0 +1,222 @@
+//! プレゼントデータ - アイテム定義・好みの設定ファイルと反応セリフの読み込み
+//!
+//! # 責務
+//! - アイテム定義とキャラクターごとの好みの読み込み（`assets/gifts/gifts.json`）
+//! - キャラクター・反応ごとの反応セリフの読み込み（`assets/scenarios/gifts/`、話者はキャラクター登録と照合）
+//!
+//! `gifts.json`:
+//! ```json
+//! {
+//!   "giver": "souma",
+//!   "items": [{ "id": "rose", "name": "バラの花束", "category": "flowers" }],
+//!   "preferences": { "yuzuki": { "flowers": "favorite", "weapons": "disliked" } }
+//! }
+//! ```
+//!
+//! 好みを指定しない種類は普通として扱う。反応セリフは
+//! `assets/scenarios/gifts/{キャラクターID}_{favorite|normal|disliked}.md` に通常のシナリオと同じ記法で書く。
+
+use crate::application::services::GiftService;
+use crate::domain::character::CharacterRegistry;
+use crate::domain::command_registry::SceneCommandRegistry;
+use crate::domain::gift::{GiftCatalog, GiftItem, GiftPreferences, GiftReaction};
+use crate::domain::scenario::DialogueBlock;
+use crate::infrastructure::scenario_loader::ScenarioLoader;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// アイテム定義・好みの設定ファイルのパス
+pub const GIFT_DATA_PATH: &str = "assets/gifts/gifts.json";
+
+/// 反応セリフのシナリオの配置ディレクトリ
+pub const GIFT_REACTION_DIRECTORY: &str = "assets/scenarios/gifts";
+
+/// 設定ファイル全体
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct GiftDataFile {
+    giver: Option<String>,
+    items: Vec<GiftItemRecord>,
+    /// キャラクターID → 種類 → 反応
+    preferences: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// アイテム定義1件
+#[derive(Deserialize, Debug)]
+struct GiftItemRecord {
+    id: String,
+    name: String,
+    category: String,
+}
+
+/// プレゼントの設定を読み込む（ファイルがない・読めない場合はアイテムなし）
+pub fn load_gift_data(path: &Path) -> GiftService {
+    let Ok(content) = fs::read_to_string(path) else {
+        return GiftService::default();
+    };
+
+    match parse_gift_data(&content) {
+        Ok(service) => {
+            println!("🎁 プレゼントの設定を読み込みました: {:?} ({} 件)", path, service.catalog().items().len());
+            service
+        }
+        Err(e) => {
+            eprintln!("⚠️ プレゼントの設定を読み込めません（アイテムなしで続行）{:?}: {}", path, e);
+            GiftService::default()
+        }
+    }
+}
+
+/// 設定ファイルの内容を解析
+pub fn parse_gift_data(content: &str) -> Result<GiftService, String> {
+    let file: GiftDataFile = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let giver = file.giver.unwrap_or_else(|| GiftService::default().giver().to_string());
+
+    let mut catalog = GiftCatalog::default();
+    for record in file.items {
+        if record.id.is_empty() || record.category.is_empty() {
+            return Err(format!("{}: id と category を指定してください", record.name));
+        }
+        if catalog.get(&record.id).is_some() {
+            return Err(format!("{}: アイテムのIDが重複しています", record.id));
+        }
+        catalog.insert(GiftItem::new(&record.id, &record.name, &record.category));
+    }
+
+    let categories = catalog.categories();
+    let mut preferences = GiftPreferences::default();
+    for (character_id, reactions) in &file.preferences {
+        if *character_id == giver {
+            return Err(format!("{}: プレゼントを渡す側の好みは指定できません", character_id));
+        }
+        for (category, reaction_name) in reactions {
+            if !categories.contains(&category.as_str()) {
+                return Err(format!("{}: アイテムにない種類です: {}", character_id, category));
+            }
+            let reaction = GiftReaction::from_name(reaction_name).ok_or_else(|| {
+                format!("{}: 未対応の反応です（favorite/normal/disliked）: {}", character_id, reaction_name)
+            })?;
+            preferences.set(character_id, category, reaction);
+        }
+    }
+
+    Ok(GiftService::new(&giver, catalog, preferences))
+}
+
+/// キャラクター・反応ごとの反応セリフのシナリオのパス
+pub fn reaction_scenario_path(dir: &Path, character_id: &str, reaction: GiftReaction) -> PathBuf {
+    dir.join(format!("{}_{}.md", character_id, reaction.as_str()))
+}
+
+/// キャラクター・反応ごとの反応セリフ（各シナリオの全シーンのセリフを順に並べたもの）
+#[derive(Debug, Default)]
+pub struct GiftReactionLines {
+    lines: HashMap<(String, GiftReaction), Vec<DialogueBlock>>,
+}
+
+impl GiftReactionLines {
+    /// 渡す相手全員の反応セリフを読み込む（ファイルがない反応はセリフなし）
+    ///
+    /// 通常のシナリオと同じく読み込むため、セリフIDは翻訳ファイルと一致し、話者はキャラクター登録と照合される
+    pub fn load(
+        dir: &Path,
+        receivers: &[&str],
+        command_registry: &SceneCommandRegistry,
+        characters: &CharacterRegistry,
+    ) -> Self {
+        let mut lines = HashMap::new();
+        for receiver in receivers {
+            for reaction in [GiftReaction::Favorite, GiftReaction::Normal, GiftReaction::Disliked] {
+                let path = reaction_scenario_path(dir, receiver, reaction);
+                let mut scenario = match ScenarioLoader::load_from_file(&path, command_registry) {
+                    Ok(scenario) => scenario,
+                    Err(e) => {
+                        eprintln!("⚠️ 反応セリフを読み込めません {:?}: {}", path, e);
+                        continue;
+                    }
+                };
+                for speaker in scenario.resolve_speakers(characters) {
+                    eprintln!("⚠️ 未登録の話者: {} ({:?})", speaker, path);
+                }
+
+                let blocks = scenario.scenes.into_iter().flat_map(|scene| scene.dialogue_blocks).collect();
+                lines.insert((receiver.to_string(), reaction), blocks);
+            }
+        }
+        Self { lines }
+    }
+
+    /// 反応セリフ（読み込んでいなければ空）
+    pub fn lines(&self, character_id: &str, reaction: GiftReaction) -> &[DialogueBlock] {
+        self.lines
+            .get(&(character_id.to_string(), reaction))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gift_data() {
+        let service = parse_gift_data(
+            r#"{
+                "items": [
+                    { "id": "rose", "name": "バラの花束", "category": "flowers" },
+                    { "id": "dagger", "name": "短剣", "category": "weapons" }
+                ],
+                "preferences": { "yuzuki": { "flowers": "favorite", "weapons": "disliked" }, "kai": {} }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(service.giver(), "souma");
+        assert_eq!(service.catalog().get("dagger").unwrap().name, "短剣");
+        assert_eq!(service.receivers(), vec!["kai", "yuzuki"]);
+    }
+
+    #[test]
+    fn test_parse_gift_data_rejects_invalid_entries() {
+        let items = r#"[{ "id": "rose", "name": "バラの花束", "category": "flowers" }]"#;
+        assert!(parse_gift_data(&format!(r#"{{ "items": {items}, "preferences": {{ "yuzuki": {{ "flowers": "love" }} }} }}"#)).is_err());
+        assert!(parse_gift_data(&format!(r#"{{ "items": {items}, "preferences": {{ "yuzuki": {{ "books": "favorite" }} }} }}"#)).is_err());
+        assert!(parse_gift_data(&format!(r#"{{ "items": {items}, "preferences": {{ "souma": {{ "flowers": "favorite" }} }} }}"#)).is_err());
+        assert!(parse_gift_data(
+            r#"{ "items": [
+                { "id": "rose", "name": "バラの花束", "category": "flowers" },
+                { "id": "rose", "name": "赤いバラ", "category": "flowers" }
+            ] }"#
+        ).is_err());
+    }
+
+    #[test]
+    fn test_bundled_gift_data_has_reaction_lines() {
+        let content = fs::read_to_string(GIFT_DATA_PATH).unwrap();
+        let service = parse_gift_data(&content).unwrap();
+        assert!(!service.catalog().items().is_empty());
+
+        let mut characters = CharacterRegistry::new();
+        characters.register_default_characters();
+        let receivers = service.receivers();
+        let reaction_lines = GiftReactionLines::load(
+            Path::new(GIFT_REACTION_DIRECTORY),
+            &receivers,
+            &SceneCommandRegistry::new(),
+            &characters,
+        );
+
+        for receiver in receivers {
+            for reaction in [GiftReaction::Favorite, GiftReaction::Normal, GiftReaction::Disliked] {
+                let lines = reaction_lines.lines(receiver, reaction);
+                assert!(!lines.is_empty(), "{} の {:?} の反応セリフがありません", receiver, reaction);
+                // 話者は受け取った本人で、セリフIDはシナリオ名から付く
+                assert!(lines.iter().all(|line| line.character_id.as_deref() == Some(receiver)));
+                assert!(lines.iter().all(|line| !line.line_id.is_empty()));
+            }
+        }
+    }
+}
@@ -3,6 +3,16 @@
 //! このモジュールには以下が含まれます：
 //! - ファイルIO処理（scenario_loader）
 //! - アセット管理（asset_manager）
+//! - ボイス台本の書き出し（voice_script）
+//! - 翻訳ファイルの読み込みと PO 形式での書き出し・取り込み（translation）
+//! - 関係値の設定ファイルの読み込み（relationship_data）
+//! - プレゼントの設定ファイルと反応セリフの読み込み（gift_data）
+//! - セーブデータの保存と復元（save_data）
 
 pub mod scenario_loader;
 pub mod asset_manager;
+pub mod voice_script;
+pub mod translation;
+pub mod relationship_data;
+pub mod gift_data;
+pub mod save_data;
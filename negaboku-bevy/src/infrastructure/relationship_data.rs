@@ -0,0 +1,509 @@
+//! 関係値データ - 関係値の設定ファイルの読み込み
+//!
+//! # 責務
+//! - 関係レベルの判定基準（既定値・ペアごとの上書き）の読み込み
+//! - ペア定義（初期値・推奨／対立ペア・専用イベント・固定変動）の読み込み
+//! - エンディングの規則表の読み込み
+//! - 嫉妬の三角関係の規則の読み込み
+//!
+//! いずれも `assets/relationships/` に置く（なければ既定値）。
+//!
+//! `levels.json`:
+//! ```json
+//! {
+//!   "default": { "conflict_max": -50, "intimate_min": 50, "hysteresis": 3 },
+//!   "pairs": [{ "a": "souma", "b": "kai", "conflict_max": -40 }]
+//! }
+//! ```
+//!
+//! ペアの上書きで省略した項目は `default` の値を引き継ぐ。
+//!
+//! `pairs.json`:
+//! ```json
+//! {
+//!   "pairs": [
+//!     { "a": "souma", "b": "kai", "initial": -25, "recommended": true, "opposed": true, "events": ["souma_kai_clash"] },
+//!     { "a": "retsuji", "b": "yuzuki", "fixed_delta": 10 }
+//!   ]
+//! }
+//! ```
+//!
+//! `endings.json`:
+//! ```json
+//! {
+//!   "main_character": "souma",
+//!   "defaults": { "intimate": "good", "normal": "normal", "conflict": "bad" },
+//!   "scenarios": { "good": "endings/good.md", "normal": "endings/normal.md", "bad": "endings/bad.md" },
+//!   "rules": [
+//!     { "a": "souma", "b": "yuzuki", "level": "intimate", "ending": "true", "scenario": "endings/souma_yuzuki_true.md" }
+//!   ]
+//! }
+//! ```
+//!
+//! `triangles.json`（`drive_percent` 省略時は 50）:
+//! ```json
+//! {
+//!   "triangles": [{ "jealous": "yuzuki", "beloved": "souma", "rival": "celine", "drive_percent": 50 }]
+//! }
+//! ```
+
+use crate::domain::ending::{Ending, EndingRule, EndingTable};
+use crate::domain::jealousy::{TriangleRule, TriangleRuleTable, DEFAULT_DRIVE_PERCENT};
+use crate::domain::relationship::{
+    PairDefinition, PairKey, RelationshipLevel, RelationshipLevelConfig, RelationshipPairTable,
+    RelationshipThresholds, RELATIONSHIP_LIMIT,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// 関係レベルの判定基準ファイルのパス
+pub const LEVEL_CONFIG_PATH: &str = "assets/relationships/levels.json";
+
+/// ペア定義ファイルのパス
+pub const PAIR_TABLE_PATH: &str = "assets/relationships/pairs.json";
+
+/// エンディングの規則表のパス
+pub const ENDING_TABLE_PATH: &str = "assets/relationships/endings.json";
+
+/// 三角関係の規則のファイルのパス
+pub const TRIANGLE_RULES_PATH: &str = "assets/relationships/triangles.json";
+
+/// 判定基準ファイル全体
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct LevelConfigFile {
+    default: ThresholdsRecord,
+    pairs: Vec<PairThresholdsRecord>,
+}
+
+/// 判定基準（省略した項目は引き継ぐ）
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(default)]
+struct ThresholdsRecord {
+    conflict_max: Option<i32>,
+    intimate_min: Option<i32>,
+    hysteresis: Option<i32>,
+}
+
+/// ペアごとの上書き
+#[derive(Deserialize, Debug)]
+struct PairThresholdsRecord {
+    a: String,
+    b: String,
+    #[serde(flatten)]
+    thresholds: ThresholdsRecord,
+}
+
+/// ペア定義ファイル全体
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct PairTableFile {
+    pairs: Vec<PairRecord>,
+}
+
+/// ペア定義1件
+#[derive(Deserialize, Debug)]
+struct PairRecord {
+    a: String,
+    b: String,
+    #[serde(default)]
+    initial: i32,
+    #[serde(default)]
+    recommended: bool,
+    #[serde(default)]
+    opposed: bool,
+    #[serde(default)]
+    events: Vec<String>,
+    #[serde(default)]
+    fixed_delta: Option<i32>,
+}
+
+/// エンディングの規則表ファイル全体
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct EndingTableFile {
+    main_character: Option<String>,
+    defaults: HashMap<String, String>,
+    scenarios: HashMap<String, String>,
+    rules: Vec<EndingRuleRecord>,
+}
+
+/// エンディングの規則1件
+#[derive(Deserialize, Debug)]
+struct EndingRuleRecord {
+    a: String,
+    b: String,
+    level: String,
+    ending: String,
+    #[serde(default)]
+    scenario: Option<String>,
+}
+
+/// 三角関係の規則ファイル全体
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct TriangleRulesFile {
+    triangles: Vec<TriangleRuleRecord>,
+}
+
+/// 三角関係の規則1件
+#[derive(Deserialize, Debug)]
+struct TriangleRuleRecord {
+    jealous: String,
+    beloved: String,
+    rival: String,
+    #[serde(default)]
+    drive_percent: Option<i32>,
+}
+
+impl ThresholdsRecord {
+    /// 省略された項目を `base` で補って判定基準にする
+    fn merge_into(self, base: RelationshipThresholds) -> RelationshipThresholds {
+        RelationshipThresholds {
+            conflict_max: self.conflict_max.unwrap_or(base.conflict_max),
+            intimate_min: self.intimate_min.unwrap_or(base.intimate_min),
+            hysteresis: self.hysteresis.unwrap_or(base.hysteresis),
+        }
+    }
+}
+
+/// 関係レベルの判定基準を読み込む（ファイルがない・読めない場合は既定値）
+pub fn load_level_config(path: &Path) -> RelationshipLevelConfig {
+    let Ok(content) = fs::read_to_string(path) else {
+        return RelationshipLevelConfig::default();
+    };
+
+    match parse_level_config(&content) {
+        Ok(config) => {
+            println!("💞 関係レベルの判定基準を読み込みました: {:?}", path);
+            config
+        }
+        Err(e) => {
+            eprintln!("⚠️ 関係レベルの判定基準を読み込めません（既定値を使用）{:?}: {}", path, e);
+            RelationshipLevelConfig::default()
+        }
+    }
+}
+
+/// 判定基準ファイルの内容を解析
+pub fn parse_level_config(content: &str) -> Result<RelationshipLevelConfig, String> {
+    let file: LevelConfigFile = serde_json::from_str(content).map_err(|e| e.to_string())?;
+
+    let defaults = file.default.merge_into(RelationshipThresholds::default());
+    validate_thresholds("default", &defaults)?;
+
+    let mut config = RelationshipLevelConfig::new(defaults);
+    for pair in file.pairs {
+        let thresholds = pair.thresholds.merge_into(defaults);
+        validate_thresholds(&format!("{} × {}", pair.a, pair.b), &thresholds)?;
+        config.set_pair_override(&pair.a, &pair.b, thresholds);
+    }
+
+    Ok(config)
+}
+
+/// ペア定義を読み込む（ファイルがない・読めない場合は定義なし）
+pub fn load_pair_table(path: &Path) -> RelationshipPairTable {
+    let Ok(content) = fs::read_to_string(path) else {
+        return RelationshipPairTable::default();
+    };
+
+    match parse_pair_table(&content) {
+        Ok(table) => {
+            println!("💞 ペア定義を読み込みました: {:?} ({} 件)", path, table.len());
+            table
+        }
+        Err(e) => {
+            eprintln!("⚠️ ペア定義を読み込めません（定義なしで続行）{:?}: {}", path, e);
+            RelationshipPairTable::default()
+        }
+    }
+}
+
+/// ペア定義ファイルの内容を解析
+pub fn parse_pair_table(content: &str) -> Result<RelationshipPairTable, String> {
+    let file: PairTableFile = serde_json::from_str(content).map_err(|e| e.to_string())?;
+
+    let mut table = RelationshipPairTable::default();
+    for record in file.pairs {
+        let key = PairKey::new(&record.a, &record.b);
+        if record.a == record.b {
+            return Err(format!("{}: 同じキャラクター同士のペアは定義できません", key));
+        }
+        if table.get(&key).is_some() {
+            return Err(format!("{}: ペアが重複しています", key));
+        }
+        if !(-RELATIONSHIP_LIMIT..=RELATIONSHIP_LIMIT).contains(&record.initial) {
+            return Err(format!("{}: initial は -100～100 の範囲で指定してください: {}", key, record.initial));
+        }
+        if record.fixed_delta.is_some_and(|delta| delta <= 0) {
+            return Err(format!("{}: fixed_delta は正の数で指定してください", key));
+        }
+
+        table.insert(PairDefinition {
+            key,
+            initial_value: record.initial,
+            is_recommended: record.recommended,
+            is_opposed: record.opposed,
+            special_events: record.events,
+            fixed_delta: record.fixed_delta,
+        });
+    }
+
+    Ok(table)
+}
+
+/// エンディングの規則表を読み込む（ファイルがない・読めない場合は既定の規則）
+pub fn load_ending_table(path: &Path) -> EndingTable {
+    let Ok(content) = fs::read_to_string(path) else {
+        return EndingTable::default();
+    };
+
+    match parse_ending_table(&content) {
+        Ok(table) => {
+            println!("🎬 エンディングの規則表を読み込みました: {:?} ({} 件)", path, table.rules().len());
+            table
+        }
+        Err(e) => {
+            eprintln!("⚠️ エンディングの規則表を読み込めません（既定の規則を使用）{:?}: {}", path, e);
+            EndingTable::default()
+        }
+    }
+}
+
+/// エンディングの規則表の内容を解析
+pub fn parse_ending_table(content: &str) -> Result<EndingTable, String> {
+    let file: EndingTableFile = serde_json::from_str(content).map_err(|e| e.to_string())?;
+
+    let level = |name: &str| RelationshipLevel::from_name(name)
+        .ok_or_else(|| format!("未対応の関係レベルです（intimate/normal/conflict）: {}", name));
+    let ending = |name: &str| Ending::from_name(name)
+        .ok_or_else(|| format!("未対応のエンディングです（true/good/normal/bad）: {}", name));
+
+    let mut table = EndingTable::default();
+    if let Some(main_character) = file.main_character {
+        table.main_character = main_character;
+    }
+    for (level_name, ending_name) in &file.defaults {
+        table.set_default(level(level_name)?, ending(ending_name)?);
+    }
+    for (ending_name, scenario) in &file.scenarios {
+        table.set_scenario(ending(ending_name)?, scenario);
+    }
+
+    for record in file.rules {
+        let pair = PairKey::new(&record.a, &record.b);
+        if record.a == record.b {
+            return Err(format!("{}: 同じキャラクター同士のペアは定義できません", pair));
+        }
+        if !pair.contains(&table.main_character) {
+            return Err(format!("{}: 主人公（{}）を含むペアを指定してください", pair, table.main_character));
+        }
+        let rule = EndingRule {
+            level: level(&record.level)?,
+            ending: ending(&record.ending)?,
+            scenario: record.scenario,
+            pair,
+        };
+        if table.rule_for(&rule.pair, rule.level).is_some() {
+            return Err(format!("{}: {} の規則が重複しています", rule.pair, rule.level.as_str()));
+        }
+        table.add_rule(rule);
+    }
+
+    Ok(table)
+}
+
+/// 三角関係の規則を読み込む（ファイルがない・読めない場合は規則なし）
+pub fn load_triangle_rules(path: &Path) -> TriangleRuleTable {
+    let Ok(content) = fs::read_to_string(path) else {
+        return TriangleRuleTable::default();
+    };
+
+    match parse_triangle_rules(&content) {
+        Ok(table) => {
+            println!("💔 三角関係の規則を読み込みました: {:?} ({} 件)", path, table.len());
+            table
+        }
+        Err(e) => {
+            eprintln!("⚠️ 三角関係の規則を読み込めません（規則なしで続行）{:?}: {}", path, e);
+            TriangleRuleTable::default()
+        }
+    }
+}
+
+/// 三角関係の規則ファイルの内容を解析
+pub fn parse_triangle_rules(content: &str) -> Result<TriangleRuleTable, String> {
+    let file: TriangleRulesFile = serde_json::from_str(content).map_err(|e| e.to_string())?;
+
+    let mut table = TriangleRuleTable::default();
+    for record in file.triangles {
+        let label = format!("{} → {} ← {}", record.jealous, record.beloved, record.rival);
+        if record.jealous == record.beloved || record.beloved == record.rival || record.jealous == record.rival {
+            return Err(format!("{}: 3人とも別のキャラクターを指定してください", label));
+        }
+        let drive_percent = record.drive_percent.unwrap_or(DEFAULT_DRIVE_PERCENT);
+        if !(0..=100).contains(&drive_percent) {
+            return Err(format!("{}: drive_percent は 0～100 の範囲で指定してください: {}", label, drive_percent));
+        }
+        if table.rules().iter().any(|rule| {
+            rule.jealous == record.jealous && rule.beloved == record.beloved && rule.rival == record.rival
+        }) {
+            return Err(format!("{}: 規則が重複しています", label));
+        }
+
+        table.insert(TriangleRule {
+            drive_percent,
+            ..TriangleRule::new(&record.jealous, &record.beloved, &record.rival)
+        });
+    }
+
+    Ok(table)
+}
+
+/// 通常の範囲が空にならないか、余裕幅が負でないかを検証
+fn validate_thresholds(label: &str, thresholds: &RelationshipThresholds) -> Result<(), String> {
+    if thresholds.conflict_max >= thresholds.intimate_min {
+        return Err(format!(
+            "{}: conflict_max ({}) は intimate_min ({}) より小さくしてください",
+            label, thresholds.conflict_max, thresholds.intimate_min
+        ));
+    }
+    if thresholds.hysteresis < 0 {
+        return Err(format!("{}: hysteresis は 0 以上にしてください", label));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level_config_with_pair_override() {
+        let config = parse_level_config(
+            r#"{
+                "default": { "hysteresis": 3 },
+                "pairs": [{ "a": "souma", "b": "kai", "conflict_max": -40 }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.defaults, RelationshipThresholds { hysteresis: 3, ..Default::default() });
+        assert_eq!(
+            config.thresholds_for("kai", "souma"),
+            RelationshipThresholds { conflict_max: -40, intimate_min: 50, hysteresis: 3 }
+        );
+    }
+
+    #[test]
+    fn test_parse_pair_table() {
+        let table = parse_pair_table(
+            r#"{
+                "pairs": [
+                    { "a": "souma", "b": "kai", "initial": -25, "recommended": true, "opposed": true, "events": ["souma_kai_clash"] },
+                    { "a": "retsuji", "b": "yuzuki", "fixed_delta": 10 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let souma_kai = table.get(&PairKey::new("kai", "souma")).unwrap();
+        assert_eq!(souma_kai.initial_value, -25);
+        assert!(souma_kai.is_recommended && souma_kai.is_opposed);
+        assert_eq!(souma_kai.special_events, vec!["souma_kai_clash".to_string()]);
+        assert_eq!(table.get(&PairKey::new("yuzuki", "retsuji")).unwrap().fixed_delta, Some(10));
+        assert_eq!(table.initial_value(&PairKey::new("souma", "yuzuki")), 0);
+    }
+
+    #[test]
+    fn test_parse_pair_table_rejects_invalid_pairs() {
+        assert!(parse_pair_table(r#"{ "pairs": [{ "a": "souma", "b": "souma" }] }"#).is_err());
+        assert!(parse_pair_table(r#"{ "pairs": [{ "a": "souma", "b": "kai" }, { "a": "kai", "b": "souma" }] }"#).is_err());
+        assert!(parse_pair_table(r#"{ "pairs": [{ "a": "souma", "b": "kai", "initial": -150 }] }"#).is_err());
+        assert!(parse_pair_table(r#"{ "pairs": [{ "a": "souma", "b": "kai", "fixed_delta": 0 }] }"#).is_err());
+    }
+
+    #[test]
+    fn test_bundled_pair_table_is_valid() {
+        let content = fs::read_to_string(PAIR_TABLE_PATH).unwrap();
+        let table = parse_pair_table(&content).unwrap();
+        assert_eq!(table.initial_value(&PairKey::new("souma", "kai")), -25);
+    }
+
+    #[test]
+    fn test_parse_ending_table() {
+        let table = parse_ending_table(
+            r#"{
+                "defaults": { "intimate": "good" },
+                "scenarios": { "normal": "endings/normal.md" },
+                "rules": [{ "a": "yuzuki", "b": "souma", "level": "intimate", "ending": "true", "scenario": "endings/souma_yuzuki_true.md" }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(table.main_character, "souma");
+        assert_eq!(table.ending_for(&PairKey::new("souma", "yuzuki"), RelationshipLevel::Intimate), Ending::True);
+        assert_eq!(table.ending_for(&PairKey::new("souma", "kai"), RelationshipLevel::Conflict), Ending::Bad);
+
+        assert!(parse_ending_table(r#"{ "defaults": { "friendly": "good" } }"#).is_err());
+        assert!(parse_ending_table(r#"{ "rules": [{ "a": "souma", "b": "kai", "level": "intimate", "ending": "best" }] }"#).is_err());
+        assert!(parse_ending_table(r#"{ "rules": [{ "a": "yuzuki", "b": "kai", "level": "intimate", "ending": "true" }] }"#).is_err());
+        assert!(parse_ending_table(
+            r#"{ "rules": [
+                { "a": "souma", "b": "kai", "level": "intimate", "ending": "true" },
+                { "a": "kai", "b": "souma", "level": "intimate", "ending": "good" }
+            ] }"#
+        ).is_err());
+    }
+
+    #[test]
+    fn test_bundled_ending_table_is_valid() {
+        let content = fs::read_to_string(ENDING_TABLE_PATH).unwrap();
+        let table = parse_ending_table(&content).unwrap();
+        for rule in table.rules() {
+            if let Some(scenario) = &rule.scenario {
+                assert!(Path::new("assets/scenarios").join(scenario).exists(), "{} がありません", scenario);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_triangle_rules() {
+        let table = parse_triangle_rules(
+            r#"{
+                "triangles": [
+                    { "jealous": "yuzuki", "beloved": "souma", "rival": "celine" },
+                    { "jealous": "celine", "beloved": "souma", "rival": "yuzuki", "drive_percent": 30 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(table.rules()[0].drive_percent, DEFAULT_DRIVE_PERCENT);
+        assert_eq!(table.between("celine", "yuzuki").next().unwrap().drive_percent, 30);
+
+        assert!(parse_triangle_rules(r#"{ "triangles": [{ "jealous": "yuzuki", "beloved": "souma", "rival": "yuzuki" }] }"#).is_err());
+        assert!(parse_triangle_rules(
+            r#"{ "triangles": [{ "jealous": "yuzuki", "beloved": "souma", "rival": "celine", "drive_percent": 150 }] }"#
+        ).is_err());
+        assert!(parse_triangle_rules(
+            r#"{ "triangles": [
+                { "jealous": "yuzuki", "beloved": "souma", "rival": "celine" },
+                { "jealous": "yuzuki", "beloved": "souma", "rival": "celine", "drive_percent": 10 }
+            ] }"#
+        ).is_err());
+
+        let bundled = parse_triangle_rules(&fs::read_to_string(TRIANGLE_RULES_PATH).unwrap()).unwrap();
+        assert!(bundled.between("yuzuki", "celine").next().is_some());
+    }
+
+    #[test]
+    fn test_parse_level_config_rejects_invalid_thresholds() {
+        assert!(parse_level_config(r#"{ "default": { "conflict_max": 60 } }"#).is_err());
+        assert!(parse_level_config(r#"{ "pairs": [{ "a": "x", "b": "y", "hysteresis": -1 }] }"#).is_err());
+        assert!(parse_level_config("{ 壊れたJSON").is_err());
+    }
+}
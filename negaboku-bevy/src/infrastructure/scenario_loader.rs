@@ -4,17 +4,39 @@
 //! - マークダウンファイルの読み込み
 //! - pulldown-cmarkを使用したパース
 //! - シーンコマンドとダイアログの抽出
-
-use crate::domain::scenario::{Scene, SceneCommand, DialogueBlock, ScenarioFile};
+//! - マクロ定義の読み込みと展開（シナリオ内・共有 macros.md）
+//! - 先頭フロントマター（`---` ブロック）の読み込み
+
+use crate::domain::scenario::{
+    Scene, SceneCommand, DialogueBlock, ScenarioFile, ScenarioMetadata, FrontMatterValue, ParseError, VoiceCommand,
+};
+use crate::domain::scenario_macro::{MacroLibrary, SourceLine};
+use crate::domain::command_registry::SceneCommandRegistry;
+use crate::application::command_executor::builtin_command_registry;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// シナリオローダー
 pub struct ScenarioLoader;
 
+/// 解析中の段落（空行で区切られるまでの本文行と、直前に指定されたボイス）
+#[derive(Default)]
+struct Paragraph {
+    lines: Vec<String>,
+    /// 各行の元ファイルでの行番号（マクロ展開行は None）
+    line_numbers: Vec<Option<usize>>,
+    voice: Option<String>,
+}
+
 impl ScenarioLoader {
     /// マークダウンファイルからシナリオを読み込み
-    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<ScenarioFile, std::io::Error> {
+    ///
+    /// コマンド行は `registry` に登録されたハンドラで解釈する
+    pub fn load_from_file<P: AsRef<Path>>(
+        path: P,
+        registry: &SceneCommandRegistry,
+    ) -> Result<ScenarioFile, std::io::Error> {
         let path_ref = path.as_ref();
         println!("🔍 シナリオファイル読み込み試行: {:?}", path_ref);
 
@@ -23,7 +45,7 @@ impl ScenarioLoader {
             println!("✅ 相対パスでファイル発見: {:?}", path_ref);
             let content = fs::read_to_string(&path)?;
             println!("📄 ファイル内容読み込み成功 ({} 文字)", content.len());
-            return Ok(Self::parse_markdown(&content));
+            return Ok(Self::parse_file_content(&content, path_ref, registry));
         } else {
             println!("❌ 相対パスにファイルが見つかりません: {:?}", path_ref);
         }
@@ -55,7 +77,7 @@ impl ScenarioLoader {
             println!("✅ 絶対パスでファイル発見: {:?}", resolved_path);
             let content = fs::read_to_string(&resolved_path)?;
             println!("📄 ファイル内容読み込み成功 ({} 文字)", content.len());
-            return Ok(Self::parse_markdown(&content));
+            return Ok(Self::parse_file_content(&content, &resolved_path, registry));
         } else {
             println!("❌ 絶対パスにもファイルが見つかりません: {:?}", resolved_path);
         }
@@ -64,17 +86,150 @@ impl ScenarioLoader {
         println!("🔍 最終試行: 元のパスで読み込み");
         fs::read_to_string(&path).map(|content| {
             println!("📄 最終試行成功 ({} 文字)", content.len());
-            Self::parse_markdown(&content)
+            Self::parse_file_content(&content, path_ref, registry)
         })
     }
 
-    /// マークダウンコンテンツをパースしてシナリオに変換
+    /// 共有マクロファイル（macros.md）を読み込み
+    pub fn load_macros_from_file<P: AsRef<Path>>(path: P) -> Result<MacroLibrary, std::io::Error> {
+        let content = fs::read_to_string(path.as_ref())?;
+        let (library, _, errors) = MacroLibrary::extract_definitions(&content);
+
+        for error in &errors {
+            eprintln!("⚠️ マクロ定義エラー: {:?} {}行目 - {}", path.as_ref(), error.line_number, error.message);
+        }
+        println!("🧩 共有マクロ読み込み: {:?} ({} 個)", path.as_ref(), library.len());
+
+        Ok(library)
+    }
+
+    /// ディレクトリ内のシナリオファイル（共有マクロを除く `.md`）をサブディレクトリも含めてパス順に列挙
+    ///
+    /// エンディング（`endings/`）やプレゼントの反応（`gifts/`）もシナリオとして扱う
+    pub fn scenario_paths<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, std::io::Error> {
+        let mut paths = Vec::new();
+        Self::collect_scenario_paths(dir.as_ref(), &mut paths)?;
+        paths.sort();
+        Ok(paths)
+    }
+
+    fn collect_scenario_paths(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), std::io::Error> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                Self::collect_scenario_paths(&path, paths)?;
+            } else if path.extension().is_some_and(|ext| ext == "md")
+                && path.file_name().is_some_and(|name| name != SHARED_MACRO_FILE)
+            {
+                paths.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// セリフIDが書かれていないダイアログに `{#id}` を書き足してファイルを更新
+    ///
+    /// 現在の自動付与ID（本文のハッシュ）をそのまま書き込むため、以後は本文を直してもIDが変わらない。
+    /// 書き足したIDの数を返す
+    pub fn write_missing_line_ids<P: AsRef<Path>>(
+        path: P,
+        registry: &SceneCommandRegistry,
+    ) -> Result<usize, std::io::Error> {
+        let content = fs::read_to_string(path.as_ref())?;
+        let scenario = Self::parse_file_content(&content, path.as_ref(), registry);
+
+        let (updated, count) = Self::insert_missing_line_ids(&content, &scenario);
+        if count > 0 {
+            fs::write(path.as_ref(), updated)?;
+            println!("🏷️ セリフIDを書き込み: {:?} ({} 件)", path.as_ref(), count);
+        }
+        Ok(count)
+    }
+
+    /// 解析済みシナリオのセリフIDのうち、原文に書かれていないものを各ブロックの最終行末に書き足す
+    ///
+    /// マクロ展開で生成されたダイアログは対象外
+    pub fn insert_missing_line_ids(content: &str, scenario: &ScenarioFile) -> (String, usize) {
+        let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+        let mut count = 0;
+
+        for block in scenario.scenes.iter().flat_map(|scene| scene.dialogue_blocks.iter()) {
+            let marker = format!("{{#{}}}", block.line_id);
+            let Some(line) = block.source_line.and_then(|number| lines.get_mut(number.wrapping_sub(1))) else {
+                continue;
+            };
+            if content.contains(&marker) {
+                continue;
+            }
+
+            // 行末の \r は残す
+            let body_len = line.trim_end_matches('\r').len();
+            line.insert_str(body_len, &format!(" {}", marker));
+            count += 1;
+        }
+
+        (lines.join("\n"), count)
+    }
+
+    /// ファイル内容をパース（同じディレクトリの macros.md を共有マクロとして使用）
+    fn parse_file_content(content: &str, scenario_path: &Path, registry: &SceneCommandRegistry) -> ScenarioFile {
+        let shared_macros = scenario_path
+            .parent()
+            .map(|dir| dir.join(SHARED_MACRO_FILE))
+            .filter(|macro_path| macro_path.exists() && macro_path != scenario_path)
+            .and_then(|macro_path| Self::load_macros_from_file(macro_path).ok())
+            .unwrap_or_default();
+
+        // chapter 指定がない場合はファイル名をセリフIDの接頭辞にする
+        let line_id_prefix = scenario_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(DEFAULT_LINE_ID_PREFIX);
+
+        Self::parse_markdown_with_line_id_prefix(content, registry, &shared_macros, line_id_prefix)
+    }
+
+    /// マークダウンコンテンツをパースしてシナリオに変換（組み込みコマンドのみ解釈）
     pub fn parse_markdown(content: &str) -> ScenarioFile {
+        Self::parse_markdown_with_registry(content, &builtin_command_registry())
+    }
+
+    /// マークダウンコンテンツをパースしてシナリオに変換（登録済みコマンドを解釈）
+    pub fn parse_markdown_with_registry(content: &str, registry: &SceneCommandRegistry) -> ScenarioFile {
+        Self::parse_markdown_with_macros(content, registry, &MacroLibrary::new())
+    }
+
+    /// マークダウンコンテンツをパースしてシナリオに変換（共有マクロを使用）
+    ///
+    /// シナリオ内で定義されたマクロは同名の共有マクロより優先される
+    pub fn parse_markdown_with_macros(
+        content: &str,
+        registry: &SceneCommandRegistry,
+        shared_macros: &MacroLibrary,
+    ) -> ScenarioFile {
+        Self::parse_markdown_with_line_id_prefix(content, registry, shared_macros, DEFAULT_LINE_ID_PREFIX)
+    }
+
+    /// マークダウンコンテンツをパースしてシナリオに変換
+    ///
+    /// `line_id_prefix` はフロントマターに chapter 指定がない場合の自動セリフIDの接頭辞
+    fn parse_markdown_with_line_id_prefix(
+        content: &str,
+        registry: &SceneCommandRegistry,
+        shared_macros: &MacroLibrary,
+        line_id_prefix: &str,
+    ) -> ScenarioFile {
         println!("🔄 マークダウンパース開始 ({} 文字)", content.len());
 
-        // シンプルな行ベース解析を使用（pulldown-cmarkは複雑すぎるため）
-        let lines: Vec<&str> = content.lines().collect();
-        println!("📝 総行数: {}", lines.len());
+        // マクロ定義を抜き出し、残りをシンプルな行ベース解析で処理（pulldown-cmarkは複雑すぎるため）
+        let (local_macros, source_lines, mut errors) = MacroLibrary::extract_definitions(content);
+        println!("📝 総行数: {}", content.lines().count());
+
+        let mut macros = shared_macros.clone();
+        macros.merge(local_macros);
+        if !macros.is_empty() {
+            println!("🧩 使用可能なマクロ: {:?}", macros.names());
+        }
 
         let mut current_scene = Scene {
             commands: Vec::new(),
@@ -84,15 +239,48 @@ impl ScenarioLoader {
         let mut title = "無題シナリオ".to_string();
         let mut found_title = false;
 
-        for line in lines {
-            let trimmed_line = line.trim();
+        // 登録済みコマンドと同名のマクロは無効（コマンドを上書きしない）
+        for name in macros.names() {
+            if registry.contains(&name) {
+                let conflicting = macros.remove(&name).unwrap();
+                errors.push(ParseError {
+                    line_number: conflicting.line_number,
+                    message: format!("マクロ {} は既存のコマンド名と重複しています", name),
+                });
+            }
+        }
+
+        // 先頭の --- ブロックがフロントマターとして解釈できる場合のみメタデータとして扱う
+        let (metadata, body_start) = match Self::split_front_matter(&source_lines) {
+            Some((metadata, consumed, front_matter_errors)) => {
+                errors.extend(front_matter_errors);
+                (metadata, consumed)
+            }
+            None => (ScenarioMetadata::default(), 0),
+        };
+
+        let mut lines = Vec::new();
+        for source_line in &source_lines[body_start..] {
+            match macros.expand_line(source_line) {
+                Ok(expanded) => lines.extend(expanded),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        // 空行で区切られるまでの連続した本文行（1つの段落）
+        let mut paragraph = Paragraph::default();
+
+        for line in &lines {
+            let trimmed_line = line.text.trim();
 
             if trimmed_line.is_empty() {
+                Self::flush_paragraph(&mut paragraph, &mut current_scene);
                 continue;
             }
 
             // タイトル行の検出（# で始まる）
             if trimmed_line.starts_with("# ") && !found_title {
+                Self::flush_paragraph(&mut paragraph, &mut current_scene);
                 title = trimmed_line[2..].trim().to_string();
                 found_title = true;
                 continue;
@@ -100,6 +288,7 @@ impl ScenarioLoader {
 
             // セクション区切りの検出
             if trimmed_line == "---" {
+                Self::flush_paragraph(&mut paragraph, &mut current_scene);
                 if !current_scene.commands.is_empty() || !current_scene.dialogue_blocks.is_empty() {
                     scenes.push(current_scene);
                     current_scene = Scene {
@@ -112,6 +301,7 @@ impl ScenarioLoader {
 
             // ヘッダー行でシーン区切り処理（## で始まる）
             if trimmed_line.starts_with("##") {
+                Self::flush_paragraph(&mut paragraph, &mut current_scene);
                 // 現在のシーンに内容があれば保存
                 if !current_scene.commands.is_empty() || !current_scene.dialogue_blocks.is_empty() {
                     scenes.push(current_scene);
@@ -124,20 +314,43 @@ impl ScenarioLoader {
             }
 
             // 行を処理
-            Self::process_line(trimmed_line, &mut current_scene);
+            Self::process_line(line, &mut current_scene, &mut paragraph, registry, &mut errors);
         }
+        Self::flush_paragraph(&mut paragraph, &mut current_scene);
 
         // 最後のシーンを追加
         if !current_scene.commands.is_empty() || !current_scene.dialogue_blocks.is_empty() {
             scenes.push(current_scene);
         }
 
-        let scenario_file = ScenarioFile {
+        // フロントマターの表示タイトルは見出しより優先
+        if let Some(display_title) = &metadata.display_title {
+            title = display_title.clone();
+        }
+
+        let line_id_prefix = metadata.chapter_id.clone().unwrap_or_else(|| line_id_prefix.to_string());
+        let mut scenario_file = ScenarioFile {
             title: title.clone(),
             scenes,
             current_scene_index: 0,
+            parse_errors: Vec::new(),
+            metadata,
         };
 
+        // 明示されたセリフIDの重複（自動付与の前に確認する）
+        for block in scenario_file.duplicate_line_ids() {
+            errors.push(ParseError {
+                line_number: block.source_line.unwrap_or(0),
+                message: format!("セリフID {} が重複しています", block.line_id),
+            });
+        }
+        scenario_file.assign_line_ids(&line_id_prefix);
+
+        for error in &errors {
+            eprintln!("⚠️ シナリオ解析エラー: {}行目 - {}", error.line_number, error.message);
+        }
+        scenario_file.parse_errors = errors;
+
         println!("✅ マークダウンパース完了");
         println!("📋 タイトル: {}", title);
         println!("🎬 シーン数: {}", scenario_file.scenes.len());
@@ -149,8 +362,120 @@ impl ScenarioLoader {
         scenario_file
     }
 
-    /// 1行を処理してコマンドまたはダイアログを抽出
-    fn process_line(line: &str, scene: &mut Scene) {
+    /// 先頭のフロントマターを分離
+    ///
+    /// 最初の空行以外の行が `---` で、次の `---` までのすべての行が
+    /// `key: value` / `- item` / `# コメント` / 空行で、かつ `key:` 行を1つ以上含む場合のみ
+    /// フロントマターとみなす。
+    /// それ以外の `---` は従来通りシーン区切りとして扱う。
+    /// 戻り値は（メタデータ, フロントマターとして消費した行数, エラー）
+    fn split_front_matter(lines: &[SourceLine]) -> Option<(ScenarioMetadata, usize, Vec<ParseError>)> {
+        let start = lines.iter().position(|line| !line.text.trim().is_empty())?;
+        if lines[start].text.trim() != "---" {
+            return None;
+        }
+
+        let end = start + 1 + lines[start + 1..].iter().position(|line| line.text.trim() == "---")?;
+        let block = &lines[start + 1..end];
+
+        let is_front_matter_line = |text: &str| {
+            text.is_empty()
+                || text.starts_with('#')
+                || text.starts_with("- ")
+                || Self::front_matter_key(text).is_some()
+        };
+        let has_key = block.iter().any(|line| Self::front_matter_key(line.text.trim()).is_some());
+        if !has_key || !block.iter().all(|line| is_front_matter_line(line.text.trim())) {
+            return None;
+        }
+
+        let mut metadata = ScenarioMetadata::default();
+        let mut errors = Vec::new();
+        let mut pending: Option<(&SourceLine, String, Vec<String>)> = None;
+
+        let flush = |pending: Option<(&SourceLine, String, Vec<String>)>,
+                         metadata: &mut ScenarioMetadata,
+                         errors: &mut Vec<ParseError>| {
+            if let Some((line, key, items)) = pending {
+                if let Err(error) = metadata.set(&key, FrontMatterValue::List(items)) {
+                    errors.push(line.locate_error(error));
+                }
+            }
+        };
+
+        for line in block {
+            let text = line.text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            // ブロック形式リストの要素
+            if let Some(item) = text.strip_prefix("- ") {
+                match pending.as_mut() {
+                    Some((_, _, items)) => items.push(Self::unquote(item)),
+                    None => errors.push(line.locate_error(ParseError {
+                        line_number: 0,
+                        message: format!("リスト項目に対応するキーがありません: {}", text),
+                    })),
+                }
+                continue;
+            }
+
+            flush(pending.take(), &mut metadata, &mut errors);
+
+            let (key, raw_value) = Self::front_matter_key(text).unwrap();
+            if raw_value.is_empty() {
+                // 次行以降の `- item` を待つ
+                pending = Some((line, key.to_string(), Vec::new()));
+                continue;
+            }
+
+            let value = match raw_value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                Some(inner) => FrontMatterValue::List(
+                    inner.split(',')
+                        .map(Self::unquote)
+                        .filter(|item| !item.is_empty())
+                        .collect(),
+                ),
+                None => FrontMatterValue::Scalar(Self::unquote(raw_value)),
+            };
+            if let Err(error) = metadata.set(key, value) {
+                errors.push(line.locate_error(error));
+            }
+        }
+        flush(pending.take(), &mut metadata, &mut errors);
+
+        println!("🗂️ フロントマター読み込み: {:?}", metadata);
+        Some((metadata, end + 1, errors))
+    }
+
+    /// `key: value` 形式（キーは英数字とアンダースコアのみ）なら（キー, 値）を返す
+    fn front_matter_key(text: &str) -> Option<(&str, &str)> {
+        let (key, value) = text.split_once(':')?;
+        let valid_key = !key.is_empty()
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        valid_key.then(|| (key, value.trim()))
+    }
+
+    /// 前後の空白と引用符を除去
+    fn unquote(value: &str) -> String {
+        let trimmed = value.trim();
+        trimmed
+            .strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+            .or_else(|| trimmed.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(trimmed)
+            .to_string()
+    }
+
+    /// 1行を処理してコマンドを抽出（コマンド以外の行は段落に追加）
+    fn process_line(
+        source_line: &SourceLine,
+        scene: &mut Scene,
+        paragraph: &mut Paragraph,
+        registry: &SceneCommandRegistry,
+        errors: &mut Vec<ParseError>,
+    ) {
+        let line = source_line.text.trim();
         if line.is_empty() {
             return;
         }
@@ -159,82 +484,90 @@ impl ScenarioLoader {
 
         // コマンド行の検出（標準形式: [...] と独自形式: `cmd:args`）
         if line.starts_with('[') && line.ends_with(']') {
+            // コマンド行は段落の区切りになる
+            Self::flush_paragraph(paragraph, scene);
             // 標準形式: [bg storage=filename time=duration]
-            match SceneCommand::parse(line) {
-                Ok(command) => {
-                    scene.commands.push(command);
-                    // println!("📋 コマンド解析成功: {:?}", line);
-                }
+            match registry.parse(line) {
+                Ok(command) => match command.payload::<VoiceCommand>() {
+                    // ボイスは直後のダイアログブロックに付与する
+                    Some(voice) => paragraph.voice = Some(voice.storage.clone()),
+                    None => {
+                        scene.commands.push(command);
+                        // println!("📋 コマンド解析成功: {:?}", line);
+                    }
+                },
                 Err(error) => {
-                    eprintln!("⚠️ コマンド解析エラー: {} - {}", line, error.message);
+                    errors.push(source_line.locate_error(error));
                 }
             }
         } else if line.starts_with('`') && line.ends_with('`') {
+            Self::flush_paragraph(paragraph, scene);
             // 独自形式: `bg:backgrounds/file.png` や `char:name:face:pos`
             let inner = &line[1..line.len()-1]; // バッククォートを除去
-            if let Some(command) = Self::parse_simple_command(inner) {
+            if let Some(command) = Self::parse_simple_command(inner, registry) {
                 scene.commands.push(command);
                 println!("📋 独自コマンド解析成功: {}", inner);
             } else {
                 println!("⚠️ 独自コマンド解析失敗: {}", inner);
             }
-        } else if let Some(dialogue) = DialogueBlock::parse(line) {
-            // ダイアログブロックとして追加
-            scene.dialogue_blocks.push(dialogue.clone());
-            // println!("💬 ダイアログ解析成功: {:?}", dialogue);
         } else {
-            println!("❓ 未処理行: '{}'", line);
+            // 行末の改行指定（半角スペース2つ）を残すため、元の行をそのまま保持
+            paragraph.lines.push(source_line.text.clone());
+            paragraph
+                .line_numbers
+                .push(source_line.origin.is_none().then_some(source_line.line_number));
+        }
+    }
+
+    /// 溜まった段落をダイアログブロックとしてシーンに追加
+    fn flush_paragraph(paragraph: &mut Paragraph, scene: &mut Scene) {
+        if paragraph.lines.is_empty() {
+            return;
+        }
+
+        let mut blocks: Vec<DialogueBlock> = DialogueBlock::parse_paragraph_spans(&paragraph.lines)
+            .into_iter()
+            .map(|(mut block, last_line)| {
+                block.source_line = paragraph.line_numbers[last_line];
+                block
+            })
+            .collect();
+        if let Some(first) = blocks.first_mut() {
+            first.voice = paragraph.voice.take();
         }
+        scene.dialogue_blocks.extend(blocks);
+        // println!("💬 ダイアログ解析成功: {:?}", scene.dialogue_blocks.last());
+        paragraph.lines.clear();
+        paragraph.line_numbers.clear();
     }
 
     /// 独自コマンド形式をパース
     ///
+    /// 標準形式のコマンド名とパラメータに読み替え、`[...]` 形式と同じくレジストリで解釈する
+    ///
     /// # 対応形式
-    /// - `bg:backgrounds/filename.png` → Background
-    /// - `char:name:face:pos` → CharacterShow
-    fn parse_simple_command(command_str: &str) -> Option<SceneCommand> {
+    /// - `bg:backgrounds/filename.png` → `[bg storage=... time=500]`
+    /// - `char:name:face:pos` → `[chara_show name=... face=... pos=...]`
+    fn parse_simple_command(command_str: &str, registry: &SceneCommandRegistry) -> Option<SceneCommand> {
         let parts: Vec<&str> = command_str.split(':').collect();
-
-        if parts.is_empty() {
-            return None;
-        }
-
-        match parts[0] {
-            "bg" => {
-                if parts.len() >= 2 {
-                    Some(SceneCommand::Background {
-                        storage: parts[1].to_string(),
-                        time: Some(500), // デフォルト500ms
-                    })
-                } else {
-                    None
-                }
-            }
-            "char" => {
-                if parts.len() >= 4 {
-                    // char:name:face:pos形式
-                    let name = parts[1].to_string();
-                    let face = Some(parts[2].to_string());
-                    let pos = Some(crate::domain::scenario::CharacterPosition::from(parts[3]));
-
-                    Some(SceneCommand::CharacterShow { name, face, pos })
-                } else if parts.len() >= 2 {
-                    // char:name形式（最小限）
-                    let name = parts[1].to_string();
-                    Some(SceneCommand::CharacterShow {
-                        name,
-                        face: None,
-                        pos: Some(crate::domain::scenario::CharacterPosition::Center)
-                    })
-                } else {
-                    None
-                }
-            }
+        let param = |key: &str, value: &str| (key.to_string(), value.to_string());
+
+        let (command_name, params) = match parts.as_slice() {
+            ["bg", storage, ..] => ("bg", HashMap::from([param("storage", storage), param("time", "500")])), // デフォルト500ms
+            // char:name:face:pos形式
+            ["char", name, face, pos, ..] => (
+                "chara_show",
+                HashMap::from([param("name", name), param("face", face), param("pos", pos)]),
+            ),
+            // char:name形式（最小限）
+            ["char", name, ..] => ("chara_show", HashMap::from([param("name", name), param("pos", "center")])),
             _ => {
-                println!("⚠️ 未対応の独自コマンド: {}", parts[0]);
-                None
+                println!("⚠️ 未対応の独自コマンド: {}", command_str);
+                return None;
             }
-        }
+        };
+
+        registry.parse_parts(command_name, &params).ok()
     }
 
     /// シナリオの統計情報を取得（デバッグ用）
@@ -242,22 +575,21 @@ impl ScenarioLoader {
         let mut total_commands = 0;
         let mut total_dialogues = 0;
         let mut command_types = std::collections::HashMap::new();
+        let mut speaker_lines = std::collections::HashMap::new();
 
         for scene in &scenario.scenes {
             total_commands += scene.commands.len();
             total_dialogues += scene.dialogue_blocks.len();
 
             for command in &scene.commands {
-                let cmd_type = match command {
-                    SceneCommand::Background { .. } => "bg",
-                    SceneCommand::CharacterShow { .. } => "chara_show",
-                    SceneCommand::CharacterHide { .. } => "chara_hide",
-                    SceneCommand::Bgm { .. } => "bgm",
-                    SceneCommand::Se { .. } => "se",
-                    SceneCommand::Wait { .. } => "wait",
-                    SceneCommand::Choice { .. } => "choice",
-                };
-                *command_types.entry(cmd_type.to_string()).or_insert(0) += 1;
+                *command_types.entry(command.name().to_string()).or_insert(0) += 1;
+            }
+
+            // 話者ごとのセリフ数（キャラクターIDが分かればIDで集計）
+            for block in &scene.dialogue_blocks {
+                if let Some(speaker) = block.character_id.as_ref().or(block.speaker.as_ref()) {
+                    *speaker_lines.entry(speaker.clone()).or_insert(0) += 1;
+                }
             }
         }
 
@@ -267,10 +599,17 @@ impl ScenarioLoader {
             total_commands,
             total_dialogues,
             command_types,
+            speaker_lines,
         }
     }
 }
 
+/// 共有マクロファイル名（シナリオと同じディレクトリに置く）
+pub const SHARED_MACRO_FILE: &str = "macros.md";
+
+/// ファイル名も chapter 指定もない場合のセリフIDの接頭辞
+const DEFAULT_LINE_ID_PREFIX: &str = "line";
+
 /// シナリオ統計情報
 #[derive(Debug)]
 pub struct ScenarioStats {
@@ -279,11 +618,14 @@ pub struct ScenarioStats {
     pub total_commands: usize,
     pub total_dialogues: usize,
     pub command_types: std::collections::HashMap<String, usize>,
+    /// 話者ごとのセリフ数
+    pub speaker_lines: std::collections::HashMap<String, usize>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::scenario::{BackgroundCommand, CharacterPosition, CharacterShowCommand};
 
     #[test]
     fn test_parse_simple_markdown() {
@@ -315,13 +657,10 @@ mod tests {
         assert_eq!(first_scene.dialogue_blocks.len(), 2);
 
         // コマンドの確認
-        match &first_scene.commands[0] {
-            SceneCommand::Background { storage, time } => {
-                assert_eq!(storage, "forest_day.jpg");
-                assert_eq!(*time, Some(500));
-            }
-            _ => panic!("期待していた Background コマンドではありません"),
-        }
+        assert_eq!(
+            first_scene.commands[0].payload::<BackgroundCommand>(),
+            Some(&BackgroundCommand { storage: "forest_day.jpg".to_string(), time: Some(500) })
+        );
 
         // ダイアログの確認
         assert_eq!(first_scene.dialogue_blocks[0].speaker, Some("ソウマ".to_string()));
@@ -350,35 +689,197 @@ mod tests {
         assert_eq!(stats.total_dialogues, 1);
         assert_eq!(stats.command_types.get("bg"), Some(&1));
         assert_eq!(stats.command_types.get("chara_show"), Some(&1));
+        assert_eq!(stats.speaker_lines.get("ソウマ"), Some(&1));
     }
 
     #[test]
-    fn test_parse_simple_command_bg() {
-        let command = ScenarioLoader::parse_simple_command("bg:backgrounds/forest.png");
-        assert!(command.is_some());
+    fn test_parse_markdown_with_custom_command() {
+        use crate::domain::command_registry::SceneCommandHandler;
+        use crate::domain::scenario::ParseError;
+        use bevy::prelude::Commands;
+
+        struct GalleryUnlockHandler;
 
-        match command.unwrap() {
-            SceneCommand::Background { storage, time } => {
-                assert_eq!(storage, "backgrounds/forest.png");
-                assert_eq!(time, Some(500));
+        impl SceneCommandHandler for GalleryUnlockHandler {
+            fn name(&self) -> &str {
+                "gallery_unlock"
             }
-            _ => panic!("期待していたBackgroundコマンドではありません"),
+
+            fn parse(&self, params: &HashMap<String, String>) -> Result<SceneCommand, ParseError> {
+                Ok(SceneCommand::new(self.name(), params.clone()))
+            }
+
+            fn execute(&self, _command: &SceneCommand, _commands: &mut Commands) {}
         }
+
+        let content = r#"
+# 独自コマンドテスト
+
+[bg storage=test.jpg]
+[gallery_unlock cg=ending01]
+
+**ソウマ**「テスト」
+"#;
+
+        // 未登録のレジストリでは独自コマンドは読み飛ばされる
+        let scenario = ScenarioLoader::parse_markdown(content);
+        assert_eq!(scenario.scenes[0].commands.len(), 1);
+
+        let mut registry = builtin_command_registry();
+        registry.register(GalleryUnlockHandler);
+
+        let scenario = ScenarioLoader::parse_markdown_with_registry(content, &registry);
+        assert_eq!(scenario.scenes[0].commands.len(), 2);
+
+        let stats = ScenarioLoader::get_scenario_stats(&scenario);
+        assert_eq!(stats.command_types.get("gallery_unlock"), Some(&1));
+    }
+
+    #[test]
+    fn test_parse_front_matter() {
+        let content = r#"---
+chapter: chapter01
+title: "第1章 遺跡の入口"
+relationship_cap: 30
+bgm: ruins_theme.mp3
+background: ruins_entrance.png
+preload_images: [characters/01_souma_kari.png, backgrounds/black.png]
+preload_sounds:
+  - se/door_open.mp3
+  - se/footsteps.mp3
+---
+
+# 遺跡探索
+
+**ソウマ**「ここが遺跡か」
+
+---
+
+**ユズキ**「気をつけて」
+"#;
+
+        let scenario = ScenarioLoader::parse_markdown(content);
+        let metadata = &scenario.metadata;
+
+        assert!(scenario.parse_errors.is_empty());
+        assert_eq!(scenario.title, "第1章 遺跡の入口");
+        assert_eq!(metadata.chapter_id.as_deref(), Some("chapter01"));
+        assert_eq!(metadata.relationship_cap, Some(30));
+        assert_eq!(metadata.default_bgm.as_deref(), Some("ruins_theme.mp3"));
+        assert_eq!(metadata.default_background.as_deref(), Some("ruins_entrance.png"));
+        assert_eq!(metadata.preload_images.len(), 2);
+        assert_eq!(metadata.preload_sounds, vec!["se/door_open.mp3", "se/footsteps.mp3"]);
+
+        // 本文中の --- はシーン区切りのまま
+        assert_eq!(scenario.scenes.len(), 2);
+    }
+
+    #[test]
+    fn test_leading_separator_is_not_front_matter() {
+        let content = r#"---
+
+**ソウマ**「最初のシーン」
+
+---
+
+**ユズキ**「次のシーン」
+"#;
+
+        let scenario = ScenarioLoader::parse_markdown(content);
+
+        assert_eq!(scenario.metadata, ScenarioMetadata::default());
+        assert_eq!(scenario.scenes.len(), 2);
+        assert_eq!(scenario.scenes[0].dialogue_blocks[0].text, "最初のシーン");
+    }
+
+    #[test]
+    fn test_front_matter_error_location() {
+        let content = "---\nchapter: chapter02\nrelationship_cap: many\n---\n\n地の文\n";
+
+        let scenario = ScenarioLoader::parse_markdown(content);
+
+        assert_eq!(scenario.metadata.chapter_id.as_deref(), Some("chapter02"));
+        assert_eq!(scenario.parse_errors.len(), 1);
+        assert_eq!(scenario.parse_errors[0].line_number, 3);
+    }
+
+    #[test]
+    fn test_parse_markdown_with_macros() {
+        let content = r#"
+# マクロテスト
+
+[macro name=enter_pair]
+[chara_show name=%left face=normal pos=left]
+[chara_show name=%right face=normal pos=right]
+[endmacro]
+
+[enter_pair left=souma right=yuzuki]
+
+**ソウマ**「行こう」
+
+[enter_pair left=souma]
+"#;
+
+        let scenario = ScenarioLoader::parse_markdown(content);
+
+        assert_eq!(scenario.scenes.len(), 1);
+        assert_eq!(scenario.scenes[0].commands.len(), 2);
+        assert_eq!(scenario.scenes[0].commands[1].payload::<CharacterShowCommand>(), Some(&CharacterShowCommand {
+            name: "yuzuki".to_string(),
+            face: Some("normal".to_string()),
+            pos: Some(CharacterPosition::Right),
+        }));
+
+        // パラメータ不足のエラーは呼び出し行（13行目）に対応付けられる
+        assert_eq!(scenario.parse_errors.len(), 1);
+        assert_eq!(scenario.parse_errors[0].line_number, 13);
+        assert!(scenario.parse_errors[0].message.contains("enter_pair"));
+    }
+
+    #[test]
+    fn test_shared_macros_are_overridden_by_local() {
+        let (shared, _, _) = MacroLibrary::extract_definitions(
+            "[macro name=intro]\n[bg storage=shared.png]\n[endmacro]\n[macro name=outro]\n[bg storage=black.png]\n[endmacro]\n"
+        );
+        let content = "[macro name=intro]\n[bg storage=local.png]\n[endmacro]\n[intro]\n[outro]\n";
+
+        let scenario = ScenarioLoader::parse_markdown_with_macros(
+            content,
+            &builtin_command_registry(),
+            &shared,
+        );
+
+        let storages: Vec<&str> = scenario.scenes[0].commands.iter()
+            .filter_map(|command| command.payload::<BackgroundCommand>())
+            .map(|background| background.storage.as_str())
+            .collect();
+        assert_eq!(storages, vec!["local.png", "black.png"]);
+    }
+
+    #[test]
+    fn test_parse_simple_command_bg() {
+        let command = ScenarioLoader::parse_simple_command("bg:backgrounds/forest.png", &builtin_command_registry());
+        assert!(command.is_some());
+
+        assert_eq!(
+            command.unwrap().payload::<BackgroundCommand>(),
+            Some(&BackgroundCommand { storage: "backgrounds/forest.png".to_string(), time: Some(500) })
+        );
     }
 
     #[test]
     fn test_parse_simple_command_char() {
-        let command = ScenarioLoader::parse_simple_command("char:souma:normal:left");
+        let command = ScenarioLoader::parse_simple_command("char:souma:normal:left", &builtin_command_registry());
         assert!(command.is_some());
 
-        match command.unwrap() {
-            SceneCommand::CharacterShow { name, face, pos } => {
-                assert_eq!(name, "souma");
-                assert_eq!(face, Some("normal".to_string()));
-                assert_eq!(pos, Some(crate::domain::scenario::CharacterPosition::Left));
-            }
-            _ => panic!("期待していたCharacterShowコマンドではありません"),
-        }
+        assert_eq!(
+            command.unwrap().payload::<CharacterShowCommand>(),
+            Some(&CharacterShowCommand {
+                name: "souma".to_string(),
+                face: Some("normal".to_string()),
+                pos: Some(CharacterPosition::Left),
+            })
+        );
     }
 
     #[test]
@@ -409,16 +910,90 @@ mod tests {
         assert_eq!(first_scene.dialogue_blocks.len(), 1);
 
         // コマンド確認
-        match &first_scene.commands[0] {
-            SceneCommand::Background { storage, time } => {
-                assert_eq!(storage, "backgrounds/test.png");
-                assert_eq!(*time, Some(500));
-            }
-            _ => panic!("期待していた Background コマンドではありません"),
-        }
+        assert_eq!(
+            first_scene.commands[0].payload::<BackgroundCommand>(),
+            Some(&BackgroundCommand { storage: "backgrounds/test.png".to_string(), time: Some(500) })
+        );
 
         // ダイアログ確認（コロン形式）
         assert_eq!(first_scene.dialogue_blocks[0].speaker, Some("ソウマ".to_string()));
         assert_eq!(first_scene.dialogue_blocks[0].text, "これは独自形式のテストです。");
     }
+
+    #[test]
+    fn test_parse_multiline_dialogue() {
+        let content = "**ソウマ**「長い話になるけど、\n聞いてくれるかな。」\n\n一行目  \n二行目\n[bg storage=ruins.jpg]\n次の段落\n";
+
+        let scenario = ScenarioLoader::parse_markdown(content);
+        let blocks = &scenario.scenes[0].dialogue_blocks;
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].speaker, Some("ソウマ".to_string()));
+        assert_eq!(blocks[0].text, "長い話になるけど、聞いてくれるかな。");
+        assert_eq!(blocks[1].text, "一行目\n二行目");
+        assert_eq!(blocks[2].text, "次の段落");
+        assert_eq!(scenario.scenes[0].commands.len(), 1);
+    }
+
+    #[test]
+    fn test_voice_is_attached_to_next_dialogue() {
+        let content = "[voice storage=souma_001.ogg]\n**ソウマ**「行こう」\n\n**ユズキ**「うん」\n";
+
+        let scenario = ScenarioLoader::parse_markdown(content);
+        let scene = &scenario.scenes[0];
+
+        assert!(scene.commands.is_empty());
+        assert_eq!(scene.dialogue_blocks[0].voice, Some("souma_001.ogg".to_string()));
+        assert_eq!(scene.dialogue_blocks[1].voice, None);
+    }
+
+    #[test]
+    fn test_explicit_line_ids_and_duplicates() {
+        let content = "**ソウマ**「行こう」{#go}\n\n地の文。\n\n**ユズキ**「うん」{#go}\n";
+
+        let scenario = ScenarioLoader::parse_markdown(content);
+        let blocks = &scenario.scenes[0].dialogue_blocks;
+
+        assert_eq!(blocks[0].line_id, "go");
+        assert_eq!(blocks[0].text, "行こう");
+        assert_eq!(blocks[0].source_line, Some(1));
+        assert!(blocks[1].line_id.starts_with("line_"));
+        assert_eq!(blocks[2].source_line, Some(5));
+        assert_eq!(scenario.parse_errors.len(), 1);
+        assert_eq!(scenario.parse_errors[0].line_number, 5);
+    }
+
+    #[test]
+    fn test_insert_missing_line_ids() {
+        let content = "# 第一章\r\n\r\n**ソウマ**「行こう。\r\nみんな」\r\n\r\n**ユズキ**「うん」{#yuzuki_ok}\r\n";
+        let scenario = ScenarioLoader::parse_markdown(content);
+        let souma_id = scenario.scenes[0].dialogue_blocks[0].line_id.clone();
+
+        let (updated, count) = ScenarioLoader::insert_missing_line_ids(content, &scenario);
+        assert_eq!(count, 1);
+        assert!(updated.contains(&format!("みんな」 {{#{}}}\r\n", souma_id)));
+
+        // 書き込み後は同じIDで読み込まれ、再実行しても何も追加されない
+        let rewritten = ScenarioLoader::parse_markdown(&updated);
+        assert_eq!(rewritten.scenes[0].dialogue_blocks[0].line_id, souma_id);
+        assert_eq!(rewritten.scenes[0].dialogue_blocks[0].text, "行こう。みんな");
+        assert_eq!(ScenarioLoader::insert_missing_line_ids(&updated, &rewritten).1, 0);
+    }
+
+    #[test]
+    fn test_scenario_paths_include_subdirectories() {
+        let dir = std::env::temp_dir().join(format!("negaboku_scenarios_{}", std::process::id()));
+        let nested = dir.join("endings");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.join("chapter1.md"), "# 第一章\n").unwrap();
+        fs::write(dir.join(SHARED_MACRO_FILE), "").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        fs::write(nested.join("ending_yuzuki.md"), "# エンディング\n").unwrap();
+        fs::write(nested.join(SHARED_MACRO_FILE), "").unwrap();
+
+        let paths = ScenarioLoader::scenario_paths(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(paths, vec![dir.join("chapter1.md"), nested.join("ending_yuzuki.md")]);
+    }
 }
@@ -0,0 +1,570 @@
+//! 翻訳ファイル - ロケール別翻訳の読み込みと PO 形式での書き出し・取り込み
+//!
+//! # 責務
+//! - ロケール別翻訳ファイル（`assets/locales/{locale}/`）の読み込み
+//! - 翻訳者向け PO ファイルの書き出し（シナリオのセリフ・UI文字列・話者名）
+//! - 翻訳済み PO ファイルの取り込み
+//!
+//! 翻訳ファイルの配置:
+//! - `assets/locales/{locale}/ui.json` : UI文字列・話者名（キー → 翻訳）
+//! - `assets/locales/{locale}/scenarios/{シナリオ名}.json` : セリフID → 原文と翻訳
+//!
+//! 翻訳者はマークダウンを直接編集せず、`export-po` で書き出した PO ファイルを翻訳し、
+//! `import-po` で取り込む。
+
+use crate::domain::command_registry::SceneCommandRegistry;
+use crate::domain::character::CharacterRegistry;
+use crate::domain::localization::{Localization, SOURCE_LOCALE, SOURCE_UI_STRINGS, SPEAKER_KEY_PREFIX};
+use crate::domain::scenario::ScenarioFile;
+use crate::infrastructure::scenario_loader::ScenarioLoader;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// ロケール別翻訳ファイルの配置ディレクトリ
+pub const LOCALE_DIRECTORY: &str = "assets/locales";
+
+/// UI文字列テーブルのファイル名（拡張子なし）
+const UI_TABLE_NAME: &str = "ui";
+
+/// シナリオ翻訳ファイルのディレクトリ名
+const SCENARIO_TABLE_DIRECTORY: &str = "scenarios";
+
+/// シナリオの配置ディレクトリ
+const DEFAULT_SCENARIO_DIR: &str = "assets/scenarios";
+
+/// PO ファイルの書き出し先・取り込み元ディレクトリ
+const DEFAULT_PO_DIR: &str = "translations";
+
+/// 1セリフ分の翻訳（原文が変わったかを判定できるよう原文も保存する）
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LineTranslation {
+    pub source: String,
+    pub text: String,
+}
+
+/// 表示言語の翻訳を読み込む（日本語、または翻訳ファイルがない場合は翻訳なし）
+pub fn load_localization(locale_dir: &Path, locale: &str) -> Localization {
+    if locale == SOURCE_LOCALE {
+        return Localization::default();
+    }
+
+    let locale_path = locale_dir.join(locale);
+    if !locale_path.is_dir() {
+        eprintln!("⚠️ 翻訳が見つかりません: {:?}（日本語で表示します）", locale_path);
+    }
+
+    let ui_strings: HashMap<String, String> = read_ui_table(&locale_path).into_iter().collect();
+    let line_texts: HashMap<String, String> = read_scenario_tables(&locale_path)
+        .into_values()
+        .flatten()
+        .map(|(line_id, translation)| (line_id, translation.text))
+        .collect();
+
+    let localization = Localization::new(locale, ui_strings, line_texts);
+    let (ui_count, line_count) = localization.translated_counts();
+    println!("🌐 表示言語: {} (UI {} 件, セリフ {} 件)", localization.locale, ui_count, line_count);
+    localization
+}
+
+/// 選択できる表示言語（日本語と、翻訳ディレクトリがあるロケール）
+pub fn available_locales(locale_dir: &Path) -> Vec<String> {
+    let mut locales: Vec<String> = fs::read_dir(locale_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_dir())
+                .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+                .filter(|locale| locale != SOURCE_LOCALE)
+                .collect()
+        })
+        .unwrap_or_default();
+    locales.sort();
+    locales.insert(0, SOURCE_LOCALE.to_string());
+    locales
+}
+
+/// UI文字列テーブルを読み込む（なければ空）
+fn read_ui_table(locale_path: &Path) -> BTreeMap<String, String> {
+    read_json(&locale_path.join(format!("{}.json", UI_TABLE_NAME))).unwrap_or_default()
+}
+
+/// シナリオ翻訳をすべて読み込む（シナリオ名 → セリフID → 翻訳）
+fn read_scenario_tables(locale_path: &Path) -> BTreeMap<String, BTreeMap<String, LineTranslation>> {
+    let Ok(entries) = fs::read_dir(locale_path.join(SCENARIO_TABLE_DIRECTORY)) else {
+        return BTreeMap::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            Some((name, read_json(&path)?))
+        })
+        .collect()
+}
+
+/// JSONファイルを読み込む（存在しない・壊れている場合は None）
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Option<T> {
+    let content = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            eprintln!("⚠️ 翻訳ファイルを読み込めません {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// JSONファイルを書き出す
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("ディレクトリを作成できません {:?}: {}", parent, e))?;
+    }
+    let content = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(path, content + "\n").map_err(|e| format!("書き込めません {:?}: {}", path, e))
+}
+
+/// PO ファイルの1項目
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoEntry {
+    /// msgctxt（セリフID・UI文字列のキー）
+    pub context: String,
+    /// msgid（日本語の原文）
+    pub source: String,
+    /// msgstr（翻訳、未翻訳なら空）
+    pub translation: String,
+    /// 翻訳者向けの説明（`#.`）
+    pub comments: Vec<String>,
+    /// 原文の位置（`#:`）
+    pub references: Vec<String>,
+    /// 原文が変わり、翻訳の見直しが必要か（`#, fuzzy`）
+    pub fuzzy: bool,
+}
+
+/// PO ファイルを文字列に整形
+pub fn render_po(locale: &str, entries: &[PoEntry]) -> String {
+    let mut output = String::new();
+    output.push_str("msgid \"\"\nmsgstr \"\"\n");
+    output.push_str("\"Content-Type: text/plain; charset=UTF-8\\n\"\n");
+    output.push_str(&format!("\"Language: {}\\n\"\n", escape_po(locale)));
+
+    for entry in entries {
+        output.push('\n');
+        for comment in &entry.comments {
+            output.push_str(&format!("#. {}\n", comment));
+        }
+        for reference in &entry.references {
+            output.push_str(&format!("#: {}\n", reference));
+        }
+        if entry.fuzzy {
+            output.push_str("#, fuzzy\n");
+        }
+        output.push_str(&format!("msgctxt \"{}\"\n", escape_po(&entry.context)));
+        output.push_str(&format!("msgid \"{}\"\n", escape_po(&entry.source)));
+        output.push_str(&format!("msgstr \"{}\"\n", escape_po(&entry.translation)));
+    }
+
+    output
+}
+
+/// PO ファイルを解析（ヘッダー項目は除く）
+pub fn parse_po(content: &str) -> Result<Vec<PoEntry>, String> {
+    #[derive(PartialEq)]
+    enum Field {
+        None,
+        Context,
+        Source,
+        Translation,
+    }
+
+    let mut entries = Vec::new();
+    let mut entry = PoEntry::default();
+    let mut field = Field::None;
+    let mut has_content = false;
+
+    let mut finish = |entry: &mut PoEntry, has_content: &mut bool| {
+        if *has_content && !entry.source.is_empty() {
+            entries.push(std::mem::take(entry));
+        }
+        *entry = PoEntry::default();
+        *has_content = false;
+    };
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line = raw_line.trim();
+        let line_error = |message: &str| format!("{}行目: {}", index + 1, message);
+
+        // 新しい項目の開始（翻訳の後にコメントや msgctxt / msgid が来た）
+        let starts_entry = line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("msgctxt ")
+            || (line.starts_with("msgid ") && field != Field::Context);
+        if starts_entry && field == Field::Translation {
+            finish(&mut entry, &mut has_content);
+            field = Field::None;
+        }
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(flags) = line.strip_prefix("#,") {
+            entry.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+        } else if let Some(comment) = line.strip_prefix("#.") {
+            entry.comments.push(comment.trim().to_string());
+        } else if let Some(reference) = line.strip_prefix("#:") {
+            entry.references.push(reference.trim().to_string());
+        } else if line.starts_with('#') {
+            // 翻訳者コメントなどは読み飛ばす
+        } else if let Some(value) = line.strip_prefix("msgctxt ") {
+            entry.context = unquote_po(value).ok_or_else(|| line_error("msgctxt の書式が不正です"))?;
+            field = Field::Context;
+        } else if let Some(value) = line.strip_prefix("msgid ") {
+            entry.source = unquote_po(value).ok_or_else(|| line_error("msgid の書式が不正です"))?;
+            field = Field::Source;
+            has_content = true;
+        } else if let Some(value) = line.strip_prefix("msgstr ") {
+            entry.translation = unquote_po(value).ok_or_else(|| line_error("msgstr の書式が不正です"))?;
+            field = Field::Translation;
+        } else if line.starts_with('"') {
+            let value = unquote_po(line).ok_or_else(|| line_error("文字列の書式が不正です"))?;
+            match field {
+                Field::Context => entry.context.push_str(&value),
+                Field::Source => entry.source.push_str(&value),
+                Field::Translation => entry.translation.push_str(&value),
+                Field::None => return Err(line_error("文字列の続きに対応する項目がありません")),
+            }
+        } else {
+            return Err(line_error(&format!("未対応の行です: {}", line)));
+        }
+    }
+    finish(&mut entry, &mut has_content);
+
+    Ok(entries)
+}
+
+/// PO 文字列用のエスケープ
+fn escape_po(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+}
+
+/// `"..."` を外してエスケープを戻す
+fn unquote_po(value: &str) -> Option<String> {
+    let inner = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    let mut text = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => text.push('\n'),
+            't' => text.push('\t'),
+            other => text.push(other),
+        }
+    }
+
+    Some(text)
+}
+
+/// シナリオのセリフを PO 項目にする（既存の翻訳を引き継ぎ、原文が変わったものは要確認にする）
+pub fn scenario_po_entries(
+    scenario: &ScenarioFile,
+    scenario_name: &str,
+    existing: &BTreeMap<String, LineTranslation>,
+) -> Vec<PoEntry> {
+    scenario
+        .scenes
+        .iter()
+        .flat_map(|scene| scene.dialogue_blocks.iter())
+        .filter(|block| !block.text.is_empty())
+        .map(|block| {
+            let previous = existing.get(&block.line_id);
+            PoEntry {
+                context: block.line_id.clone(),
+                source: block.text.clone(),
+                translation: previous.map(|t| t.text.clone()).unwrap_or_default(),
+                comments: block.speaker.iter().map(|speaker| format!("話者: {}", speaker)).collect(),
+                references: block
+                    .source_line
+                    .map(|line| format!("{}:{}", scenario_name, line))
+                    .into_iter()
+                    .collect(),
+                fuzzy: previous.is_some_and(|t| t.source != block.text),
+            }
+        })
+        .collect()
+}
+
+/// UI文字列と話者名を PO 項目にする
+pub fn ui_po_entries(speakers: &[String], existing: &BTreeMap<String, String>) -> Vec<PoEntry> {
+    let ui_strings = SOURCE_UI_STRINGS
+        .iter()
+        .map(|(key, text)| (key.to_string(), text.to_string()));
+    let speaker_names = speakers
+        .iter()
+        .map(|speaker| (format!("{}{}", SPEAKER_KEY_PREFIX, speaker), speaker.clone()));
+
+    ui_strings
+        .chain(speaker_names)
+        .map(|(key, source)| PoEntry {
+            translation: existing.get(&key).cloned().unwrap_or_default(),
+            context: key,
+            source,
+            ..PoEntry::default()
+        })
+        .collect()
+}
+
+/// 翻訳ツールの設定
+#[derive(Debug, Clone)]
+pub struct TranslationOptions {
+    pub locale: String,
+    pub scenario_dir: PathBuf,
+    pub locale_dir: PathBuf,
+    /// PO ファイルの書き出し先・取り込み元（`{po_dir}/{locale}/`）
+    pub po_dir: PathBuf,
+}
+
+impl TranslationOptions {
+    /// コマンドライン引数（サブコマンド名より後ろ）から設定を作る
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut locale = None;
+        let mut options = Self {
+            locale: String::new(),
+            scenario_dir: PathBuf::from(DEFAULT_SCENARIO_DIR),
+            locale_dir: PathBuf::from(LOCALE_DIRECTORY),
+            po_dir: PathBuf::from(DEFAULT_PO_DIR),
+        };
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            let mut value = || iter.next().cloned().ok_or_else(|| format!("{} の値がありません", arg));
+            match arg.as_str() {
+                "--locale" => locale = Some(value()?),
+                "--scenarios" => options.scenario_dir = PathBuf::from(value()?),
+                "--locales" => options.locale_dir = PathBuf::from(value()?),
+                "--po" => options.po_dir = PathBuf::from(value()?),
+                other => return Err(format!("不明な引数です: {}", other)),
+            }
+        }
+
+        options.locale = locale.ok_or("--locale で翻訳先の言語を指定してください")?;
+        if options.locale == SOURCE_LOCALE {
+            return Err(format!("{} は原文の言語です", SOURCE_LOCALE));
+        }
+        Ok(options)
+    }
+
+    fn po_locale_dir(&self) -> PathBuf {
+        self.po_dir.join(&self.locale)
+    }
+}
+
+/// 全シナリオと UI文字列の PO ファイルを書き出す（既存の翻訳は引き継ぐ）
+///
+/// コマンド行は `command_registry` に登録されたハンドラで解釈する。書き出したファイルのパスを返す
+pub fn export_po(options: &TranslationOptions, command_registry: &SceneCommandRegistry) -> Result<Vec<PathBuf>, String> {
+    let mut character_registry = CharacterRegistry::new();
+    character_registry.register_default_characters();
+
+    let locale_path = options.locale_dir.join(&options.locale);
+    let existing_lines = read_scenario_tables(&locale_path);
+    let output_dir = options.po_locale_dir();
+    fs::create_dir_all(&output_dir).map_err(|e| format!("出力先を作成できません {:?}: {}", output_dir, e))?;
+
+    let scenario_paths = ScenarioLoader::scenario_paths(&options.scenario_dir)
+        .map_err(|e| format!("シナリオディレクトリを開けません {:?}: {}", options.scenario_dir, e))?;
+
+    let mut written = Vec::new();
+    let mut speakers: Vec<String> = Vec::new();
+    for path in &scenario_paths {
+        let mut scenario = ScenarioLoader::load_from_file(path, command_registry)
+            .map_err(|e| format!("シナリオを読み込めません {:?}: {}", path, e))?;
+        scenario.resolve_speakers(&character_registry);
+
+        for speaker in scenario.scenes.iter().flat_map(|scene| &scene.dialogue_blocks).filter_map(|b| b.speaker.as_ref()) {
+            if !speakers.contains(speaker) {
+                speakers.push(speaker.clone());
+            }
+        }
+
+        let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let empty = BTreeMap::new();
+        let entries = scenario_po_entries(&scenario, file_name, existing_lines.get(stem).unwrap_or(&empty));
+
+        let po_path = output_dir.join(format!("{}.po", stem));
+        fs::write(&po_path, render_po(&options.locale, &entries))
+            .map_err(|e| format!("書き込めません {:?}: {}", po_path, e))?;
+        let untranslated = entries.iter().filter(|entry| entry.translation.is_empty()).count();
+        println!("🌐 {}: {} 項目（未翻訳 {}） -> {:?}", file_name, entries.len(), untranslated, po_path);
+        written.push(po_path);
+    }
+
+    let ui_entries = ui_po_entries(&speakers, &read_ui_table(&locale_path));
+    let ui_path = output_dir.join(format!("{}.po", UI_TABLE_NAME));
+    fs::write(&ui_path, render_po(&options.locale, &ui_entries))
+        .map_err(|e| format!("書き込めません {:?}: {}", ui_path, e))?;
+    println!("🌐 UI文字列: {} 項目 -> {:?}", ui_entries.len(), ui_path);
+    written.push(ui_path);
+
+    Ok(written)
+}
+
+/// 翻訳済みの PO ファイルを取り込み、ロケール別翻訳ファイルを更新する
+///
+/// 未翻訳・要確認（fuzzy）の項目は取り込まない。取り込んだ項目数を返す
+pub fn import_po(options: &TranslationOptions) -> Result<usize, String> {
+    let input_dir = options.po_locale_dir();
+    let mut po_paths: Vec<PathBuf> = fs::read_dir(&input_dir)
+        .map_err(|e| format!("PO ディレクトリを開けません {:?}: {}", input_dir, e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "po"))
+        .collect();
+    po_paths.sort();
+
+    let locale_path = options.locale_dir.join(&options.locale);
+    let mut imported = 0;
+
+    for po_path in &po_paths {
+        let content = fs::read_to_string(po_path).map_err(|e| format!("読み込めません {:?}: {}", po_path, e))?;
+        let entries: Vec<PoEntry> = parse_po(&content)
+            .map_err(|e| format!("{:?}: {}", po_path, e))?
+            .into_iter()
+            .filter(|entry| !entry.translation.is_empty() && !entry.fuzzy)
+            .collect();
+
+        let stem = po_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        if stem == UI_TABLE_NAME {
+            let table: BTreeMap<String, String> =
+                entries.iter().map(|entry| (entry.context.clone(), entry.translation.clone())).collect();
+            write_json(&locale_path.join(format!("{}.json", UI_TABLE_NAME)), &table)?;
+        } else {
+            let table: BTreeMap<String, LineTranslation> = entries
+                .iter()
+                .map(|entry| {
+                    let translation = LineTranslation { source: entry.source.clone(), text: entry.translation.clone() };
+                    (entry.context.clone(), translation)
+                })
+                .collect();
+            write_json(&locale_path.join(SCENARIO_TABLE_DIRECTORY).join(format!("{}.json", stem)), &table)?;
+        }
+
+        println!("📥 {:?}: {} 項目を取り込み", po_path, entries.len());
+        imported += entries.len();
+    }
+
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::scenario::DialogueBlock;
+
+    #[test]
+    fn test_available_locales() {
+        assert_eq!(available_locales(Path::new(LOCALE_DIRECTORY)), vec!["ja".to_string(), "en".to_string()]);
+        assert_eq!(available_locales(Path::new("no/such/locales")), vec!["ja".to_string()]);
+    }
+
+    #[test]
+    fn test_po_round_trip() {
+        let entries = vec![PoEntry {
+            context: "ch1_go".to_string(),
+            source: "行こう。\n「みんな」".to_string(),
+            translation: "Let's go.\n\"Everyone\"".to_string(),
+            comments: vec!["話者: ソウマ".to_string()],
+            references: vec!["chapter01.md:12".to_string()],
+            fuzzy: true,
+        }];
+
+        let content = render_po("en", &entries);
+        assert!(content.contains("msgctxt \"ch1_go\"\n"));
+        assert!(content.contains("#, fuzzy\n"));
+        assert_eq!(parse_po(&content).unwrap(), entries);
+    }
+
+    #[test]
+    fn test_parse_po_multiline_strings() {
+        let content = "msgid \"\"\nmsgstr \"\"\n\"Language: en\\n\"\n\n\
+                       msgctxt \"menu.new_game\"\nmsgid \"はじめ\"\n\"から\"\nmsgstr \"\"\n\"New \"\n\"Game\"\n\
+                       msgctxt \"menu.exit\"\nmsgid \"ゲーム終了\"\nmsgstr \"\"\n";
+        let entries = parse_po(content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].source, "はじめから");
+        assert_eq!(entries[0].translation, "New Game");
+        assert_eq!(entries[1].context, "menu.exit");
+        assert!(entries[1].translation.is_empty());
+
+        assert!(parse_po("msgid \"未閉じ\nmsgstr \"\"").is_err());
+    }
+
+    #[test]
+    fn test_scenario_po_entries_mark_changed_source() {
+        let scenario = ScenarioLoader::parse_markdown("**ソウマ**「行こう」{#go}\n\n**ユズキ**「うん」{#ok}\n");
+        let existing = BTreeMap::from([
+            ("go".to_string(), LineTranslation { source: "行くぞ".to_string(), text: "Come on".to_string() }),
+            ("ok".to_string(), LineTranslation { source: "うん".to_string(), text: "Yeah".to_string() }),
+        ]);
+
+        let entries = scenario_po_entries(&scenario, "chapter01.md", &existing);
+
+        assert_eq!(entries[0].context, "go");
+        assert_eq!(entries[0].references, vec!["chapter01.md:1".to_string()]);
+        assert_eq!(entries[0].comments, vec!["話者: ソウマ".to_string()]);
+        assert!(entries[0].fuzzy);
+        assert_eq!(entries[1].translation, "Yeah");
+        assert!(!entries[1].fuzzy);
+    }
+
+    #[test]
+    fn test_import_po_and_load_localization() {
+        let root = std::env::temp_dir().join(format!("negaboku_translation_{}", std::process::id()));
+        let options = TranslationOptions {
+            locale: "en".to_string(),
+            scenario_dir: root.join("scenarios"),
+            locale_dir: root.join("locales"),
+            po_dir: root.join("po"),
+        };
+        fs::create_dir_all(options.po_locale_dir()).unwrap();
+        fs::write(
+            options.po_locale_dir().join("chapter01.po"),
+            "msgctxt \"go\"\nmsgid \"行こう\"\nmsgstr \"Let's go\"\n\n#, fuzzy\nmsgctxt \"ok\"\nmsgid \"うん\"\nmsgstr \"Yeah\"\n",
+        )
+        .unwrap();
+        fs::write(options.po_locale_dir().join("ui.po"), "msgctxt \"menu.exit\"\nmsgid \"ゲーム終了\"\nmsgstr \"Quit\"\n").unwrap();
+
+        // 要確認（fuzzy）の項目は取り込まない
+        assert_eq!(import_po(&options).unwrap(), 2);
+
+        let localization = load_localization(&options.locale_dir, "en");
+        let translated = DialogueBlock::parse("行こう{#go}").unwrap();
+        let fuzzy = DialogueBlock::parse("うん{#ok}").unwrap();
+        assert_eq!(localization.ui("menu.exit"), "Quit");
+        assert_eq!(localization.line_text(&translated), "Let's go");
+        assert_eq!(localization.line_text(&fuzzy), "うん");
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_ui_po_entries_include_speakers() {
+        let existing = BTreeMap::from([("menu.exit".to_string(), "Quit".to_string())]);
+        let entries = ui_po_entries(&["ソウマ".to_string()], &existing);
+
+        assert_eq!(entries.len(), SOURCE_UI_STRINGS.len() + 1);
+        assert!(entries.iter().any(|entry| entry.context == "menu.exit" && entry.translation == "Quit"));
+        assert_eq!(entries.last().unwrap().context, "speaker.ソウマ");
+    }
+}
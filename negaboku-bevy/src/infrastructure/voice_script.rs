@@ -0,0 +1,327 @@
+//! ボイス台本書き出し - 収録用のキャラクター別台本の生成
+//!
+//! # 責務
+//! - 全シナリオの読み込みとセリフの収集（シーン文脈付き）
+//! - キャラクター別の CSV/TSV 台本の書き出し
+//! - 期待するボイスファイル名と収録済みかどうかの確認
+//!
+//! `negaboku-bevy export-voice-script [--format csv|tsv] [--out DIR]` で実行する。
+
+use crate::domain::command_registry::SceneCommandRegistry;
+use crate::domain::character::CharacterRegistry;
+use crate::domain::scenario::{DialogueBlock, ScenarioFile};
+use crate::infrastructure::scenario_loader::ScenarioLoader;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// サブコマンド名
+pub const SUBCOMMAND: &str = "export-voice-script";
+
+/// シナリオの配置ディレクトリ
+const DEFAULT_SCENARIO_DIR: &str = "assets/scenarios";
+
+/// 収録済みボイスの配置ディレクトリ
+const DEFAULT_VOICE_DIR: &str = "assets/sounds/voice";
+
+/// 台本の出力先ディレクトリ
+const DEFAULT_OUTPUT_DIR: &str = "voice_scripts";
+
+/// 台本の列見出し
+const HEADER: [&str; 10] = [
+    "line_id", "scenario", "scene", "title", "speaker", "text", "previous_line", "voice_file", "recorded", "character",
+];
+
+/// 台本の書き出し形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptFormat {
+    Csv,
+    Tsv,
+}
+
+impl ScriptFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" => Some(Self::Tsv),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+        }
+    }
+
+    /// 1フィールド分の文字列をエスケープ
+    fn escape(&self, field: &str) -> String {
+        match self {
+            Self::Csv if field.contains([',', '"', '\n', '\r']) => format!("\"{}\"", field.replace('"', "\"\"")),
+            Self::Csv => field.to_string(),
+            Self::Tsv => field
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r"),
+        }
+    }
+
+    fn separator(&self) -> char {
+        match self {
+            Self::Csv => ',',
+            Self::Tsv => '\t',
+        }
+    }
+}
+
+/// 台本の1行（収録対象のセリフ1つ）
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceScriptLine {
+    pub line_id: String,
+    /// シナリオファイル名
+    pub scenario: String,
+    /// シーン番号（1始まり）
+    pub scene: usize,
+    /// シナリオのタイトル
+    pub title: String,
+    /// 表示上の話者名（`？？？` など伏せ名を含む）
+    pub speaker: String,
+    /// 台本のまとめ先（キャラクターID、未登録なら話者名）
+    pub character: String,
+    pub text: String,
+    /// 同じシーンの直前のセリフ・地の文
+    pub previous_line: String,
+    /// 期待するボイスファイル名（ボイスディレクトリからの相対パス）
+    pub voice_file: String,
+    /// ボイスファイルが既に存在するか
+    pub recorded: bool,
+}
+
+impl VoiceScriptLine {
+    fn fields(&self) -> [String; 10] {
+        [
+            self.line_id.clone(),
+            self.scenario.clone(),
+            self.scene.to_string(),
+            self.title.clone(),
+            self.speaker.clone(),
+            self.text.clone(),
+            self.previous_line.clone(),
+            self.voice_file.clone(),
+            if self.recorded { "yes" } else { "no" }.to_string(),
+            self.character.clone(),
+        ]
+    }
+}
+
+/// シナリオから話者付きのセリフを収集する
+///
+/// 地の文は収録対象に含めず、直前の文脈としてだけ使う
+pub fn collect_lines(scenario: &ScenarioFile, scenario_name: &str, voice_dir: &Path) -> Vec<VoiceScriptLine> {
+    let mut lines = Vec::new();
+
+    for (scene_index, scene) in scenario.scenes.iter().enumerate() {
+        let mut previous: Option<&DialogueBlock> = None;
+
+        for block in &scene.dialogue_blocks {
+            if let Some(speaker) = &block.speaker {
+                let character = block.character_id.clone().unwrap_or_else(|| speaker.clone());
+                let voice_file = block
+                    .expected_voice_file()
+                    .unwrap_or_else(|| format!("{}/{}.ogg", character, block.line_id));
+
+                lines.push(VoiceScriptLine {
+                    line_id: block.line_id.clone(),
+                    scenario: scenario_name.to_string(),
+                    scene: scene_index + 1,
+                    title: scenario.title.clone(),
+                    speaker: speaker.clone(),
+                    character,
+                    text: block.text.clone(),
+                    previous_line: previous.map(context_text).unwrap_or_default(),
+                    recorded: voice_dir.join(&voice_file).is_file(),
+                    voice_file,
+                });
+            }
+            previous = Some(block);
+        }
+    }
+
+    lines
+}
+
+/// 文脈表示用の1行（`話者「本文」`、地の文はそのまま）
+fn context_text(block: &DialogueBlock) -> String {
+    match &block.speaker {
+        Some(speaker) => format!("{}「{}」", speaker, block.text),
+        None => block.text.clone(),
+    }
+}
+
+/// キャラクターごとにセリフをまとめる
+pub fn group_by_character(lines: Vec<VoiceScriptLine>) -> BTreeMap<String, Vec<VoiceScriptLine>> {
+    let mut groups: BTreeMap<String, Vec<VoiceScriptLine>> = BTreeMap::new();
+    for line in lines {
+        groups.entry(line.character.clone()).or_default().push(line);
+    }
+    groups
+}
+
+/// 台本を文字列に整形（見出し行付き）
+pub fn render(lines: &[VoiceScriptLine], format: ScriptFormat) -> String {
+    let row = |fields: &[String]| {
+        let escaped: Vec<String> = fields.iter().map(|field| format.escape(field)).collect();
+        escaped.join(&format.separator().to_string())
+    };
+
+    let header: Vec<String> = HEADER.iter().map(|name| name.to_string()).collect();
+    let mut output = row(&header);
+    output.push('\n');
+    for line in lines {
+        output.push_str(&row(&line.fields()));
+        output.push('\n');
+    }
+    output
+}
+
+/// 書き出しの設定
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub scenario_dir: PathBuf,
+    pub voice_dir: PathBuf,
+    pub output_dir: PathBuf,
+    pub format: ScriptFormat,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            scenario_dir: PathBuf::from(DEFAULT_SCENARIO_DIR),
+            voice_dir: PathBuf::from(DEFAULT_VOICE_DIR),
+            output_dir: PathBuf::from(DEFAULT_OUTPUT_DIR),
+            format: ScriptFormat::Csv,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// コマンドライン引数（サブコマンド名より後ろ）から設定を作る
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            let mut value = || iter.next().cloned().ok_or_else(|| format!("{} の値がありません", arg));
+            match arg.as_str() {
+                "--format" => {
+                    let format = value()?;
+                    options.format = ScriptFormat::parse(&format)
+                        .ok_or_else(|| format!("未対応の形式です: {}（csv / tsv）", format))?;
+                }
+                "--out" => options.output_dir = PathBuf::from(value()?),
+                "--scenarios" => options.scenario_dir = PathBuf::from(value()?),
+                "--voices" => options.voice_dir = PathBuf::from(value()?),
+                other => return Err(format!("不明な引数です: {}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// 全シナリオを読み込み、キャラクター別の台本ファイルを書き出す
+///
+/// コマンド行は `command_registry` に登録されたハンドラで解釈する。書き出したファイルのパスを返す
+pub fn export(options: &ExportOptions, command_registry: &SceneCommandRegistry) -> Result<Vec<PathBuf>, String> {
+    let mut character_registry = CharacterRegistry::new();
+    character_registry.register_default_characters();
+
+    let scenario_paths = ScenarioLoader::scenario_paths(&options.scenario_dir)
+        .map_err(|e| format!("シナリオディレクトリを開けません {:?}: {}", options.scenario_dir, e))?;
+
+    let mut lines = Vec::new();
+    for path in &scenario_paths {
+        let mut scenario = ScenarioLoader::load_from_file(path, command_registry)
+            .map_err(|e| format!("シナリオを読み込めません {:?}: {}", path, e))?;
+        for speaker in scenario.resolve_speakers(&character_registry) {
+            println!("⚠️ 未登録の話者: {} ({:?})", speaker, path);
+        }
+
+        let scenario_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        lines.extend(collect_lines(&scenario, scenario_name, &options.voice_dir));
+    }
+
+    fs::create_dir_all(&options.output_dir)
+        .map_err(|e| format!("出力先を作成できません {:?}: {}", options.output_dir, e))?;
+
+    let mut written = Vec::new();
+    for (character, character_lines) in group_by_character(lines) {
+        let recorded = character_lines.iter().filter(|line| line.recorded).count();
+        let path = options.output_dir.join(format!("{}.{}", character, options.format.extension()));
+        fs::write(&path, render(&character_lines, options.format))
+            .map_err(|e| format!("台本を書き出せません {:?}: {}", path, e))?;
+
+        println!("🎙️ {}: {} セリフ（収録済み {}） -> {:?}", character, character_lines.len(), recorded, path);
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_scenario() -> ScenarioFile {
+        let mut scenario = ScenarioLoader::parse_markdown(
+            "# 第一章\n\n夜の森。\n\n**ソウマ**「行こう」\n\n**ユズキ**「待って、ソウマ」\n",
+        );
+        let mut characters = CharacterRegistry::new();
+        characters.register_default_characters();
+        scenario.resolve_speakers(&characters);
+        scenario
+    }
+
+    #[test]
+    fn test_collect_lines_with_context() {
+        let lines = collect_lines(&sample_scenario(), "chapter01.md", Path::new("/nonexistent"));
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].character, "souma");
+        assert_eq!(lines[0].previous_line, "夜の森。");
+        assert_eq!(lines[0].voice_file, format!("souma/{}.ogg", lines[0].line_id));
+        assert!(!lines[0].recorded);
+        assert_eq!(lines[1].previous_line, "ソウマ「行こう」");
+        assert_eq!(lines[1].title, "第一章");
+
+        let groups = group_by_character(lines);
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec!["souma", "yuzuki"]);
+    }
+
+    #[test]
+    fn test_render_escapes_fields() {
+        let mut lines = collect_lines(&sample_scenario(), "chapter01.md", Path::new("/nonexistent"));
+        lines.truncate(1);
+        lines[0].text = "a,\"b\"\tc\nd".to_string();
+
+        let csv = render(&lines, ScriptFormat::Csv);
+        assert!(csv.starts_with("line_id,scenario,scene,"));
+        assert!(csv.contains(",\"a,\"\"b\"\"\tc\nd\","));
+
+        let tsv = render(&lines, ScriptFormat::Tsv);
+        assert!(tsv.contains("\ta,\"b\"\\tc\\nd\t"));
+        assert_eq!(tsv.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_export_options_from_args() {
+        let args: Vec<String> = ["--format", "tsv", "--out", "out"].iter().map(|s| s.to_string()).collect();
+        let options = ExportOptions::from_args(&args).unwrap();
+
+        assert_eq!(options.format, ScriptFormat::Tsv);
+        assert_eq!(options.output_dir, PathBuf::from("out"));
+        assert!(ExportOptions::from_args(&["--format".to_string(), "xml".to_string()]).is_err());
+    }
+}
@@ -14,11 +14,72 @@ use application::scenario_system::{
     scenario_progression_system
 };
 use application::command_executor::BackgroundImage;
+use application::command_executor::game_command_registry;
+use application::audio_system::{AudioSettings, voice_line_system, bgm_ducking_system};
+use application::services::{RelationshipChanged, RelationshipService};
+use application::relationship_system::relationship_event_system;
 use presentation::ui_components::*;
 use presentation::screen_systems::*;
 use presentation::systems::*;
 
 fn main() {
+    // 開発用ツールのサブコマンド（ゲームは起動しない）
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(result) = run_tool(&args) {
+        match result {
+            Ok(message) => println!("✅ {}", message),
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // プレイヤー設定（設定画面で変更し、閉じるときに保存）
+    let player_settings = presentation::settings_ui::PlayerSettings::load_or_default(
+        std::path::Path::new(presentation::settings_ui::PLAYER_SETTINGS_PATH),
+    );
+
+    // 表示言語（`--locale en` の指定が優先、なければ設定画面で選んだ言語）
+    let locale = args
+        .windows(2)
+        .find(|pair| pair[0] == "--locale")
+        .map(|pair| pair[1].as_str())
+        .unwrap_or(&player_settings.locale);
+    let localization = infrastructure::translation::load_localization(
+        std::path::Path::new(infrastructure::translation::LOCALE_DIRECTORY),
+        locale,
+    );
+
+    // 関係レベルの判定基準（既定値とペアごとの上書き）
+    let level_config = infrastructure::relationship_data::load_level_config(
+        std::path::Path::new(infrastructure::relationship_data::LEVEL_CONFIG_PATH),
+    );
+
+    let pair_table = infrastructure::relationship_data::load_pair_table(
+        std::path::Path::new(infrastructure::relationship_data::PAIR_TABLE_PATH),
+    );
+
+    // 嫉妬の三角関係（想い人と恋敵の関係値の上昇が嫉妬に連動する）
+    let triangle_rules = infrastructure::relationship_data::load_triangle_rules(
+        std::path::Path::new(infrastructure::relationship_data::TRIANGLE_RULES_PATH),
+    );
+
+    let mut relationships = RelationshipService::with_level_config(level_config);
+    relationships.set_pair_table(pair_table);
+    relationships.set_triangle_rules(triangle_rules);
+
+    // エンディングの規則表（ルート確定時の関係レベル → エンディング）
+    let ending_table = infrastructure::relationship_data::load_ending_table(
+        std::path::Path::new(infrastructure::relationship_data::ENDING_TABLE_PATH),
+    );
+
+    // プレゼントのアイテム定義とキャラクターごとの好み
+    let gifts = infrastructure::gift_data::load_gift_data(
+        std::path::Path::new(infrastructure::gift_data::GIFT_DATA_PATH),
+    );
+
     App::new()
         // Bevy基本機能
         .add_plugins(DefaultPlugins
@@ -54,9 +115,21 @@ fn main() {
         .init_resource::<MenuCursor>()
         .init_resource::<AppState>()
         .init_resource::<DialogueLog>()
+        .init_resource::<TypewriterSettings>()
+        .insert_resource(player_settings.speaker_display)
+        .init_resource::<AudioSettings>()
+        .insert_resource(localization)
         .init_resource::<ScenarioState>()
         .init_resource::<MarkdownScenarioState>()
         .init_resource::<CharacterRegistry>()
+        .insert_resource(game_command_registry())
+        .insert_resource(relationships)
+        .insert_resource(ending_table)
+        .insert_resource(gifts)
+        .add_event::<RelationshipChanged>()
+        .init_resource::<presentation::relationship_chart::RelationshipChartState>()
+        .init_resource::<presentation::gift_ui::GiftUiState>()
+        .init_resource::<presentation::settings_ui::SettingsUiState>()
         // システム追加
         .add_systems(Startup, (setup_assets, setup_character_registry))
         .add_systems(Update, (
@@ -67,6 +140,8 @@ fn main() {
             presentation::systems::mouse_input_system,
             presentation::text_systems::story_mouse_input_system,
             presentation::text_systems::vn_typing_system,
+            presentation::text_systems::dialogue_text_render_system,
+            presentation::text_systems::ruby_layout_system,
             presentation::systems::background_system,
             presentation::systems::button_visual_system,
             presentation::text_systems::log_input_system,
@@ -78,5 +153,92 @@ fn main() {
             markdown_scenario_system,
             markdown_scenario_input_system,
         ))
+        // 話者表示（名前欄・立ち絵の強調と暗転）
+        .add_systems(Update, (
+            presentation::dialogue_ui::speaker_name_render_system,
+            presentation::dialogue_ui::speaker_focus_system,
+        ))
+        // セーブ（F5キー、「つづきから」で再開）
+        .add_systems(Update, presentation::systems::save_input_system)
+        // ボイス再生・BGMのダッキング
+        .add_systems(Update, (
+            voice_line_system,
+            bgm_ducking_system,
+        ))
+        // 関係値の変動イベントと画面通知
+        .add_systems(Update, (
+            relationship_event_system,
+            presentation::relationship_toast::relationship_toast_spawn_system,
+            presentation::relationship_toast::relationship_toast_animation_system,
+        ).chain())
+        // 戦闘画面の関係値表示（ストーリーと共有の関係値に追従）
+        .add_systems(Update, presentation::battle_ui::relationship_display_sync_system)
+        // 相関図（サイトウに話しかけると開く）
+        .add_systems(Update, (
+            presentation::relationship_chart::relationship_chart_setup_system,
+            presentation::relationship_chart::relationship_chart_input_system,
+            presentation::relationship_chart::relationship_chart_selection_system,
+        ).chain())
+        // 設定画面（タイトルの「設定」から開く）
+        .add_systems(Update, (
+            presentation::settings_ui::settings_setup_system,
+            presentation::settings_ui::settings_input_system,
+            presentation::settings_ui::settings_panel_render_system,
+        ).chain())
+        // プレゼント（拠点で仲間に渡す）
+        .add_systems(Update, (
+            presentation::gift_ui::gift_setup_system,
+            presentation::gift_ui::gift_input_system,
+            presentation::gift_ui::gift_panel_render_system,
+        ).chain())
         .run();
 }
+
+/// 開発用ツールのサブコマンドを実行（サブコマンドでなければ None）
+///
+/// - `export-voice-script` : キャラクター別のボイス台本を書き出す
+/// - `assign-line-ids [DIR]` : セリフIDの未記入ブロックに `{#id}` を書き込む
+/// - `export-po --locale LANG` : 翻訳者向けの PO ファイルを書き出す
+/// - `import-po --locale LANG` : 翻訳済みの PO ファイルを取り込む
+fn run_tool(args: &[String]) -> Option<Result<String, String>> {
+    use infrastructure::scenario_loader::ScenarioLoader;
+    use infrastructure::translation::{self, TranslationOptions};
+    use infrastructure::voice_script;
+
+    match args.get(1)?.as_str() {
+        voice_script::SUBCOMMAND => Some(
+            voice_script::ExportOptions::from_args(&args[2..])
+                .and_then(|options| voice_script::export(&options, &game_command_registry()))
+                .map(|files| format!("ボイス台本を {} ファイル書き出しました", files.len()))
+                .map_err(|e| format!("ボイス台本の書き出しに失敗: {}", e)),
+        ),
+        "assign-line-ids" => {
+            let dir = args.get(2).map(String::as_str).unwrap_or("assets/scenarios");
+            let registry = game_command_registry();
+            let result = ScenarioLoader::scenario_paths(dir).and_then(|paths| {
+                paths
+                    .iter()
+                    .map(|path| ScenarioLoader::write_missing_line_ids(path, &registry))
+                    .sum::<Result<usize, _>>()
+            });
+            Some(
+                result
+                    .map(|count| format!("セリフIDを {} 件書き込みました", count))
+                    .map_err(|e| format!("セリフIDの書き込みに失敗: {}", e)),
+            )
+        }
+        "export-po" => Some(
+            TranslationOptions::from_args(&args[2..])
+                .and_then(|options| translation::export_po(&options, &game_command_registry()))
+                .map(|files| format!("PO ファイルを {} 件書き出しました", files.len()))
+                .map_err(|e| format!("PO ファイルの書き出しに失敗: {}", e)),
+        ),
+        "import-po" => Some(
+            TranslationOptions::from_args(&args[2..])
+                .and_then(|options| translation::import_po(&options))
+                .map(|count| format!("翻訳を {} 項目取り込みました", count))
+                .map_err(|e| format!("翻訳の取り込みに失敗: {}", e)),
+        ),
+        _ => None,
+    }
+}
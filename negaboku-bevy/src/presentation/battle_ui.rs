@@ -6,7 +6,9 @@
 use bevy::prelude::*;
 use crate::presentation::ui_components::*;
 use crate::domain::battle::{BattleParty, BattleSkill, BattleResult};
-use crate::application::services::BattleService;
+use crate::application::services::{BattleService, RelationshipService};
+use crate::domain::relationship::RelationshipLevel;
+use crate::presentation::relationship_chart::level_color;
 
 /// 戦闘画面の要素を示すマーカーコンポーネント
 #[derive(Component)]
@@ -38,12 +40,14 @@ pub struct DamageText {
     pub initial_position: Vec3,
 }
 
-/// 関係値表示UI
+/// 関係値表示UI（共有の `RelationshipService` の値を表示）
 #[derive(Component, Debug)]
 pub struct RelationshipDisplay {
     pub character_a: String,
     pub character_b: String,
     pub relationship_value: i32,
+    /// ペアの判定基準で判定した関係レベル
+    pub level: RelationshipLevel,
 }
 
 /// 戦闘ログ表示
@@ -113,7 +117,7 @@ impl HPBar {
         let name_entity = commands.spawn((
             Text2d::new(character_name),
             TextFont {
-                font: assets.main_font.clone(),
+                font: assets.font_fallback.font_for_text(&assets.name_font, character_name),
                 font_size: 16.0,
                 ..default()
             },
@@ -127,7 +131,7 @@ impl HPBar {
         let hp_text_entity = commands.spawn((
             Text2d::new(&hp_text),
             TextFont {
-                font: assets.main_font.clone(),
+                font: assets.font_fallback.font_for_text(&assets.number_font, &hp_text),
                 font_size: 14.0,
                 ..default()
             },
@@ -161,6 +165,7 @@ impl SkillButton {
                 crate::domain::battle::SkillType::Normal => Color::srgba(0.4, 0.6, 0.8, 0.8), // 青系
                 crate::domain::battle::SkillType::Cooperation => Color::srgba(0.6, 0.8, 0.4, 0.8), // 緑系
                 crate::domain::battle::SkillType::Conflict => Color::srgba(0.8, 0.4, 0.4, 0.8), // 赤系
+                crate::domain::battle::SkillType::SuperConflict => Color::srgba(0.7, 0.2, 0.5, 0.9), // 赤紫系
             }
         }
     }
@@ -190,7 +195,7 @@ impl SkillButton {
             let name_entity = commands.spawn((
                 Text2d::new(&skill.name),
                 TextFont {
-                    font: assets.main_font.clone(),
+                    font: assets.font_fallback.font_for_text(&assets.main_font, &skill.name),
                     font_size: 14.0,
                     ..default()
                 },
@@ -200,20 +205,30 @@ impl SkillButton {
                 BattleUIElement,
             )).id();
 
-            // 威力表示
+            // 威力表示（数値は数値フォント、ラベルはフォールバックのフォントで表示）
             let power_text = format!("威力: {}", skill.base_power);
+            let power_font = TextFont {
+                font: assets.number_font.clone(),
+                font_size: 10.0,
+                ..default()
+            };
+            let power_color = if is_usable { Color::srgba(0.9, 0.9, 0.9, 1.0) } else { Color::srgba(0.5, 0.5, 0.5, 1.0) };
             let power_entity = commands.spawn((
-                Text2d::new(&power_text),
-                TextFont {
-                    font: assets.main_font.clone(),
-                    font_size: 10.0,
-                    ..default()
-                },
+                Text2d::new(""),
+                power_font.clone(),
                 TextLayout::new_with_justify(JustifyText::Center),
-                TextColor(if is_usable { Color::srgba(0.9, 0.9, 0.9, 1.0) } else { Color::srgba(0.5, 0.5, 0.5, 1.0) }),
+                TextColor(power_color),
                 Transform::from_xyz(0.0, -10.0, 1.0),
                 BattleUIElement,
-            )).id();
+            )).with_children(|parent| {
+                for (range, font) in assets.font_fallback.runs(&power_font.font, &power_text) {
+                    parent.spawn((
+                        TextSpan::new(&power_text[range]),
+                        TextFont { font, ..power_font.clone() },
+                        TextColor(power_color),
+                    ));
+                }
+            }).id();
 
             commands.entity(button_entity).add_children(&[name_entity, power_entity]);
             button_entities.push(button_entity);
@@ -245,7 +260,7 @@ impl DamageText {
         commands.spawn((
             Text2d::new(&text_content),
             TextFont {
-                font: assets.main_font.clone(),
+                font: assets.font_fallback.font_for_text(&assets.number_font, &text_content),
                 font_size: 24.0,
                 ..default()
             },
@@ -258,45 +273,51 @@ impl DamageText {
 }
 
 impl RelationshipDisplay {
-    pub fn new(character_a: String, character_b: String, relationship_value: i32) -> Self {
+    /// 共有の関係値からペアの表示を作成
+    pub fn new(relationships: &RelationshipService, character_a: &str, character_b: &str) -> Self {
         Self {
-            character_a,
-            character_b,
-            relationship_value,
+            character_a: character_a.to_string(),
+            character_b: character_b.to_string(),
+            relationship_value: relationships.get_relationship_value(character_a, character_b),
+            level: relationships.get_relationship_level(character_a, character_b),
         }
     }
 
-    /// 関係値の表示色を取得
+    /// 共有の関係値を読み直す（変わった場合は true）
+    pub fn refresh(&mut self, relationships: &RelationshipService) -> bool {
+        let value = relationships.get_relationship_value(&self.character_a, &self.character_b);
+        let level = relationships.get_relationship_level(&self.character_a, &self.character_b);
+        let changed = value != self.relationship_value || level != self.level;
+        self.relationship_value = value;
+        self.level = level;
+        changed
+    }
+
+    /// 関係値の表示色を取得（相関図と同じ、関係レベルの色）
     pub fn get_relationship_color(&self) -> Color {
-        if self.relationship_value >= 50 {
-            Color::srgb(0.3, 0.8, 0.3) // 親密（緑）
-        } else if self.relationship_value <= -50 {
-            Color::srgb(0.8, 0.3, 0.3) // 対立（赤）
-        } else {
-            Color::srgb(0.7, 0.7, 0.7) // 通常（グレー）
-        }
+        level_color(self.level)
+    }
+
+    /// 表示する文字列
+    pub fn text(&self) -> String {
+        format!("{}⇔{}: {}", self.character_a, self.character_b, self.relationship_value)
     }
 
     /// 関係値表示をspawn
     pub fn spawn_relationship_display(
         commands: &mut Commands,
         assets: &GameAssets,
+        relationships: &RelationshipService,
         character_a: &str,
         character_b: &str,
-        relationship_value: i32,
     ) -> Entity {
-        let rel_display = RelationshipDisplay::new(
-            character_a.to_string(),
-            character_b.to_string(),
-            relationship_value,
-        );
-
-        let relationship_text = format!("{}⇔{}: {}", character_a, character_b, relationship_value);
+        let rel_display = RelationshipDisplay::new(relationships, character_a, character_b);
+        let relationship_text = rel_display.text();
 
         commands.spawn((
             Text2d::new(&relationship_text),
             TextFont {
-                font: assets.main_font.clone(),
+                font: assets.font_fallback.font_for_text(&assets.number_font, &relationship_text),
                 font_size: 16.0,
                 ..default()
             },
@@ -308,11 +329,14 @@ impl RelationshipDisplay {
     }
 }
 
+/// スキルボタンとそのスプライト（HPバーのスプライトとは別に借りる）
+type SkillButtonQuery<'w, 's> =
+    Query<'w, 's, (&'static mut SkillButton, &'static mut Sprite), (With<SkillButton>, Without<HPBar>)>;
+
 /// 戦闘UI更新システム
 pub fn battle_ui_update_system(
     mut hp_bar_query: Query<(&mut HPBar, &mut Sprite), With<HPBar>>,
-    mut skill_button_query: Query<(&mut SkillButton, &mut Sprite), (With<SkillButton>, Without<HPBar>)>,
-    mut relationship_query: Query<(&mut RelationshipDisplay, &mut Text2d)>,
+    mut skill_button_query: SkillButtonQuery,
 ) {
     // HPバーの更新
     for (hp_bar, mut sprite) in hp_bar_query.iter_mut() {
@@ -327,16 +351,22 @@ pub fn battle_ui_update_system(
     for (skill_button, mut sprite) in skill_button_query.iter_mut() {
         sprite.color = skill_button.get_button_color();
     }
+}
+
+/// 関係値表示を共有の関係値に追従させるシステム（ストーリー・戦闘のどちらの変動も反映）
+pub fn relationship_display_sync_system(
+    relationships: Res<RelationshipService>,
+    mut relationship_query: Query<(&mut RelationshipDisplay, &mut Text2d, &mut TextColor)>,
+) {
+    if !relationships.is_changed() {
+        return;
+    }
 
-    // 関係値表示の色更新
-    for (relationship_display, mut text) in relationship_query.iter_mut() {
-        // テキストの内容更新
-        let new_text = format!("{}⇔{}: {}",
-            relationship_display.character_a,
-            relationship_display.character_b,
-            relationship_display.relationship_value
-        );
-        **text = new_text;
+    for (mut relationship_display, mut text, mut color) in relationship_query.iter_mut() {
+        if relationship_display.refresh(&relationships) {
+            **text = relationship_display.text();
+            color.0 = relationship_display.get_relationship_color();
+        }
     }
 }
 
@@ -488,13 +518,39 @@ mod tests {
 
     #[test]
     fn relationship_display_colors() {
-        let intimate_rel = RelationshipDisplay::new("A".to_string(), "B".to_string(), 75);
+        use crate::domain::relationship::{RelationshipLevelConfig, RelationshipThresholds};
+
+        let mut relationships = RelationshipService::new();
+        relationships.modify_relationship("A", "B", 75);
+        let intimate_rel = RelationshipDisplay::new(&relationships, "A", "B");
         assert_eq!(intimate_rel.get_relationship_color(), Color::srgb(0.3, 0.8, 0.3)); // 緑
 
-        let conflict_rel = RelationshipDisplay::new("A".to_string(), "B".to_string(), -60);
+        relationships.modify_relationship("A", "C", -60);
+        let conflict_rel = RelationshipDisplay::new(&relationships, "A", "C");
         assert_eq!(conflict_rel.get_relationship_color(), Color::srgb(0.8, 0.3, 0.3)); // 赤
 
-        let normal_rel = RelationshipDisplay::new("A".to_string(), "B".to_string(), 25);
+        relationships.modify_relationship("B", "C", 25);
+        let normal_rel = RelationshipDisplay::new(&relationships, "B", "C");
         assert_eq!(normal_rel.get_relationship_color(), Color::srgb(0.7, 0.7, 0.7)); // グレー
+
+        // 色はペアの判定基準に従う（-40 で対立のペア）
+        let mut config = RelationshipLevelConfig::default();
+        config.set_pair_override("souma", "kai", RelationshipThresholds { conflict_max: -40, ..Default::default() });
+        let mut relationships = RelationshipService::with_level_config(config);
+        relationships.modify_relationship("souma", "kai", -40);
+        assert_eq!(RelationshipDisplay::new(&relationships, "kai", "souma").level, RelationshipLevel::Conflict);
+    }
+
+    #[test]
+    fn relationship_display_follows_shared_relationships() {
+        let mut relationships = RelationshipService::new();
+        let mut display = RelationshipDisplay::new(&relationships, "souma", "yuzuki");
+        assert_eq!(display.text(), "souma⇔yuzuki: 0");
+        assert!(!display.refresh(&relationships));
+
+        relationships.modify_relationship("yuzuki", "souma", 55);
+        assert!(display.refresh(&relationships));
+        assert_eq!(display.relationship_value, 55);
+        assert_eq!(display.level, RelationshipLevel::Intimate);
     }
 }
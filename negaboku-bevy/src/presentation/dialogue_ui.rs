@@ -6,6 +6,40 @@
 use bevy::prelude::*;
 use crate::presentation::ui_components::*;
 use crate::application::scenario_system::MarkdownScenarioState;
+use crate::domain::character::{CharacterDisplay, CharacterRegistry};
+use crate::domain::localization::Localization;
+
+/// 名前欄の既定の文字色
+const DEFAULT_NAME_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
+
+/// 立ち絵の強調・暗転の状態（切り替えをアニメーションさせるため現在値を保持）
+#[derive(Component, Debug)]
+pub struct SpeakerFocus {
+    /// 強調前の拡大率
+    pub base_scale: Vec3,
+    /// 強調前のZ座標
+    pub base_z: f32,
+    /// 強調の度合い（0.0〜1.0）
+    pub emphasis: f32,
+    /// 暗転の度合い（0.0〜1.0）
+    pub dim: f32,
+}
+
+impl SpeakerFocus {
+    /// 現在値を目標値へ `step` だけ近づける
+    pub fn approach(&mut self, target_emphasis: f32, target_dim: f32, step: f32) {
+        self.emphasis = approach(self.emphasis, target_emphasis, step);
+        self.dim = approach(self.dim, target_dim, step);
+    }
+}
+
+fn approach(current: f32, target: f32, step: f32) -> f32 {
+    if current < target {
+        (current + step).min(target)
+    } else {
+        (current - step).max(target)
+    }
+}
 
 /// ダイアログボックスのコンポーネント
 #[derive(Component, Debug)]
@@ -257,10 +291,7 @@ pub fn dialogue_ui_system(
                 for mut vn_dialogue in vn_dialogue_query.iter_mut() {
                     let dialogue_text = format!("{:?}", dialogue_block); // 仮実装
                     if vn_dialogue.full_text != dialogue_text {
-                        vn_dialogue.full_text = dialogue_text;
-                        vn_dialogue.current_char = 0;
-                        vn_dialogue.is_complete = false;
-                        vn_dialogue.timer.reset();
+                        vn_dialogue.set_text(dialogue_text);
                     }
                 }
             }
@@ -319,19 +350,16 @@ pub fn character_display_system(
         if let Some(current_scene) = scenario.scenes.get(markdown_state.current_scene_index) {
             // シーンコマンドからキャラクター表示指示を処理
             for command in &current_scene.commands {
-                // キャラクター表示コマンドの処理（実装例）
-                match command {
-                    crate::domain::scenario::SceneCommand::CharacterShow { name, face, pos } => {
-                        for (mut character_sprite, mut transform, mut sprite) in character_query.iter_mut() {
-                            // キャラクター表示の更新ロジック
-                            // TODO: コマンドに応じてキャラクターの位置、表情、透明度を更新
-                            character_sprite.character_name = name.clone();
-                            if let Some(expression) = face {
-                                character_sprite.expression = expression.clone();
-                            }
+                // キャラクター表示コマンドの処理（実装例、その他のコマンドは無視）
+                if let Some(crate::domain::scenario::CharacterShowCommand { name, face, pos }) = command.payload() {
+                    for (mut character_sprite, mut transform, mut sprite) in character_query.iter_mut() {
+                        // キャラクター表示の更新ロジック
+                        // TODO: コマンドに応じてキャラクターの位置、表情、透明度を更新
+                        character_sprite.character_name = name.clone();
+                        if let Some(expression) = face {
+                            character_sprite.expression = expression.clone();
                         }
                     }
-                    _ => {} // その他のコマンドは無視
                 }
             }
         }
@@ -352,10 +380,125 @@ pub fn cleanup_dialogue_ui_system(
     }
 }
 
+/// 名前欄の表示を話者に合わせて更新するシステム（キャラクターごとの色・フォント）
+pub fn speaker_name_render_system(
+    mut name_query: Query<(&VNCharacterName, &mut Text2d, &mut TextColor, &mut TextFont), Changed<VNCharacterName>>,
+    character_registry: Res<CharacterRegistry>,
+    localization: Res<Localization>,
+    asset_server: Res<AssetServer>,
+    assets: Option<Res<GameAssets>>,
+) {
+    for (character_name, mut text, mut color, mut font) in name_query.iter_mut() {
+        text.0 = localization.speaker_name(&character_name.name).to_string();
+        let Some(assets) = &assets else { continue; };
+
+        // 匿名表示（？？？）の話者は色・フォントで正体が分からないよう既定の見た目にする
+        let character = character_name.revealed_character(&character_registry);
+        color.0 = character.and_then(|character| character.name_color).unwrap_or(DEFAULT_NAME_COLOR);
+
+        // キャラクター固有フォント → 名前欄フォント（名前に含まれない文字はフォールバック）
+        let name_font = match character.and_then(|character| character.name_font.as_deref()) {
+            Some(font_path) => asset_server.load(font_path),
+            None => assets.name_font.clone(),
+        };
+        font.font = assets.font_fallback.font_for_text(&name_font, &text.0);
+    }
+}
+
+/// 話者の立ち絵を強調し、話者以外を暗くするシステム
+///
+/// 話者は現在のダイアログのキャラクターID（名前欄）から決まる。
+/// プレイヤー設定と `[speaker_focus enabled=false]`（シーン単位）で無効化できる。
+pub fn speaker_focus_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<SpeakerDisplaySettings>,
+    scenario_state: Res<MarkdownScenarioState>,
+    character_registry: Res<CharacterRegistry>,
+    name_query: Query<&VNCharacterName>,
+    mut character_query: Query<(Entity, &CharacterDisplay, &mut Transform, &mut Sprite, Option<&mut SpeakerFocus>)>,
+) {
+    let speaker_id = name_query
+        .iter()
+        .next()
+        .filter(|_| !scenario_state.is_speaker_focus_disabled)
+        .and_then(|name| name.revealed_character(&character_registry))
+        .map(|character| character.id.as_str());
+
+    // 話者が画面にいない場合（地の文・画面外の声）は誰も暗くしない
+    let speaker_on_screen = character_query
+        .iter()
+        .any(|(_, display, ..)| display.is_visible && Some(display.character_id.as_str()) == speaker_id);
+
+    let step = if settings.transition_seconds > 0.0 {
+        time.delta_secs() / settings.transition_seconds
+    } else {
+        1.0
+    };
+
+    for (entity, display, mut transform, mut sprite, focus) in character_query.iter_mut() {
+        let is_speaker = speaker_on_screen && Some(display.character_id.as_str()) == speaker_id;
+        let target_emphasis = if is_speaker && settings.highlight_speaker { 1.0 } else { 0.0 };
+        let target_dim = if speaker_on_screen && !is_speaker && settings.dim_non_speakers { 1.0 } else { 0.0 };
+
+        let Some(mut focus) = focus else {
+            if target_emphasis > 0.0 || target_dim > 0.0 {
+                commands.entity(entity).insert(SpeakerFocus {
+                    base_scale: transform.scale,
+                    base_z: transform.translation.z,
+                    emphasis: 0.0,
+                    dim: 0.0,
+                });
+            }
+            continue;
+        };
+
+        if focus.emphasis != target_emphasis || focus.dim != target_dim {
+            focus.approach(target_emphasis, target_dim, step);
+        }
+
+        // 変化があった場合のみ書き込む（毎フレームの変更検知を避ける）
+        let scale = focus.base_scale * (1.0 + (settings.highlight_scale - 1.0) * focus.emphasis);
+        if transform.scale != scale {
+            transform.scale = scale;
+        }
+        let z = focus.base_z + 0.5 * focus.emphasis;
+        if transform.translation.z != z {
+            transform.translation.z = z;
+        }
+
+        // 非表示（透明化）中の立ち絵は色を変えない
+        if display.is_visible {
+            let brightness = 1.0 - (1.0 - settings.dim_brightness) * focus.dim;
+            let color = Color::srgb(brightness, brightness, brightness);
+            if sprite.color != color {
+                sprite.color = color;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn speaker_focus_approaches_target() {
+        let mut focus = SpeakerFocus {
+            base_scale: Vec3::ONE,
+            base_z: -5.0,
+            emphasis: 0.0,
+            dim: 1.0,
+        };
+
+        focus.approach(1.0, 0.0, 0.4);
+        assert_eq!((focus.emphasis, focus.dim), (0.4, 0.6));
+
+        // 目標値を超えない
+        focus.approach(1.0, 0.0, 0.8);
+        assert_eq!((focus.emphasis, focus.dim), (1.0, 0.0));
+    }
+
     #[test]
     fn dialogue_box_creation() {
         let dialogue_box = DialogueBox::new(800.0, 200.0);
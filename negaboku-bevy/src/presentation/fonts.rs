@@ -0,0 +1,353 @@
+//! フォント設定 - 用途別フォントとフォールバック
+//!
+//! # 責務
+//! - 用途別（本文・名前欄・UI・戦闘の数値）のフォント設定の読み込み
+//! - フォントファイルが見つからない場合の代替フォントの選択
+//! - 収録グリフに基づく文字単位のフォールバック（絵文字・外字・記号など）
+//!
+//! 設定は `assets/fonts/fonts.json` に置く（なければ既定値）。
+//! フォントのパスは assets からの相対パスで書く。
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// フォント設定ファイルのパス
+pub const FONT_CONFIG_PATH: &str = "assets/fonts/fonts.json";
+
+/// 日本語本文の既定フォント
+const DEFAULT_JAPANESE_FONT: &str = "fonts/NotoSansJP-VariableFont_wght.ttf";
+
+/// 欧文・数字の既定フォント
+const DEFAULT_LATIN_FONT: &str = "fonts/FiraSans-Bold.otf";
+
+/// フォントの用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontRole {
+    /// セリフ・地の文・ログの本文
+    Dialogue,
+    /// 名前欄
+    Name,
+    /// メニュー・ボタンなどのUI
+    Ui,
+    /// 戦闘画面の数値（パワー・関係値など）
+    BattleNumber,
+}
+
+/// 用途別フォントとフォールバック順の設定（Bevy Resource）
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct FontSettings {
+    pub dialogue: String,
+    pub name: String,
+    pub ui: String,
+    pub battle_number: String,
+    /// `**強調**` 用フォント（なければ本文フォントで代用）
+    pub bold: String,
+    /// グリフがない文字・ファイルがないフォントの代わりに使うフォント（優先順）
+    pub fallbacks: Vec<String>,
+}
+
+impl Default for FontSettings {
+    fn default() -> Self {
+        Self {
+            dialogue: DEFAULT_JAPANESE_FONT.to_string(),
+            name: DEFAULT_JAPANESE_FONT.to_string(),
+            ui: DEFAULT_JAPANESE_FONT.to_string(),
+            battle_number: DEFAULT_LATIN_FONT.to_string(),
+            bold: "fonts/NotoSansJP-Bold.ttf".to_string(),
+            fallbacks: vec![
+                DEFAULT_JAPANESE_FONT.to_string(),
+                "fonts/NotoSansSymbols2-Regular.ttf".to_string(),
+                "fonts/NotoEmoji-Regular.ttf".to_string(),
+                DEFAULT_LATIN_FONT.to_string(),
+            ],
+        }
+    }
+}
+
+impl FontSettings {
+    /// 設定ファイルを読み込む（ファイルがない・読めない場合は既定値）
+    pub fn load_or_default(path: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str(&content) {
+            Ok(settings) => {
+                println!("🔤 フォント設定を読み込みました: {:?}", path);
+                settings
+            }
+            Err(e) => {
+                eprintln!("⚠️ フォント設定を読み込めません（既定値を使用）{:?}: {}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// 用途のフォントのパス
+    pub fn path(&self, role: FontRole) -> &str {
+        match role {
+            FontRole::Dialogue => &self.dialogue,
+            FontRole::Name => &self.name,
+            FontRole::Ui => &self.ui,
+            FontRole::BattleNumber => &self.battle_number,
+        }
+    }
+}
+
+/// フォントに収録されている文字の集合
+#[derive(Debug, Clone, Default)]
+pub struct GlyphCoverage {
+    chars: HashSet<char>,
+}
+
+impl GlyphCoverage {
+    /// フォントデータ（TTF/OTF）の cmap から収録文字を読み取る
+    pub fn from_font_data(data: &[u8]) -> Option<Self> {
+        let face = ttf_parser::Face::parse(data, 0).ok()?;
+        let mut chars = HashSet::new();
+
+        for subtable in face.tables().cmap?.subtables {
+            if subtable.is_unicode() {
+                subtable.codepoints(|codepoint| {
+                    if let Some(c) = char::from_u32(codepoint) {
+                        chars.insert(c);
+                    }
+                });
+            }
+        }
+
+        Some(Self { chars })
+    }
+
+    /// 書記素クラスタの表示に必要な文字がすべて収録されているか
+    ///
+    /// 結合文字・異体字セレクタなど幅のない文字は判定に含めない
+    pub fn covers(&self, grapheme: &str) -> bool {
+        grapheme
+            .chars()
+            .filter(|c| !is_zero_width(*c))
+            .all(|c| self.chars.contains(&c))
+    }
+}
+
+/// グリフを持たなくてよい幅のない文字
+fn is_zero_width(c: char) -> bool {
+    c.is_control()
+        || matches!(c, '\u{200B}'..='\u{200F}' | '\u{FE00}'..='\u{FE0F}' | '\u{E0100}'..='\u{E01EF}')
+        || matches!(c, '\u{0300}'..='\u{036F}' | '\u{3099}' | '\u{309A}')
+}
+
+/// 読み込み済みのフォント1つ
+#[derive(Debug, Clone)]
+struct FontFace {
+    path: String,
+    handle: Handle<Font>,
+    coverage: GlyphCoverage,
+    /// フォールバック順に含まれるか（用途別フォントとしてだけ読み込んだものは含まない）
+    in_chain: bool,
+}
+
+/// フォールバック付きのフォント集合
+///
+/// 文字列をフォントごとの区間（ラン）に分け、主フォントにない文字を
+/// フォールバック順で最初に収録しているフォントで表示する
+#[derive(Debug, Clone, Default)]
+pub struct FontFallback {
+    faces: Arc<Vec<FontFace>>,
+}
+
+impl FontFallback {
+    /// 設定のフォントをすべて読み込む（ファイルがないフォントは除く）
+    pub fn load(settings: &FontSettings, asset_server: &AssetServer) -> Self {
+        let role_fonts = [&settings.dialogue, &settings.name, &settings.ui, &settings.battle_number, &settings.bold];
+        let paths = settings
+            .fallbacks
+            .iter()
+            .map(|path| (path, true))
+            .chain(role_fonts.into_iter().map(|path| (path, false)));
+
+        let mut faces: Vec<FontFace> = Vec::new();
+        for (path, in_chain) in paths {
+            if faces.iter().any(|face| face.path == *path) {
+                continue;
+            }
+
+            let full_path = format!("assets/{}", path);
+            let Ok(data) = std::fs::read(&full_path) else {
+                continue;
+            };
+            let Some(coverage) = GlyphCoverage::from_font_data(&data) else {
+                eprintln!("⚠️ フォントを解析できません: {}", full_path);
+                continue;
+            };
+
+            println!("✅ フォントを確認: {}（{} 文字）", full_path, coverage.chars.len());
+            faces.push(FontFace {
+                path: path.clone(),
+                handle: asset_server.load(path.as_str()),
+                coverage,
+                in_chain,
+            });
+        }
+
+        Self { faces: Arc::new(faces) }
+    }
+
+    /// パスのフォントを取得（ファイルがなければフォールバック順の最初のフォント）
+    ///
+    /// どのフォントもない場合は `None`
+    pub fn font_or_fallback(&self, path: &str) -> Option<Handle<Font>> {
+        if let Some(face) = self.faces.iter().find(|face| face.path == path) {
+            return Some(face.handle.clone());
+        }
+
+        let fallback = self.faces.iter().find(|face| face.in_chain)?;
+        println!("⚠️ フォントが見つかりません: assets/{} → 代替フォントを使用: assets/{}", path, fallback.path);
+        Some(fallback.handle.clone())
+    }
+
+    /// フォントが書記素クラスタを表示できるか（収録文字が不明なフォントは表示できるとみなす）
+    fn covers(&self, font: &Handle<Font>, grapheme: &str) -> bool {
+        self.faces
+            .iter()
+            .find(|face| face.handle == *font)
+            .map_or(true, |face| face.coverage.covers(grapheme))
+    }
+
+    /// 書記素クラスタを表示するフォント（主フォント → フォールバック順）
+    fn font_for_grapheme(&self, primary: &Handle<Font>, grapheme: &str) -> Handle<Font> {
+        if self.covers(primary, grapheme) {
+            return primary.clone();
+        }
+
+        self.faces
+            .iter()
+            .filter(|face| face.in_chain)
+            .find(|face| face.coverage.covers(grapheme))
+            .map_or_else(|| primary.clone(), |face| face.handle.clone())
+    }
+
+    /// 文字列をフォントごとの区間（バイト範囲, フォント）に分ける
+    ///
+    /// 空白は直前の区間のフォントのまま続ける
+    pub fn runs(&self, primary: &Handle<Font>, text: &str) -> Vec<(Range<usize>, Handle<Font>)> {
+        let mut runs: Vec<(Range<usize>, Handle<Font>)> = Vec::new();
+
+        for (offset, grapheme) in text.grapheme_indices(true) {
+            let end = offset + grapheme.len();
+            let font = if grapheme.chars().all(char::is_whitespace) {
+                runs.last().map_or_else(|| primary.clone(), |(_, font)| font.clone())
+            } else {
+                self.font_for_grapheme(primary, grapheme)
+            };
+
+            match runs.last_mut() {
+                Some((range, last_font)) if *last_font == font => range.end = end,
+                _ => runs.push((offset..end, font)),
+            }
+        }
+
+        if runs.is_empty() {
+            runs.push((0..text.len(), primary.clone()));
+        }
+        runs
+    }
+
+    /// 文字列全体を1つのフォントで表示する場合のフォント
+    ///
+    /// 主フォントで表示できない文字があれば、全体を表示できる最初のフォールバックを選ぶ
+    pub fn font_for_text(&self, primary: &Handle<Font>, text: &str) -> Handle<Font> {
+        let displayable = |font: &Handle<Font>| text.graphemes(true).all(|grapheme| self.covers(font, grapheme));
+        if displayable(primary) {
+            return primary.clone();
+        }
+
+        self.faces
+            .iter()
+            .filter(|face| face.in_chain)
+            .map(|face| &face.handle)
+            .find(|font| displayable(font))
+            .cloned()
+            .unwrap_or_else(|| primary.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn face(path: &str, id: u128, chars: &str) -> FontFace {
+        FontFace {
+            path: path.to_string(),
+            handle: Handle::weak_from_u128(id),
+            coverage: GlyphCoverage {
+                chars: chars.chars().collect(),
+            },
+            in_chain: true,
+        }
+    }
+
+    fn sample_fallback() -> (FontFallback, [Handle<Font>; 3]) {
+        let faces = vec![
+            face("fonts/jp.ttf", 1, "あいう漢字AB 「」"),
+            face("fonts/symbols.ttf", 2, "★♪"),
+            face("fonts/latin.otf", 3, "0123456789AB ★"),
+        ];
+        let handles = [faces[0].handle.clone(), faces[1].handle.clone(), faces[2].handle.clone()];
+        (FontFallback { faces: Arc::new(faces) }, handles)
+    }
+
+    #[test]
+    fn test_runs_split_by_coverage() {
+        let (fallback, [japanese, symbols, _]) = sample_fallback();
+
+        let text = "あい★♪う";
+        let runs = fallback.runs(&japanese, text);
+        assert_eq!(runs.len(), 3);
+        assert_eq!(&text[runs[0].0.clone()], "あい");
+        assert_eq!(&text[runs[1].0.clone()], "★♪");
+        assert_eq!(runs[1].1, symbols);
+        assert_eq!(&text[runs[2].0.clone()], "う");
+        assert_eq!(runs[2].1, japanese);
+
+        // 空白は直前のフォントで続け、どのフォントにもない文字は主フォントのまま
+        let runs = fallback.runs(&japanese, "★ 𩸽");
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0], (0..4, symbols.clone()));
+        assert_eq!(runs[1].1, japanese);
+
+        assert_eq!(fallback.runs(&japanese, ""), vec![(0..0, japanese.clone())]);
+    }
+
+    #[test]
+    fn test_font_for_text_and_missing_file() {
+        let (fallback, [japanese, _, latin]) = sample_fallback();
+
+        assert_eq!(fallback.font_for_text(&latin, "128"), latin);
+        assert_eq!(fallback.font_for_text(&latin, "あ"), japanese);
+        // 結合文字・異体字セレクタは収録を問わない
+        assert_eq!(fallback.font_for_text(&japanese, "う\u{3099}"), japanese);
+
+        // 収録文字が不明なフォント（キャラクター固有フォントなど）はそのまま使う
+        let unknown = Handle::<Font>::weak_from_u128(9);
+        assert_eq!(fallback.font_for_text(&unknown, "★"), unknown);
+
+        assert_eq!(fallback.font_or_fallback("fonts/latin.otf"), Some(latin));
+        assert_eq!(fallback.font_or_fallback("fonts/missing.ttf"), Some(japanese));
+        assert_eq!(FontFallback::default().font_or_fallback("fonts/missing.ttf"), None);
+    }
+
+    #[test]
+    fn test_font_settings_partial_config() {
+        let settings: FontSettings = serde_json::from_str(r#"{ "name": "fonts/name.ttf" }"#).unwrap();
+        assert_eq!(settings.path(FontRole::Name), "fonts/name.ttf");
+        assert_eq!(settings.path(FontRole::Dialogue), DEFAULT_JAPANESE_FONT);
+        assert_eq!(settings.path(FontRole::BattleNumber), DEFAULT_LATIN_FONT);
+    }
+}
@@ -0,0 +1,395 @@
+//! プレゼント画面 - 拠点で仲間にプレゼントを渡す
+//!
+//! # 責務
+//! - 渡す相手・アイテムの選択とプレゼントの受け渡し（関係値の変動は `GiftService` 経由）
+//! - キャラクター・反応ごとの反応セリフの表示
+//! - 反応から判明した好み（好みの手帳）の表示（未判明の種類は伏せる）
+//!
+//! 拠点のシナリオから `[gift]` コマンドで開く
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use std::path::Path;
+use crate::application::scenario_system::MarkdownScenarioState;
+use crate::application::services::{GiftOutcome, GiftService, RelationshipService};
+use crate::domain::character::CharacterRegistry;
+use crate::domain::command_registry::SceneCommandRegistry;
+use crate::domain::gift::{GiftReaction, PreferenceJournal};
+use crate::domain::localization::Localization;
+use crate::domain::scenario::DialogueBlock;
+use crate::infrastructure::gift_data::{GiftReactionLines, GIFT_REACTION_DIRECTORY};
+use super::ui_components::{GameAssets, GameMode, GameScreen};
+use super::ui_utils::display_name;
+
+/// 各列の左端のx座標（渡す相手・アイテム・好みの手帳）
+const RECEIVER_COLUMN_X: f32 = -820.0;
+const ITEM_COLUMN_X: f32 = -420.0;
+const JOURNAL_COLUMN_X: f32 = 180.0;
+/// 各列の見出しのy座標と行の高さ
+const COLUMN_TOP: f32 = 360.0;
+const ROW_HEIGHT: f32 = 52.0;
+/// 反応セリフ欄の位置と大きさ
+const REACTION_BOX_CENTER: Vec2 = Vec2::new(0.0, -330.0);
+const REACTION_BOX_SIZE: Vec2 = Vec2::new(1680.0, 200.0);
+/// プレゼント画面の描画順（ストーリー画面より手前）
+const GIFT_Z: f32 = 30.0;
+/// 選択中の行の色
+const SELECTED_COLOR: Color = Color::srgb(1.0, 0.85, 0.4);
+
+/// プレゼント画面の表示状態（Bevy Resource）
+#[derive(Resource, Debug, Default)]
+pub struct GiftUiState {
+    /// 選択中の渡す相手の番号
+    pub receiver: usize,
+    /// 選択中のアイテムの番号
+    pub item: usize,
+    /// 直前に渡したプレゼントへの反応
+    pub reaction_text: Option<String>,
+    /// 画面を開いてから渡した回数（反応セリフの選択に使う）
+    pub gifts_given: usize,
+    /// 反応セリフ（初めて開いたときに読み込み、以後は使い回す）
+    pub reaction_lines: Option<GiftReactionLines>,
+    pub is_open: bool,
+}
+
+/// 反応セリフの読み込みに使うリソース（受け取る仲間・コマンド・話者の照合）
+#[derive(SystemParam)]
+pub struct GiftReactionSources<'w> {
+    gifts: Res<'w, GiftService>,
+    command_registry: Res<'w, SceneCommandRegistry>,
+    characters: Res<'w, CharacterRegistry>,
+}
+
+/// プレゼントの受け渡しで更新するリソース（関係値の変動と渡した場所）
+#[derive(SystemParam)]
+pub struct GiftExchange<'w> {
+    gifts: ResMut<'w, GiftService>,
+    relationships: ResMut<'w, RelationshipService>,
+    scenario_state: Res<'w, MarkdownScenarioState>,
+}
+
+/// プレゼント画面の要素（閉じるときにまとめて破棄）
+#[derive(Component)]
+pub struct GiftElement;
+
+/// 選択状態に応じて描き直す部分
+#[derive(Component)]
+pub struct GiftPanel;
+
+/// 反応の表示名のキー
+fn reaction_key(reaction: GiftReaction) -> &'static str {
+    match reaction {
+        GiftReaction::Favorite => "gift.reaction.favorite",
+        GiftReaction::Normal => "gift.reaction.normal",
+        GiftReaction::Disliked => "gift.reaction.disliked",
+    }
+}
+
+/// 反応セリフを選ぶ（渡すたびに順に切り替える）
+pub fn pick_reaction_line(lines: &[DialogueBlock], count: usize) -> Option<&DialogueBlock> {
+    if lines.is_empty() {
+        return None;
+    }
+    lines.get(count % lines.len())
+}
+
+/// 好みの手帳の行（種類と、判明していればその反応）
+pub fn journal_rows<'a>(
+    categories: &[&'a str],
+    journal: &PreferenceJournal,
+    character_id: &str,
+) -> Vec<(&'a str, Option<GiftReaction>)> {
+    categories
+        .iter()
+        .map(|category| (*category, journal.known(character_id, category)))
+        .collect()
+}
+
+/// 反応の表示文（セリフと、初めて判明した好み）
+fn reaction_text(outcome: &GiftOutcome, line: Option<&DialogueBlock>, localization: &Localization) -> String {
+    let mut text = match line {
+        Some(block) => match &block.speaker {
+            Some(speaker) => format!("{}「{}」", localization.speaker_name(speaker), localization.line_text(block)),
+            None => localization.line_text(block).to_string(),
+        },
+        None => outcome.item.name.clone(),
+    };
+    if outcome.newly_learned {
+        let category = localization.ui(&format!("gift.category.{}", outcome.item.category)).to_string();
+        text.push_str(&format!(
+            "\n{}: {} = {}",
+            localization.ui("gift.learned"),
+            category,
+            localization.ui(reaction_key(outcome.reaction)),
+        ));
+    }
+    text
+}
+
+/// 1行分の文字を生成
+fn spawn_label(parent: &mut ChildBuilder, assets: &GameAssets, text: &str, size: f32, color: Color, position: Vec2) {
+    parent.spawn((
+        Text2d::new(text),
+        TextFont {
+            font: assets.font_fallback.font_for_text(&assets.main_font, text),
+            font_size: size,
+            ..default()
+        },
+        TextColor(color),
+        Anchor::CenterLeft,
+        Transform::from_translation(position.extend(1.0)),
+    ));
+}
+
+/// プレゼント画面を開いたときに画面を構築するシステム
+pub fn gift_setup_system(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    localization: Res<Localization>,
+    game_assets: Option<Res<GameAssets>>,
+    sources: GiftReactionSources,
+    mut state: ResMut<GiftUiState>,
+) {
+    if game_mode.current_screen != GameScreen::Gift || state.is_open {
+        return;
+    }
+    let Some(assets) = game_assets else { return; };
+
+    if state.reaction_lines.is_none() {
+        state.reaction_lines = Some(GiftReactionLines::load(
+            Path::new(GIFT_REACTION_DIRECTORY),
+            &sources.gifts.receivers(),
+            &sources.command_registry,
+            &sources.characters,
+        ));
+    }
+
+    commands
+        .spawn((
+            Sprite::from_color(Color::srgba(0.05, 0.03, 0.06, 0.95), Vec2::new(1920.0, 1080.0)),
+            Transform::from_xyz(0.0, 0.0, GIFT_Z),
+            GiftElement,
+        ))
+        .with_children(|parent| {
+            let title = localization.ui("gift.title");
+            parent.spawn((
+                Text2d::new(title),
+                TextFont {
+                    font: assets.font_fallback.font_for_text(&assets.main_font, title),
+                    font_size: 40.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Transform::from_xyz(0.0, 480.0, 5.0),
+            ));
+
+            let help = localization.ui("gift.help");
+            parent.spawn((
+                Text2d::new(help),
+                TextFont {
+                    font: assets.font_fallback.font_for_text(&assets.main_font, help),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                Transform::from_xyz(0.0, -500.0, 5.0),
+            ));
+
+            parent.spawn((
+                Sprite::from_color(Color::srgba(0.12, 0.1, 0.16, 0.95), REACTION_BOX_SIZE),
+                Transform::from_translation(REACTION_BOX_CENTER.extend(1.0)),
+            ));
+
+            parent.spawn((Transform::from_xyz(0.0, 0.0, 2.0), Visibility::default(), GiftPanel));
+        });
+
+    state.receiver = 0;
+    state.item = 0;
+    state.reaction_text = None;
+    state.gifts_given = 0;
+    state.is_open = true;
+}
+
+/// プレゼント画面の操作システム（相手・アイテムの選択、受け渡し、閉じる）
+pub fn gift_input_system(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut game_mode: ResMut<GameMode>,
+    mut state: ResMut<GiftUiState>,
+    mut exchange: GiftExchange,
+    localization: Res<Localization>,
+    element_query: Query<Entity, With<GiftElement>>,
+) {
+    if !state.is_open {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) || keyboard_input.just_pressed(KeyCode::Backspace) {
+        println!("🎁 プレゼント画面を閉じる");
+        for entity in element_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        state.is_open = false;
+        game_mode.current_screen = GameScreen::Story;
+        return;
+    }
+
+    let GiftExchange { gifts, relationships, scenario_state } = &mut exchange;
+    let receiver_count = gifts.receivers().len();
+    let item_count = gifts.catalog().items().len();
+    if keyboard_input.just_pressed(KeyCode::ArrowRight) && receiver_count > 0 {
+        state.receiver = (state.receiver + 1) % receiver_count;
+    } else if keyboard_input.just_pressed(KeyCode::ArrowLeft) && receiver_count > 0 {
+        state.receiver = (state.receiver + receiver_count - 1) % receiver_count;
+    } else if keyboard_input.just_pressed(KeyCode::ArrowDown) && item_count > 0 {
+        state.item = (state.item + 1) % item_count;
+    } else if keyboard_input.just_pressed(KeyCode::ArrowUp) && item_count > 0 {
+        state.item = (state.item + item_count - 1) % item_count;
+    }
+
+    if !(keyboard_input.just_pressed(KeyCode::Enter) || keyboard_input.just_pressed(KeyCode::Space)) {
+        return;
+    }
+    let Some(receiver) = gifts.receivers().get(state.receiver).map(|receiver| receiver.to_string()) else { return; };
+    let Some(item_id) = gifts.catalog().items().get(state.item).map(|item| item.id.clone()) else { return; };
+
+    match gifts.give_gift(relationships, &receiver, &item_id, scenario_state.current_location()) {
+        Ok(outcome) => {
+            println!(
+                "🎁 {} に {} を渡した: {:?} ({} → {})",
+                receiver, outcome.item.name, outcome.reaction, outcome.old_value, outcome.new_value
+            );
+            let lines = state
+                .reaction_lines
+                .as_ref()
+                .map(|reaction_lines| reaction_lines.lines(&receiver, outcome.reaction))
+                .unwrap_or(&[]);
+            let text = reaction_text(&outcome, pick_reaction_line(lines, state.gifts_given), &localization);
+            state.reaction_text = Some(text);
+            state.gifts_given += 1;
+        }
+        Err(e) => eprintln!("⚠️ プレゼントを渡せません: {}", e),
+    }
+}
+
+/// 選択中の相手・アイテム、好みの手帳、反応セリフの描き直し
+pub fn gift_panel_render_system(
+    mut commands: Commands,
+    state: Res<GiftUiState>,
+    gifts: Res<GiftService>,
+    characters: Res<CharacterRegistry>,
+    localization: Res<Localization>,
+    game_assets: Option<Res<GameAssets>>,
+    panel_query: Query<Entity, With<GiftPanel>>,
+) {
+    if !state.is_changed() || !state.is_open {
+        return;
+    }
+    let Some(assets) = game_assets else { return; };
+    let Ok(panel) = panel_query.get_single() else { return; };
+
+    let receivers = gifts.receivers();
+    let categories = gifts.catalog().categories();
+    let heading_color = Color::srgb(0.75, 0.75, 0.85);
+    let row_color = |selected: bool| if selected { SELECTED_COLOR } else { Color::WHITE };
+
+    commands.entity(panel).despawn_descendants();
+    commands.entity(panel).with_children(|parent| {
+        // 渡す相手
+        spawn_label(parent, &assets, localization.ui("gift.receivers"), 26.0, heading_color, Vec2::new(RECEIVER_COLUMN_X, COLUMN_TOP));
+        for (row, receiver) in receivers.iter().enumerate() {
+            let name = display_name(receiver, &characters, &localization);
+            let y = COLUMN_TOP - ROW_HEIGHT * (row + 1) as f32;
+            spawn_label(parent, &assets, &name, 24.0, row_color(row == state.receiver), Vec2::new(RECEIVER_COLUMN_X, y));
+        }
+
+        // アイテム
+        spawn_label(parent, &assets, localization.ui("gift.items"), 26.0, heading_color, Vec2::new(ITEM_COLUMN_X, COLUMN_TOP));
+        for (row, item) in gifts.catalog().items().iter().enumerate() {
+            let y = COLUMN_TOP - ROW_HEIGHT * (row + 1) as f32;
+            spawn_label(parent, &assets, &item.name, 24.0, row_color(row == state.item), Vec2::new(ITEM_COLUMN_X, y));
+        }
+
+        // 好みの手帳（選択中の相手の判明した好みだけを表示）
+        let Some(receiver) = receivers.get(state.receiver) else { return; };
+        let heading = format!(
+            "{} - {}",
+            localization.ui("gift.journal"),
+            display_name(receiver, &characters, &localization),
+        );
+        spawn_label(parent, &assets, &heading, 26.0, heading_color, Vec2::new(JOURNAL_COLUMN_X, COLUMN_TOP));
+        for (row, (category, known)) in journal_rows(&categories, gifts.journal(), receiver).into_iter().enumerate() {
+            let y = COLUMN_TOP - ROW_HEIGHT * (row + 1) as f32;
+            let category_key = format!("gift.category.{}", category);
+            let text = format!(
+                "{}　{}",
+                localization.ui(&category_key),
+                known.map_or(localization.ui("gift.unknown"), |reaction| localization.ui(reaction_key(reaction))),
+            );
+            let color = match known {
+                Some(GiftReaction::Favorite) => Color::srgb(0.4, 0.9, 0.4),
+                Some(GiftReaction::Disliked) => Color::srgb(0.9, 0.4, 0.4),
+                Some(GiftReaction::Normal) => Color::WHITE,
+                None => Color::srgb(0.5, 0.5, 0.5),
+            };
+            spawn_label(parent, &assets, &text, 22.0, color, Vec2::new(JOURNAL_COLUMN_X, y));
+        }
+
+        // 反応セリフ
+        if let Some(text) = &state.reaction_text {
+            let position = REACTION_BOX_CENTER + Vec2::new(-REACTION_BOX_SIZE.x / 2.0 + 40.0, 0.0);
+            spawn_label(parent, &assets, text, 26.0, Color::WHITE, position);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::gift::GiftItem;
+    use std::collections::HashMap;
+
+    #[test]
+    fn journal_rows_hide_unknown_preferences() {
+        let mut journal = PreferenceJournal::default();
+        journal.learn("yuzuki", "flowers", GiftReaction::Favorite);
+
+        let rows = journal_rows(&["books", "flowers"], &journal, "yuzuki");
+        assert_eq!(rows, vec![("books", None), ("flowers", Some(GiftReaction::Favorite))]);
+        assert!(journal_rows(&["flowers"], &journal, "kai").iter().all(|(_, known)| known.is_none()));
+    }
+
+    #[test]
+    fn reaction_lines_rotate() {
+        let lines: Vec<DialogueBlock> = ["**ユズキ**「ありがとう」", "**ユズキ**「大事にするね」"]
+            .iter()
+            .filter_map(|line| DialogueBlock::parse(line))
+            .collect();
+
+        assert_eq!(pick_reaction_line(&lines, 0).unwrap().text, "ありがとう");
+        assert_eq!(pick_reaction_line(&lines, 3).unwrap().text, "大事にするね");
+        assert!(pick_reaction_line(&[], 0).is_none());
+    }
+
+    #[test]
+    fn reaction_text_mentions_newly_learned_preference() {
+        let localization = Localization::new("ja", HashMap::new(), HashMap::new());
+        let line = DialogueBlock::parse("**ユズキ**「わあ、嬉しい！」").unwrap();
+        let mut outcome = GiftOutcome {
+            receiver: "yuzuki".to_string(),
+            item: GiftItem::new("wildflowers", "野の花の花束", "flowers"),
+            reaction: GiftReaction::Favorite,
+            old_value: 0,
+            new_value: 10,
+            newly_learned: true,
+        };
+
+        let text = reaction_text(&outcome, Some(&line), &localization);
+        assert!(text.starts_with("ユズキ「わあ、嬉しい！」"));
+        assert!(text.contains("花 = 好物"));
+
+        outcome.newly_learned = false;
+        assert_eq!(reaction_text(&outcome, Some(&line), &localization), "ユズキ「わあ、嬉しい！」");
+    }
+}
@@ -9,6 +9,11 @@
 //! - テキスト表示システム（text_systems）
 //! - ダイアログUI（dialogue_ui）
 //! - 戦闘UI（battle_ui）
+//! - フォント設定・フォールバック（fonts）
+//! - 関係値変動の通知表示（relationship_toast）
+//! - 相関図画面（relationship_chart）
+//! - プレゼント画面（gift_ui）
+//! - 設定画面とプレイヤー設定の保存（settings_ui）
 
 pub mod ui_components;
 pub mod ui_utils;
@@ -18,3 +23,8 @@ pub mod systems;
 pub mod text_systems;
 pub mod dialogue_ui;
 pub mod battle_ui;
+pub mod fonts;
+pub mod relationship_toast;
+pub mod relationship_chart;
+pub mod gift_ui;
+pub mod settings_ui;
@@ -0,0 +1,568 @@
+//! 相関図画面 - キャラクター同士の関係値をノードと線で表示
+//!
+//! # 責務
+//! - 関係値サービスとペア定義から相関図（ノード・線）を組み立てる
+//! - 線は関係レベルで色分けし、推奨ペアにだけ矢印を付ける（特別な要素の有無は詳細パネルに表示）
+//! - キーボード・マウスでのノード選択と詳細パネル（数値とバー）の表示
+//!
+//! 拠点でサイトウに話しかけると `[relationship_chart]` コマンドで開く
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use std::collections::{BTreeMap, BTreeSet};
+use crate::application::services::RelationshipService;
+use crate::domain::character::CharacterRegistry;
+use crate::domain::localization::Localization;
+use crate::domain::relationship::{PairKey, RelationshipLevel, RELATIONSHIP_LIMIT};
+use super::ui_components::{GameAssets, GameMode, GameScreen};
+use super::ui_utils::{display_name, CursorClick};
+
+/// ノードを並べる円の中心と半径
+const CHART_CENTER: Vec2 = Vec2::new(-260.0, -20.0);
+const CHART_RADIUS: f32 = 330.0;
+/// ノードの大きさ
+const NODE_SIZE: Vec2 = Vec2::new(150.0, 56.0);
+/// 線をノードの手前で止める距離
+const NODE_GAP: f32 = 60.0;
+/// 線の太さ
+const EDGE_WIDTH: f32 = 4.0;
+/// 矢印の羽の長さと開き（ラジアン）
+const ARROW_LENGTH: f32 = 22.0;
+const ARROW_SPREAD: f32 = 0.45;
+/// 詳細パネルの位置と大きさ
+const PANEL_CENTER: Vec2 = Vec2::new(640.0, -20.0);
+const PANEL_SIZE: Vec2 = Vec2::new(520.0, 860.0);
+const PANEL_ROW_HEIGHT: f32 = 96.0;
+/// 関係値バーの大きさ（中央が 0、左右の端が ±100）
+const BAR_WIDTH: f32 = 240.0;
+const BAR_HEIGHT: f32 = 14.0;
+/// 相関図の描画順（ストーリー画面より手前）
+const CHART_Z: f32 = 30.0;
+
+/// 相関図の線（ペア1組）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartEdge {
+    pub pair: PairKey,
+    pub value: i32,
+    pub level: RelationshipLevel,
+    /// 推奨ペア（矢印を付ける）
+    pub is_recommended: bool,
+    /// 推奨・対立・専用イベントのいずれかがある（詳細パネルに表示）
+    pub has_special_content: bool,
+}
+
+/// 相関図（ノードと線）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelationshipChart {
+    /// キャラクターID（ID順）
+    pub nodes: Vec<String>,
+    /// 線（ペア順）
+    pub edges: Vec<ChartEdge>,
+}
+
+impl RelationshipChart {
+    /// 作成済みの関係値とペア定義から組み立てる（未作成のペアは初期値で表示）
+    pub fn build(relationships: &RelationshipService) -> Self {
+        let mut values: BTreeMap<PairKey, i32> = relationships.get_all_relationships().into_iter().collect();
+        for definition in relationships.pair_table().definitions() {
+            values.entry(definition.key.clone()).or_insert(definition.initial_value);
+        }
+
+        let edges: Vec<ChartEdge> = values
+            .into_iter()
+            .map(|(pair, value)| {
+                let definition = relationships.pair_definition(pair.first(), pair.second());
+                ChartEdge {
+                    value,
+                    level: relationships.get_relationship_level(pair.first(), pair.second()),
+                    is_recommended: definition.is_some_and(|definition| definition.is_recommended),
+                    has_special_content: definition.is_some_and(|definition| definition.has_special_content()),
+                    pair,
+                }
+            })
+            .collect();
+
+        let nodes: BTreeSet<String> = edges
+            .iter()
+            .flat_map(|edge| [edge.pair.first().to_string(), edge.pair.second().to_string()])
+            .collect();
+
+        Self {
+            nodes: nodes.into_iter().collect(),
+            edges,
+        }
+    }
+
+    /// キャラクターに繋がる線
+    pub fn edges_of<'a>(&'a self, character_id: &'a str) -> impl Iterator<Item = &'a ChartEdge> + 'a {
+        self.edges.iter().filter(move |edge| edge.pair.contains(character_id))
+    }
+
+    /// キャラクターのノード番号
+    pub fn node_index(&self, character_id: &str) -> Option<usize> {
+        self.nodes.iter().position(|node| node == character_id)
+    }
+}
+
+/// 相関図の表示状態（Bevy Resource）
+#[derive(Resource, Debug, Default)]
+pub struct RelationshipChartState {
+    pub chart: RelationshipChart,
+    /// 選択中のノード番号
+    pub selected: usize,
+    pub is_open: bool,
+}
+
+/// 相関図の要素（閉じるときにまとめて破棄）
+#[derive(Component)]
+pub struct RelationshipChartElement;
+
+/// 相関図のノード
+#[derive(Component)]
+pub struct ChartNode {
+    pub index: usize,
+}
+
+/// 相関図の線・矢印
+#[derive(Component)]
+pub struct ChartEdgeView {
+    pub pair: PairKey,
+    pub level: RelationshipLevel,
+}
+
+/// 選択中のキャラクターの詳細パネル
+#[derive(Component)]
+pub struct ChartDetailPanel;
+
+/// 選択に合わせて色を変えるノードと線のスプライト
+#[derive(SystemParam)]
+pub struct ChartSprites<'w, 's> {
+    nodes: Query<'w, 's, (&'static ChartNode, &'static mut Sprite)>,
+    edges: Query<'w, 's, (&'static ChartEdgeView, &'static mut Sprite), Without<ChartNode>>,
+}
+
+/// ノードの配置（円周上、真上から時計回り）
+pub fn node_positions(count: usize) -> Vec<Vec2> {
+    (0..count)
+        .map(|index| {
+            let angle = std::f32::consts::FRAC_PI_2 - std::f32::consts::TAU * index as f32 / count as f32;
+            CHART_CENTER + Vec2::from_angle(angle) * CHART_RADIUS
+        })
+        .collect()
+}
+
+/// 2点を結ぶ線分の中心・長さ・角度
+pub fn line_segment(from: Vec2, to: Vec2) -> (Vec2, f32, f32) {
+    let delta = to - from;
+    ((from + to) / 2.0, delta.length(), delta.y.atan2(delta.x))
+}
+
+/// 矢印の羽（先端から後ろへ開く2本の線分の端点）
+pub fn arrow_head(tip: Vec2, direction: Vec2) -> [(Vec2, Vec2); 2] {
+    let back = -direction.normalize_or_zero() * ARROW_LENGTH;
+    [
+        (tip, tip + Vec2::from_angle(ARROW_SPREAD).rotate(back)),
+        (tip, tip + Vec2::from_angle(-ARROW_SPREAD).rotate(back)),
+    ]
+}
+
+/// 関係値バーの塗り部分（中心からのずれと幅）
+pub fn value_bar(value: i32) -> (f32, f32) {
+    let ratio = value.clamp(-RELATIONSHIP_LIMIT, RELATIONSHIP_LIMIT) as f32 / RELATIONSHIP_LIMIT as f32;
+    let width = ratio.abs() * BAR_WIDTH / 2.0;
+    (ratio.signum() * width / 2.0, width)
+}
+
+/// 関係レベルの表示色（親密は緑、対立は赤、通常はグレー）
+pub fn level_color(level: RelationshipLevel) -> Color {
+    match level {
+        RelationshipLevel::Intimate => Color::srgb(0.3, 0.8, 0.3),
+        RelationshipLevel::Conflict => Color::srgb(0.8, 0.3, 0.3),
+        RelationshipLevel::Normal => Color::srgb(0.7, 0.7, 0.7),
+    }
+}
+
+/// 関係レベルの表示名のキー
+fn level_key(level: RelationshipLevel) -> &'static str {
+    match level {
+        RelationshipLevel::Intimate => "chart.level.intimate",
+        RelationshipLevel::Normal => "chart.level.normal",
+        RelationshipLevel::Conflict => "chart.level.conflict",
+    }
+}
+
+/// 選択を前後に動かす（端で反対側に回り込む）
+pub fn step_selection(current: usize, count: usize, forward: bool) -> usize {
+    if count == 0 {
+        return 0;
+    }
+    if forward {
+        (current + 1) % count
+    } else {
+        (current + count - 1) % count
+    }
+}
+
+/// ワールド座標の位置にあるノード
+pub fn node_at(point: Vec2, positions: &[Vec2]) -> Option<usize> {
+    positions.iter().position(|center| {
+        (point.x - center.x).abs() <= NODE_SIZE.x / 2.0 && (point.y - center.y).abs() <= NODE_SIZE.y / 2.0
+    })
+}
+
+/// 線分のスプライトを生成
+fn spawn_segment(parent: &mut ChildBuilder, from: Vec2, to: Vec2, width: f32, color: Color, view: ChartEdgeView) {
+    let (center, length, angle) = line_segment(from, to);
+    parent.spawn((
+        Sprite::from_color(color, Vec2::new(length, width)),
+        Transform::from_translation(center.extend(1.0)).with_rotation(Quat::from_rotation_z(angle)),
+        view,
+    ));
+}
+
+/// 相関図を開いたときに画面を構築するシステム
+pub fn relationship_chart_setup_system(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    relationships: Res<RelationshipService>,
+    characters: Res<CharacterRegistry>,
+    localization: Res<Localization>,
+    game_assets: Option<Res<GameAssets>>,
+    mut state: ResMut<RelationshipChartState>,
+) {
+    if game_mode.current_screen != GameScreen::RelationshipChart || state.is_open {
+        return;
+    }
+    let Some(assets) = game_assets else { return; };
+
+    let chart = RelationshipChart::build(&relationships);
+    let positions = node_positions(chart.nodes.len());
+    println!("🕸️ 相関図を構築: {}人・{}組", chart.nodes.len(), chart.edges.len());
+
+    commands
+        .spawn((
+            Sprite::from_color(Color::srgba(0.02, 0.02, 0.08, 0.95), Vec2::new(1920.0, 1080.0)),
+            Transform::from_xyz(0.0, 0.0, CHART_Z),
+            RelationshipChartElement,
+        ))
+        .with_children(|parent| {
+            let title = localization.ui("chart.title");
+            parent.spawn((
+                Text2d::new(title),
+                TextFont {
+                    font: assets.font_fallback.font_for_text(&assets.main_font, title),
+                    font_size: 40.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Transform::from_xyz(0.0, 480.0, 5.0),
+            ));
+
+            let help = localization.ui("chart.help");
+            parent.spawn((
+                Text2d::new(help),
+                TextFont {
+                    font: assets.font_fallback.font_for_text(&assets.main_font, help),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                Transform::from_xyz(0.0, -500.0, 5.0),
+            ));
+
+            // 線（ノードの手前で止め、推奨ペアは両端に矢印）
+            for edge in &chart.edges {
+                let (Some(a), Some(b)) = (chart.node_index(edge.pair.first()), chart.node_index(edge.pair.second())) else {
+                    continue;
+                };
+                let direction = (positions[b] - positions[a]).normalize_or_zero();
+                let from = positions[a] + direction * NODE_GAP;
+                let to = positions[b] - direction * NODE_GAP;
+                let color = level_color(edge.level);
+                let view = || ChartEdgeView { pair: edge.pair.clone(), level: edge.level };
+
+                spawn_segment(parent, from, to, EDGE_WIDTH, color, view());
+                if edge.is_recommended {
+                    for (tip, direction) in [(to, direction), (from, -direction)] {
+                        for (start, end) in arrow_head(tip, direction) {
+                            spawn_segment(parent, start, end, EDGE_WIDTH, color, view());
+                        }
+                    }
+                }
+
+                let label = format!("{:+}", edge.value);
+                parent.spawn((
+                    Text2d::new(&label),
+                    TextFont {
+                        font: assets.number_font.clone(),
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(color),
+                    Transform::from_translation(((from + to) / 2.0 + Vec2::new(0.0, 14.0)).extend(3.0)),
+                ));
+            }
+
+            // ノード
+            for (index, (character_id, position)) in chart.nodes.iter().zip(&positions).enumerate() {
+                let name = display_name(character_id, &characters, &localization);
+                parent
+                    .spawn((
+                        Sprite::from_color(Color::srgba(0.2, 0.2, 0.3, 0.95), NODE_SIZE),
+                        Transform::from_translation(position.extend(4.0)),
+                        ChartNode { index },
+                    ))
+                    .with_children(|node| {
+                        node.spawn((
+                            Text2d::new(&name),
+                            TextFont {
+                                font: assets.font_fallback.font_for_text(&assets.name_font, &name),
+                                font_size: 24.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            Transform::from_xyz(0.0, 0.0, 1.0),
+                        ));
+                    });
+            }
+
+            parent.spawn((
+                Sprite::from_color(Color::srgba(0.08, 0.08, 0.16, 0.95), PANEL_SIZE),
+                Transform::from_translation(PANEL_CENTER.extend(2.0)),
+                ChartDetailPanel,
+            ));
+        });
+
+    state.chart = chart;
+    state.selected = 0;
+    state.is_open = true;
+}
+
+/// 相関図の操作システム（ノード選択・閉じる）
+pub fn relationship_chart_input_system(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    cursor: CursorClick,
+    mut game_mode: ResMut<GameMode>,
+    mut state: ResMut<RelationshipChartState>,
+    element_query: Query<Entity, With<RelationshipChartElement>>,
+) {
+    if !state.is_open {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) || keyboard_input.just_pressed(KeyCode::Backspace) {
+        println!("🕸️ 相関図を閉じる");
+        for entity in element_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        state.is_open = false;
+        game_mode.current_screen = GameScreen::Story;
+        return;
+    }
+
+    let count = state.chart.nodes.len();
+    if keyboard_input.just_pressed(KeyCode::ArrowRight) || keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        state.selected = step_selection(state.selected, count, true);
+    } else if keyboard_input.just_pressed(KeyCode::ArrowLeft) || keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        state.selected = step_selection(state.selected, count, false);
+    }
+
+    let Some(world_position) = cursor.clicked_position() else { return; };
+
+    if let Some(index) = node_at(world_position, &node_positions(count)) {
+        if index != state.selected {
+            state.selected = index;
+        }
+    }
+}
+
+/// 選択中のノードの強調と詳細パネルの更新
+pub fn relationship_chart_selection_system(
+    mut commands: Commands,
+    state: Res<RelationshipChartState>,
+    characters: Res<CharacterRegistry>,
+    localization: Res<Localization>,
+    game_assets: Option<Res<GameAssets>>,
+    mut sprites: ChartSprites,
+    panel_query: Query<Entity, With<ChartDetailPanel>>,
+) {
+    if !state.is_changed() || !state.is_open {
+        return;
+    }
+    let Some(assets) = game_assets else { return; };
+    let Some(selected_id) = state.chart.nodes.get(state.selected) else { return; };
+
+    for (node, mut sprite) in sprites.nodes.iter_mut() {
+        sprite.color = if node.index == state.selected {
+            Color::srgba(0.9, 0.7, 0.3, 0.95)
+        } else {
+            Color::srgba(0.2, 0.2, 0.3, 0.95)
+        };
+    }
+
+    // 選択中のキャラクターに繋がる線以外は薄くする
+    for (view, mut sprite) in sprites.edges.iter_mut() {
+        let alpha = if view.pair.contains(selected_id) { 1.0 } else { 0.3 };
+        sprite.color = level_color(view.level).with_alpha(alpha);
+    }
+
+    let Ok(panel) = panel_query.get_single() else { return; };
+    commands.entity(panel).despawn_descendants();
+    commands.entity(panel).with_children(|parent| {
+        let top = PANEL_SIZE.y / 2.0 - 50.0;
+        let left = -PANEL_SIZE.x / 2.0 + 30.0;
+
+        let name = display_name(selected_id, &characters, &localization);
+        parent.spawn((
+            Text2d::new(&name),
+            TextFont {
+                font: assets.font_fallback.font_for_text(&assets.name_font, &name),
+                font_size: 32.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            Anchor::CenterLeft,
+            Transform::from_xyz(left, top, 1.0),
+        ));
+
+        for (row, edge) in state.chart.edges_of(selected_id).enumerate() {
+            let y = top - 70.0 - row as f32 * PANEL_ROW_HEIGHT;
+            let partner = edge.pair.partner_of(selected_id).unwrap_or_default();
+            let color = level_color(edge.level);
+
+            let summary = format!(
+                "{}　{:+}　{}",
+                display_name(partner, &characters, &localization),
+                edge.value,
+                localization.ui(level_key(edge.level)),
+            );
+            parent.spawn((
+                Text2d::new(&summary),
+                TextFont {
+                    font: assets.font_fallback.font_for_text(&assets.main_font, &summary),
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(color),
+                Anchor::CenterLeft,
+                Transform::from_xyz(left, y, 1.0),
+            ));
+
+            let hint = localization.ui(if edge.has_special_content { "chart.special" } else { "chart.no_special" });
+            parent.spawn((
+                Text2d::new(hint),
+                TextFont {
+                    font: assets.font_fallback.font_for_text(&assets.main_font, hint),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.75, 0.75, 0.75)),
+                Anchor::CenterLeft,
+                Transform::from_xyz(left, y - 30.0, 1.0),
+            ));
+
+            // 関係値バー（中央が 0）
+            let bar_center = Vec2::new(PANEL_SIZE.x / 2.0 - 30.0 - BAR_WIDTH / 2.0, y - 30.0);
+            let (offset, width) = value_bar(edge.value);
+            parent.spawn((
+                Sprite::from_color(Color::srgba(0.3, 0.3, 0.35, 0.9), Vec2::new(BAR_WIDTH, BAR_HEIGHT)),
+                Transform::from_translation(bar_center.extend(1.0)),
+            ));
+            parent.spawn((
+                Sprite::from_color(color, Vec2::new(width, BAR_HEIGHT)),
+                Transform::from_xyz(bar_center.x + offset, bar_center.y, 2.0),
+            ));
+            parent.spawn((
+                Sprite::from_color(Color::WHITE, Vec2::new(2.0, BAR_HEIGHT + 6.0)),
+                Transform::from_translation(bar_center.extend(3.0)),
+            ));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::relationship::{PairDefinition, RelationshipPairTable};
+
+    fn sample_service() -> RelationshipService {
+        let mut table = RelationshipPairTable::default();
+        let mut recommended = PairDefinition::new("souma", "yuzuki");
+        recommended.is_recommended = true;
+        table.insert(recommended);
+        let mut rival = PairDefinition::new("souma", "kai");
+        rival.initial_value = -25;
+        rival.is_opposed = true;
+        table.insert(rival);
+
+        let mut service = RelationshipService::new();
+        service.set_pair_table(table);
+        service.modify_relationship("souma", "yuzuki", 60);
+        service.modify_relationship("retsuji", "yuzuki", -10);
+        service
+    }
+
+    #[test]
+    fn chart_includes_defined_and_created_pairs() {
+        let chart = RelationshipChart::build(&sample_service());
+
+        assert_eq!(chart.nodes, vec!["kai", "retsuji", "souma", "yuzuki"]);
+        assert_eq!(chart.edges.len(), 3);
+
+        let souma_yuzuki = chart.edges.iter().find(|edge| edge.pair == PairKey::new("souma", "yuzuki")).unwrap();
+        assert_eq!(souma_yuzuki.value, 60);
+        assert_eq!(souma_yuzuki.level, RelationshipLevel::Intimate);
+        assert!(souma_yuzuki.is_recommended);
+        assert!(souma_yuzuki.has_special_content);
+
+        // 未作成のペアは初期値、推奨ペア以外は特別な要素（対立）があっても矢印なし
+        let souma_kai = chart.edges.iter().find(|edge| edge.pair == PairKey::new("kai", "souma")).unwrap();
+        assert_eq!(souma_kai.value, -25);
+        assert!(!souma_kai.is_recommended);
+        assert!(souma_kai.has_special_content);
+        let retsuji_yuzuki = chart.edges.iter().find(|edge| edge.pair.contains("retsuji")).unwrap();
+        assert!(!retsuji_yuzuki.is_recommended);
+        assert!(!retsuji_yuzuki.has_special_content);
+
+        assert_eq!(chart.edges_of("souma").count(), 2);
+    }
+
+    #[test]
+    fn node_layout_and_hit_test() {
+        let positions = node_positions(4);
+        assert!((positions[0] - (CHART_CENTER + Vec2::new(0.0, CHART_RADIUS))).length() < 1e-3);
+        assert!((positions[1] - (CHART_CENTER + Vec2::new(CHART_RADIUS, 0.0))).length() < 1e-3);
+
+        assert_eq!(node_at(positions[2] + Vec2::new(10.0, -5.0), &positions), Some(2));
+        assert_eq!(node_at(CHART_CENTER, &positions), None);
+    }
+
+    #[test]
+    fn selection_wraps_around() {
+        assert_eq!(step_selection(3, 4, true), 0);
+        assert_eq!(step_selection(0, 4, false), 3);
+        assert_eq!(step_selection(1, 4, true), 2);
+        assert_eq!(step_selection(0, 0, true), 0);
+    }
+
+    #[test]
+    fn segments_arrows_and_bars() {
+        let (center, length, angle) = line_segment(Vec2::ZERO, Vec2::new(0.0, 10.0));
+        assert_eq!(center, Vec2::new(0.0, 5.0));
+        assert_eq!(length, 10.0);
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+
+        // 右向きの矢印の羽は先端より左に開く
+        for (tip, end) in arrow_head(Vec2::ZERO, Vec2::X) {
+            assert_eq!(tip, Vec2::ZERO);
+            assert!(end.x < 0.0);
+            assert!(((end - tip).length() - ARROW_LENGTH).abs() < 1e-3);
+        }
+
+        assert_eq!(value_bar(0), (0.0, 0.0));
+        assert_eq!(value_bar(100), (BAR_WIDTH / 4.0, BAR_WIDTH / 2.0));
+        assert_eq!(value_bar(-50), (-BAR_WIDTH / 8.0, BAR_WIDTH / 4.0));
+    }
+}
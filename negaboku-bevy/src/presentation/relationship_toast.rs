@@ -0,0 +1,244 @@
+//! 関係値変動の通知 - ストーリー画面の右上に出るトースト表示
+//!
+//! # 責務
+//! - `RelationshipChanged` イベントからトーストを生成（例: 「ソウマ⇔ユズキ ↑10」）
+//! - 親密・対立に入った／抜けた変動は画面上部中央に強調表示
+//! - スライドイン・フェードアウトのアニメーションと破棄
+
+use bevy::prelude::*;
+use crate::application::services::RelationshipChanged;
+use crate::domain::character::CharacterRegistry;
+use crate::domain::relationship::RelationshipLevel;
+use super::ui_components::{GameAssets, GameMode, StoryScreenElement};
+
+/// 通常トーストの表示時間（秒）
+const TOAST_DURATION: f32 = 2.5;
+/// 強調トーストの表示時間（秒）
+const PROMINENT_TOAST_DURATION: f32 = 3.5;
+/// スライドインにかける時間（秒）
+const SLIDE_IN_TIME: f32 = 0.25;
+/// フェードアウトにかける時間（秒）
+const FADE_OUT_TIME: f32 = 0.5;
+/// スライドインの移動距離（px）
+const SLIDE_DISTANCE: f32 = 120.0;
+
+/// 通常トーストの位置（右上、下方向に積む）
+const TOAST_X: f32 = 760.0;
+const TOAST_TOP_Y: f32 = 440.0;
+const TOAST_SPACING: f32 = 56.0;
+const TOAST_SIZE: Vec2 = Vec2::new(340.0, 46.0);
+
+/// 強調トーストの位置（上部中央）
+const PROMINENT_TOAST_Y: f32 = 380.0;
+const PROMINENT_TOAST_SIZE: Vec2 = Vec2::new(560.0, 64.0);
+
+/// 関係値変動のトースト
+#[derive(Component, Debug)]
+pub struct RelationshipToast {
+    pub elapsed: f32,
+    pub duration: f32,
+    /// 右上に積む段（強調トーストは 0 固定）
+    pub slot: usize,
+    /// 親密・対立の出入りを知らせる強調表示か
+    pub is_prominent: bool,
+}
+
+/// トーストの文字（表示名で「ソウマ⇔ユズキ ↑10」の形式）
+pub fn toast_text(event: &RelationshipChanged, characters: &CharacterRegistry) -> String {
+    let name = |id: &str| characters.get(id).map(|character| character.name.clone()).unwrap_or_else(|| id.to_string());
+    let delta = event.delta();
+    let arrow = if delta >= 0 { "↑" } else { "↓" };
+    format!("{}⇔{} {}{}", name(&event.a), name(&event.b), arrow, delta.abs())
+}
+
+/// 親密・対立に入った／抜けたときの強調表示の文言（それ以外は None）
+pub fn level_change_notice(old: RelationshipLevel, new: RelationshipLevel) -> Option<&'static str> {
+    match (old, new) {
+        (old, new) if old == new => None,
+        (_, RelationshipLevel::Intimate) => Some("親密になった！"),
+        (_, RelationshipLevel::Conflict) => Some("対立してしまった…"),
+        (RelationshipLevel::Intimate, _) => Some("親密ではなくなった"),
+        (RelationshipLevel::Conflict, _) => Some("対立が解けた"),
+        _ => None,
+    }
+}
+
+/// 経過時間からトーストの横方向のずれと不透明度を求める
+///
+/// 最初の `SLIDE_IN_TIME` 秒で右から滑り込み、最後の `FADE_OUT_TIME` 秒で消える
+pub fn toast_motion(elapsed: f32, duration: f32) -> (f32, f32) {
+    let slide = (elapsed / SLIDE_IN_TIME).clamp(0.0, 1.0);
+    let offset = (1.0 - slide) * SLIDE_DISTANCE;
+    let alpha = ((duration - elapsed) / FADE_OUT_TIME).clamp(0.0, 1.0);
+    (offset, alpha.min(slide))
+}
+
+/// 強調トーストの背景色（親密は金、対立は赤、解消はグレー）
+fn prominent_color(new_level: RelationshipLevel) -> Color {
+    match new_level {
+        RelationshipLevel::Intimate => Color::srgba(0.85, 0.65, 0.15, 0.9),
+        RelationshipLevel::Conflict => Color::srgba(0.75, 0.2, 0.2, 0.9),
+        RelationshipLevel::Normal => Color::srgba(0.4, 0.4, 0.45, 0.9),
+    }
+}
+
+/// トーストの基準位置
+fn toast_position(toast: &RelationshipToast) -> Vec3 {
+    if toast.is_prominent {
+        Vec3::new(0.0, PROMINENT_TOAST_Y, 20.0)
+    } else {
+        Vec3::new(TOAST_X, TOAST_TOP_Y - toast.slot as f32 * TOAST_SPACING, 20.0)
+    }
+}
+
+/// 関係値変動イベントからトーストを生成するシステム（ストーリー画面のみ）
+pub fn relationship_toast_spawn_system(
+    mut commands: Commands,
+    mut changed_events: EventReader<RelationshipChanged>,
+    game_mode: Res<GameMode>,
+    characters: Res<CharacterRegistry>,
+    game_assets: Option<Res<GameAssets>>,
+    toast_query: Query<&RelationshipToast>,
+) {
+    if !game_mode.is_story_mode {
+        changed_events.clear();
+        return;
+    }
+    let Some(assets) = game_assets else {
+        changed_events.clear();
+        return;
+    };
+
+    let mut next_slot = toast_query
+        .iter()
+        .filter(|toast| !toast.is_prominent)
+        .map(|toast| toast.slot + 1)
+        .max()
+        .unwrap_or(0);
+
+    for event in changed_events.read().filter(|event| event.notify) {
+        let mut text = toast_text(event, &characters);
+        let notice = level_change_notice(event.old_level, event.new_level);
+        if let Some(notice) = notice {
+            text = format!("{}　{}", text, notice);
+        }
+
+        let toast = if notice.is_some() {
+            RelationshipToast { elapsed: 0.0, duration: PROMINENT_TOAST_DURATION, slot: 0, is_prominent: true }
+        } else {
+            next_slot += 1;
+            RelationshipToast { elapsed: 0.0, duration: TOAST_DURATION, slot: next_slot - 1, is_prominent: false }
+        };
+        let (background, size, font_size) = if toast.is_prominent {
+            (prominent_color(event.new_level), PROMINENT_TOAST_SIZE, 26.0)
+        } else {
+            (Color::srgba(0.1, 0.1, 0.2, 0.85), TOAST_SIZE, 20.0)
+        };
+        let position = toast_position(&toast);
+
+        commands
+            .spawn((
+                Sprite::from_color(background.with_alpha(0.0), size),
+                Transform::from_translation(position + Vec3::X * SLIDE_DISTANCE),
+                toast,
+                StoryScreenElement,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(&text),
+                    TextFont {
+                        font: assets.font_fallback.font_for_text(&assets.main_font, &text),
+                        font_size,
+                        ..default()
+                    },
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    TextColor(Color::WHITE.with_alpha(0.0)),
+                    Transform::from_xyz(0.0, 0.0, 1.0),
+                ));
+            });
+    }
+}
+
+/// トーストのスライドイン・フェードアウトと破棄
+pub fn relationship_toast_animation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut RelationshipToast, &mut Transform, &mut Sprite, &Children)>,
+    mut text_query: Query<&mut TextColor>,
+) {
+    for (entity, mut toast, mut transform, mut sprite, children) in toast_query.iter_mut() {
+        toast.elapsed += time.delta_secs();
+        if toast.elapsed >= toast.duration {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let (offset, alpha) = toast_motion(toast.elapsed, toast.duration);
+        transform.translation = toast_position(&toast) + Vec3::X * offset;
+
+        let max_alpha = if toast.is_prominent { 0.9 } else { 0.85 };
+        sprite.color.set_alpha(alpha * max_alpha);
+        for &child in children.iter() {
+            if let Ok(mut text_color) = text_query.get_mut(child) {
+                text_color.0.set_alpha(alpha);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::relationship_history::ChangeSource;
+
+    fn changed(old: i32, new: i32, old_level: RelationshipLevel, new_level: RelationshipLevel) -> RelationshipChanged {
+        RelationshipChanged {
+            a: "souma".to_string(),
+            b: "yuzuki".to_string(),
+            old,
+            new,
+            old_level,
+            new_level,
+            reason: String::new(),
+            source: ChangeSource::Choice,
+            notify: true,
+        }
+    }
+
+    #[test]
+    fn toast_text_uses_display_names() {
+        let mut characters = CharacterRegistry::new();
+        characters.register_default_characters();
+
+        let up = changed(0, 10, RelationshipLevel::Normal, RelationshipLevel::Normal);
+        assert_eq!(toast_text(&up, &characters), "ソウマ⇔ユズキ ↑10");
+
+        let down = changed(10, 0, RelationshipLevel::Normal, RelationshipLevel::Normal);
+        assert_eq!(toast_text(&down, &characters), "ソウマ⇔ユズキ ↓10");
+
+        // 未登録のキャラクターはIDで表示
+        assert_eq!(toast_text(&up, &CharacterRegistry::new()), "souma⇔yuzuki ↑10");
+    }
+
+    #[test]
+    fn level_change_notice_only_for_threshold_crossings() {
+        use RelationshipLevel::*;
+        assert_eq!(level_change_notice(Normal, Normal), None);
+        assert_eq!(level_change_notice(Normal, Intimate), Some("親密になった！"));
+        assert_eq!(level_change_notice(Normal, Conflict), Some("対立してしまった…"));
+        assert_eq!(level_change_notice(Intimate, Normal), Some("親密ではなくなった"));
+        assert_eq!(level_change_notice(Conflict, Normal), Some("対立が解けた"));
+    }
+
+    #[test]
+    fn toast_motion_slides_in_and_fades_out() {
+        let (offset, alpha) = toast_motion(0.0, TOAST_DURATION);
+        assert_eq!((offset, alpha), (SLIDE_DISTANCE, 0.0));
+
+        let (offset, alpha) = toast_motion(1.0, TOAST_DURATION);
+        assert_eq!((offset, alpha), (0.0, 1.0));
+
+        let (_, alpha) = toast_motion(TOAST_DURATION - FADE_OUT_TIME / 2.0, TOAST_DURATION);
+        assert!((alpha - 0.5).abs() < 1e-5);
+    }
+}
@@ -6,26 +6,32 @@ use bevy::prelude::*;
 use super::ui_components::*;
 use crate::domain::character::CharacterRegistry;
 use crate::application::command_executor::BackgroundImage;
+use crate::domain::line_break::LineBreakRule;
+use crate::domain::localization::Localization;
+use super::fonts::{FontFallback, FontRole, FontSettings, FONT_CONFIG_PATH};
+use bevy::sprite::Anchor;
 
 pub fn setup_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // フォント設定（存在するNotoSansJPを使用）
-    let font_path = "fonts/NotoSansJP-VariableFont_wght.ttf";
+    // フォント設定（用途別フォントとフォールバック順）
+    let font_settings = FontSettings::load_or_default(std::path::Path::new(FONT_CONFIG_PATH));
     let background_path = "images/backgrounds/souma_home.png";
     let character_path = "images/characters/01_souma_kari.png";
 
     // ファイル存在確認
-    let font_full_path = format!("assets/{}", font_path);
     let bg_full_path = format!("assets/{}", background_path);
     let char_full_path = format!("assets/{}", character_path);
 
-    if !std::path::Path::new(&font_full_path).exists() {
-        eprintln!("❌ フォントファイルが見つかりません: {}", font_full_path);
-        eprintln!("💡 以下のコマンドでフォントを配置してください:");
-        eprintln!("   mkdir -p assets/fonts");
-        eprintln!("   cp path/to/NotoSansJP-VariableFont_wght.ttf assets/fonts/");
-    } else {
-        println!("✅ フォントファイルを確認: {}", font_full_path);
-    }
+    let font_fallback = FontFallback::load(&font_settings, &asset_server);
+    let role_font = |role: FontRole| -> Handle<Font> {
+        let path = font_settings.path(role);
+        font_fallback.font_or_fallback(path).unwrap_or_else(|| {
+            eprintln!("❌ フォントファイルが見つかりません: assets/{}", path);
+            eprintln!("💡 以下のコマンドでフォントを配置してください:");
+            eprintln!("   mkdir -p assets/fonts");
+            eprintln!("   cp path/to/NotoSansJP-VariableFont_wght.ttf assets/fonts/");
+            asset_server.load(path)
+        })
+    };
 
     if !std::path::Path::new(&bg_full_path).exists() {
         eprintln!("❌ 背景画像が見つかりません: {}", bg_full_path);
@@ -46,15 +52,32 @@ pub fn setup_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
     }
 
     // アセット読み込み
-    let main_font = asset_server.load(font_path);
+    let main_font = role_font(FontRole::Ui);
+    let dialogue_font = role_font(FontRole::Dialogue);
+    let name_font = role_font(FontRole::Name);
+    let number_font = role_font(FontRole::BattleNumber);
+
+    // 強調表示用フォント（配置されていなければ本文フォントで代用）
+    let bold_font = if std::path::Path::new(&format!("assets/{}", font_settings.bold)).exists() {
+        asset_server.load(font_settings.bold.as_str())
+    } else {
+        println!("ℹ️ 強調用フォントがないため本文フォントで代用: assets/{}", font_settings.bold);
+        dialogue_font.clone()
+    };
     let background_souma_home = asset_server.load(background_path);
     let character_souma = asset_server.load(character_path);
 
     commands.insert_resource(GameAssets {
         main_font,
+        dialogue_font,
+        name_font,
+        number_font,
+        bold_font,
+        font_fallback,
         background_souma_home,
         character_souma,
     });
+    commands.insert_resource(font_settings);
 
     println!("🔄 アセット読み込み開始: フォント、背景、キャラクター");
 }
@@ -65,7 +88,7 @@ pub fn setup_character_registry(mut character_registry: ResMut<CharacterRegistry
 }
 
 /// ビジュアルノベル風UI構築関数
-pub fn setup_visual_novel_ui(commands: &mut Commands, assets: &Res<GameAssets>) {
+pub fn setup_visual_novel_ui(commands: &mut Commands, assets: &Res<GameAssets>, localization: &Localization) {
     println!("🖼️ 背景画像スプライトを作成中...");
 
     // 0. 背景画像を最背面に全画面表示（Z座標: -10.0）
@@ -110,46 +133,59 @@ pub fn setup_visual_novel_ui(commands: &mut Commands, assets: &Res<GameAssets>)
     // 2. テキストボックスを半透明背景・角丸風にスタイリング（Z座標: 1.0）
     let textbox_height = 1080.0 * 0.25; // 270pxに増大（より見やすく）
     let textbox_y = -(1080.0 / 2.0) + (textbox_height / 2.0); // -405px
+    let textbox_width = 1800.0;
+    let text_padding = 80.0;
+    let text_left = -(textbox_width / 2.0) + text_padding; // -820px（本文・名前の左端）
 
     commands.spawn((
         Sprite::from_color(
             Color::srgba(0.0, 0.0, 0.1, 0.85),
-            Vec2::new(1800.0, textbox_height),
+            Vec2::new(textbox_width, textbox_height),
         ), // 幅を少し狭く、濃い背景
         Transform::from_xyz(0.0, textbox_y, 1.0),
         VNTextBox,
         StoryScreenElement,
     ));
 
-    // 3. キャラクター名表示（白・太字・大きく右に移動）（Z座標: 2.0）
+    // 3. キャラクター名表示（白・太字・本文と左端を揃える）（Z座標: 2.0）
     commands.spawn((
         Text2d::new("ソウマ"),
         TextFont {
-            font: assets.main_font.clone(),
+            font: assets.name_font.clone(),
             font_size: 32.0, // フォントサイズを少し大きく
             ..default()
         },
         TextLayout::new_with_justify(JustifyText::Left),
         TextColor(Color::srgb(1.0, 1.0, 1.0)),
-        Transform::from_xyz(-500.0, textbox_y + 60.0, 2.0), // 大きく右に移動、下に移動
+        Anchor::CenterLeft,
+        Transform::from_xyz(text_left, textbox_y + 60.0, 2.0),
         VNCharacterName {
             name: "ソウマ".to_string(),
+            character_id: Some("souma".to_string()),
         },
         StoryScreenElement,
     ));
 
-    // 4. 本文表示（白・標準・大きく右に移動、下に移動）（Z座標: 2.0）
+    // 4. 本文表示（白・標準・左端揃え、下に移動）（Z座標: 2.0）
+    // テキストボックス幅で事前に折り返す（禁則処理付き）
+    let dialogue_font_size = 22.0; // フォントサイズを少し大きく
+    let line_break = LineBreakRule::new(textbox_width - text_padding * 2.0, dialogue_font_size);
     let vn_dialogue_entity = commands.spawn((
         Text2d::new(""),
         TextFont {
-            font: assets.main_font.clone(),
-            font_size: 22.0, // フォントサイズを少し大きく
+            font: assets.dialogue_font.clone(),
+            font_size: dialogue_font_size,
             ..default()
         },
         TextLayout::new_with_justify(JustifyText::Left),
         TextColor(Color::srgb(1.0, 1.0, 1.0)),
-        Transform::from_xyz(-480.0, textbox_y - 20.0, 2.0),  // 大きく右に移動（-780→-480）、下に移動（+10→-20）
-        VNDialogue::new("願い石と僕たちの絆へようこそ！\nここは新しい冒険の始まりです。\nスペースキーで進めます。".to_string()),
+        Anchor::CenterLeft,
+        Transform::from_xyz(text_left, textbox_y - 20.0, 2.0),
+        VNDialogue::with_line_break(
+            "願い石と僕たちの絆へようこそ！\nここは新しい冒険の始まりです。\nスペースキーで進めます。".to_string(),
+            line_break,
+        ),
+        VNDialogueView::default(),
         StoryScreenElement,
     )).id();
 
@@ -167,7 +203,7 @@ pub fn setup_visual_novel_ui(commands: &mut Commands, assets: &Res<GameAssets>)
     ));
 
     commands.spawn((
-        Text2d::new("ログ"),
+        Text2d::new(localization.ui("story.log")),
         TextFont {
             font: assets.main_font.clone(),
             font_size: 18.0,
@@ -187,14 +223,14 @@ pub fn setup_visual_novel_ui(commands: &mut Commands, assets: &Res<GameAssets>)
         VNButtonType::Load,
     ];
 
-    let button_texts = vec!["スキップ", "オート", "セーブ", "ロード"];
+    let button_keys = vec!["story.skip", "story.auto", "story.save", "story.load"];
     let button_width = 100.0; // 幅を少し大きく
     let button_height = 50.0; // 高さを少し大きく
     let button_spacing = 120.0; // 間隔を少し幅広く
     let start_x = 1920.0 / 2.0 - 480.0; // 右下にしっかり配置
 
-    for (index, (button_type, button_text)) in
-        button_types.into_iter().zip(button_texts).enumerate()
+    for (index, (button_type, button_key)) in
+        button_types.into_iter().zip(button_keys).enumerate()
     {
         let x_pos = start_x + (index as f32 * button_spacing);
         let y_pos = textbox_y - 100.0; // テキストボックスの下、より遠くに
@@ -212,7 +248,7 @@ pub fn setup_visual_novel_ui(commands: &mut Commands, assets: &Res<GameAssets>)
 
         // ボタンテキスト
         commands.spawn((
-            Text2d::new(button_text),
+            Text2d::new(localization.ui(button_key)),
             TextFont {
                 font: assets.main_font.clone(),
                 font_size: 18.0, // フォントサイズを少し大きく
@@ -227,7 +263,7 @@ pub fn setup_visual_novel_ui(commands: &mut Commands, assets: &Res<GameAssets>)
 
     // 操作説明を画面下部に追加
     commands.spawn((
-        Text2d::new("操作: Spaceキー = 次へ / Lキー or ログボタン = ログ表示 / Escキー = タイトルに戻る"),
+        Text2d::new(localization.ui("story.help")),
         TextFont {
             font: assets.main_font.clone(),
             font_size: 16.0,
@@ -241,5 +277,5 @@ pub fn setup_visual_novel_ui(commands: &mut Commands, assets: &Res<GameAssets>)
 
     println!("🎨 ビジュアルノベル風UI構築完了（背景・立ち絵・テキストウィンドウ・操作ボタン）");
     println!("📖 テキストログ機能: Lキーまたはログボタンで表示/非表示切り替え");
-    println!("💡 操作方法: Spaceキーでテキスト進行、完了後にログに自動追加");
+    println!("💡 操作方法: Spaceキーでテキスト進行、完了後にログに自動追加、F5キーでセーブ");
 }
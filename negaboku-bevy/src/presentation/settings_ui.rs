@@ -0,0 +1,411 @@
+//! 設定画面 - プレイヤー設定の変更と保存
+//!
+//! # 責務
+//! - タイトルの「設定」から開く設定画面の構築と操作
+//! - 話者の強調表示・非話者の暗転の切り替え
+//! - 表示言語の切り替え（翻訳の読み込み直し）
+//! - プレイヤー設定ファイル（`saves/settings.json`）の読み込みと保存
+//!
+//! 設定は画面を閉じたときに保存し、次回起動時に読み込む。
+//! 起動時の `--locale` 指定は保存した表示言語より優先する
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::domain::localization::{Localization, SOURCE_LOCALE};
+use crate::infrastructure::translation::{available_locales, load_localization, LOCALE_DIRECTORY};
+use super::systems::spawn_title_screen;
+use super::ui_components::{GameAssets, GameMode, GameScreen, SpeakerDisplaySettings, TitleScreenElement};
+
+/// プレイヤー設定ファイルのパス
+pub const PLAYER_SETTINGS_PATH: &str = "saves/settings.json";
+
+/// 設定項目の左端のx座標と、先頭行のy座標・行の高さ
+const ITEM_COLUMN_X: f32 = -420.0;
+const VALUE_COLUMN_X: f32 = 220.0;
+const ROW_TOP: f32 = 260.0;
+const ROW_HEIGHT: f32 = 64.0;
+/// 設定画面の描画順（タイトル画面より手前）
+const SETTINGS_Z: f32 = 30.0;
+/// 選択中の行の色
+const SELECTED_COLOR: Color = Color::srgb(1.0, 0.85, 0.4);
+
+/// プレイヤー設定ファイルの内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerSettings {
+    pub speaker_display: SpeakerDisplaySettings,
+    /// 表示言語（`ja`, `en` など）
+    pub locale: String,
+}
+
+impl Default for PlayerSettings {
+    fn default() -> Self {
+        Self {
+            speaker_display: SpeakerDisplaySettings::default(),
+            locale: SOURCE_LOCALE.to_string(),
+        }
+    }
+}
+
+impl PlayerSettings {
+    /// 設定ファイルを読み込む（ファイルがない・読めない場合は既定値）
+    pub fn load_or_default(path: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str(&content) {
+            Ok(settings) => {
+                println!("⚙️ プレイヤー設定を読み込みました: {:?}", path);
+                settings
+            }
+            Err(e) => {
+                eprintln!("⚠️ プレイヤー設定を読み込めません（既定値を使用）{:?}: {}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// 設定ファイルに保存
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("{:?}: {}", dir, e))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, content).map_err(|e| format!("{:?}: {}", path, e))
+    }
+}
+
+/// 設定項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsItem {
+    /// 話者の立ち絵を強調表示する
+    HighlightSpeaker,
+    /// 話者以外の立ち絵を暗くする
+    DimNonSpeakers,
+    /// 表示言語
+    Locale,
+}
+
+/// 設定画面の項目（表示順）
+pub const SETTINGS_ITEMS: [SettingsItem; 3] = [
+    SettingsItem::HighlightSpeaker,
+    SettingsItem::DimNonSpeakers,
+    SettingsItem::Locale,
+];
+
+impl SettingsItem {
+    /// 項目名のキー
+    fn label_key(self) -> &'static str {
+        match self {
+            SettingsItem::HighlightSpeaker => "settings.highlight_speaker",
+            SettingsItem::DimNonSpeakers => "settings.dim_non_speakers",
+            SettingsItem::Locale => "settings.locale",
+        }
+    }
+
+    /// オン・オフの項目の値を切り替える（表示言語は `next_locale` で切り替える）
+    pub fn toggle(self, speaker_display: &mut SpeakerDisplaySettings) {
+        match self {
+            SettingsItem::HighlightSpeaker => speaker_display.highlight_speaker = !speaker_display.highlight_speaker,
+            SettingsItem::DimNonSpeakers => speaker_display.dim_non_speakers = !speaker_display.dim_non_speakers,
+            SettingsItem::Locale => {}
+        }
+    }
+
+    /// 項目の値の表示文
+    fn value_text(self, speaker_display: &SpeakerDisplaySettings, localization: &Localization) -> String {
+        let enabled = match self {
+            SettingsItem::HighlightSpeaker => speaker_display.highlight_speaker,
+            SettingsItem::DimNonSpeakers => speaker_display.dim_non_speakers,
+            SettingsItem::Locale => return locale_name(localization, &localization.locale),
+        };
+        localization.ui(if enabled { "settings.on" } else { "settings.off" }).to_string()
+    }
+}
+
+/// 表示言語の名前（名前の文字列がなければロケール名そのもの）
+fn locale_name(localization: &Localization, locale: &str) -> String {
+    let key = format!("settings.locale.{}", locale);
+    match localization.ui(&key) {
+        name if name == key => locale.to_string(),
+        name => name.to_string(),
+    }
+}
+
+/// 選択できる表示言語の中で、現在の言語の次（`forward` が false なら前）の言語
+pub fn next_locale(locales: &[String], current: &str, forward: bool) -> String {
+    if locales.is_empty() {
+        return current.to_string();
+    }
+    let next = match locales.iter().position(|locale| locale == current) {
+        Some(index) if forward => (index + 1) % locales.len(),
+        Some(index) => (index + locales.len() - 1) % locales.len(),
+        None => 0,
+    };
+    locales[next].clone()
+}
+
+/// 設定画面の表示状態（Bevy Resource）
+#[derive(Resource, Debug, Default)]
+pub struct SettingsUiState {
+    /// 選択中の項目の番号
+    pub cursor: usize,
+    /// 選択できる表示言語（開いたときに翻訳ディレクトリから列挙）
+    pub locales: Vec<String>,
+    /// 開いたときの表示言語（閉じるときにタイトル画面を作り直すか判定する）
+    pub opened_locale: String,
+    pub is_open: bool,
+}
+
+/// 設定画面の要素（閉じるときにまとめて破棄）
+#[derive(Component)]
+pub struct SettingsElement;
+
+/// 選択状態・設定値に応じて描き直す部分
+#[derive(Component)]
+pub struct SettingsPanel;
+
+/// 1行分の文字を生成
+fn spawn_label(parent: &mut ChildBuilder, assets: &GameAssets, text: &str, size: f32, color: Color, position: Vec2) {
+    parent.spawn((
+        Text2d::new(text),
+        TextFont {
+            font: assets.font_fallback.font_for_text(&assets.main_font, text),
+            font_size: size,
+            ..default()
+        },
+        TextColor(color),
+        Anchor::CenterLeft,
+        Transform::from_translation(position.extend(1.0)),
+    ));
+}
+
+/// 設定画面を開いたときに画面を構築するシステム
+pub fn settings_setup_system(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    localization: Res<Localization>,
+    game_assets: Option<Res<GameAssets>>,
+    mut state: ResMut<SettingsUiState>,
+) {
+    if game_mode.current_screen != GameScreen::Settings || state.is_open || game_assets.is_none() {
+        return;
+    }
+
+    commands
+        .spawn((
+            Sprite::from_color(Color::srgba(0.04, 0.04, 0.08, 0.95), Vec2::new(1920.0, 1080.0)),
+            Transform::from_xyz(0.0, 0.0, SETTINGS_Z),
+            SettingsElement,
+        ))
+        .with_children(|parent| {
+            parent.spawn((Transform::from_xyz(0.0, 0.0, 2.0), Visibility::default(), SettingsPanel));
+        });
+
+    state.cursor = 0;
+    state.locales = available_locales(Path::new(LOCALE_DIRECTORY));
+    state.opened_locale = localization.locale.clone();
+    state.is_open = true;
+}
+
+/// 設定画面で変更するプレイヤー設定（話者表示・表示言語）
+#[derive(SystemParam)]
+pub struct EditablePlayerSettings<'w> {
+    speaker_display: ResMut<'w, SpeakerDisplaySettings>,
+    localization: ResMut<'w, Localization>,
+}
+
+impl EditablePlayerSettings<'_> {
+    /// 保存用のプレイヤー設定
+    fn to_player_settings(&self) -> PlayerSettings {
+        PlayerSettings {
+            speaker_display: self.speaker_display.clone(),
+            locale: self.localization.locale.clone(),
+        }
+    }
+}
+
+/// 表示言語を変えたときに作り直すタイトル画面
+#[derive(SystemParam)]
+pub struct TitleScreenRebuild<'w, 's> {
+    game_assets: Option<Res<'w, GameAssets>>,
+    title_query: Query<'w, 's, Entity, With<TitleScreenElement>>,
+}
+
+impl TitleScreenRebuild<'_, '_> {
+    /// タイトル画面の文字を現在の表示言語で作り直す
+    fn rebuild(&self, commands: &mut Commands, localization: &Localization) {
+        let Some(assets) = &self.game_assets else { return; };
+        for entity in self.title_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        spawn_title_screen(commands, assets, localization);
+    }
+}
+
+/// 設定画面の操作システム（項目の選択・切り替え、閉じるときに保存）
+pub fn settings_input_system(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut game_mode: ResMut<GameMode>,
+    mut state: ResMut<SettingsUiState>,
+    mut settings: EditablePlayerSettings,
+    title_screen: TitleScreenRebuild,
+    element_query: Query<Entity, With<SettingsElement>>,
+) {
+    // 開いたフレームの決定キーはタイトルメニューの操作なので無視する
+    if !state.is_open || game_mode.is_changed() {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) || keyboard_input.just_pressed(KeyCode::Backspace) {
+        println!("⚙️ 設定画面を閉じる");
+        if let Err(e) = settings.to_player_settings().save(Path::new(PLAYER_SETTINGS_PATH)) {
+            eprintln!("⚠️ プレイヤー設定を保存できません: {}", e);
+        }
+        for entity in element_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        // 表示言語を変えた場合はタイトル画面の文字を作り直す
+        if settings.localization.locale != state.opened_locale {
+            title_screen.rebuild(&mut commands, &settings.localization);
+        }
+        state.is_open = false;
+        game_mode.current_screen = GameScreen::Title;
+        return;
+    }
+
+    let count = SETTINGS_ITEMS.len();
+    if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        state.cursor = (state.cursor + 1) % count;
+    } else if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        state.cursor = (state.cursor + count - 1) % count;
+    }
+
+    let backward = keyboard_input.just_pressed(KeyCode::ArrowLeft);
+    let toggled = backward
+        || [KeyCode::Enter, KeyCode::Space, KeyCode::ArrowRight]
+            .iter()
+            .any(|key| keyboard_input.just_pressed(*key));
+    if !toggled {
+        return;
+    }
+
+    let EditablePlayerSettings { speaker_display, localization } = &mut settings;
+    match SETTINGS_ITEMS[state.cursor] {
+        SettingsItem::Locale => {
+            let locale = next_locale(&state.locales, &localization.locale, !backward);
+            println!("⚙️ 表示言語: {} -> {}", localization.locale, locale);
+            **localization = load_localization(Path::new(LOCALE_DIRECTORY), &locale);
+        }
+        item => {
+            item.toggle(speaker_display);
+            println!("⚙️ {:?}: {:?}", item, **speaker_display);
+        }
+    }
+}
+
+/// 見出し・設定項目と現在の値の描き直し（表示言語を切り替えたときも描き直す）
+pub fn settings_panel_render_system(
+    mut commands: Commands,
+    state: Res<SettingsUiState>,
+    speaker_display: Res<SpeakerDisplaySettings>,
+    localization: Res<Localization>,
+    game_assets: Option<Res<GameAssets>>,
+    panel_query: Query<Entity, With<SettingsPanel>>,
+) {
+    if !state.is_open || !(state.is_changed() || speaker_display.is_changed() || localization.is_changed()) {
+        return;
+    }
+    let Some(assets) = game_assets else { return; };
+    let Ok(panel) = panel_query.get_single() else { return; };
+
+    commands.entity(panel).despawn_descendants();
+    commands.entity(panel).with_children(|parent| {
+        let title = localization.ui("settings.title");
+        parent.spawn((
+            Text2d::new(title),
+            TextFont {
+                font: assets.font_fallback.font_for_text(&assets.main_font, title),
+                font_size: 40.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            Transform::from_xyz(0.0, 420.0, 1.0),
+        ));
+
+        let help = localization.ui("settings.help");
+        parent.spawn((
+            Text2d::new(help),
+            TextFont {
+                font: assets.font_fallback.font_for_text(&assets.main_font, help),
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            Transform::from_xyz(0.0, -500.0, 1.0),
+        ));
+
+        for (row, item) in SETTINGS_ITEMS.iter().enumerate() {
+            let color = if row == state.cursor { SELECTED_COLOR } else { Color::WHITE };
+            let y = ROW_TOP - ROW_HEIGHT * row as f32;
+            spawn_label(parent, &assets, localization.ui(item.label_key()), 28.0, color, Vec2::new(ITEM_COLUMN_X, y));
+            let value = item.value_text(&speaker_display, &localization);
+            spawn_label(parent, &assets, &value, 28.0, color, Vec2::new(VALUE_COLUMN_X, y));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggling_items_changes_speaker_display() {
+        let mut speaker_display = SpeakerDisplaySettings::default();
+
+        SettingsItem::DimNonSpeakers.toggle(&mut speaker_display);
+        assert!(!speaker_display.dim_non_speakers);
+        assert!(speaker_display.highlight_speaker);
+
+        SettingsItem::HighlightSpeaker.toggle(&mut speaker_display);
+        SettingsItem::DimNonSpeakers.toggle(&mut speaker_display);
+        assert!(!speaker_display.highlight_speaker);
+        assert!(speaker_display.dim_non_speakers);
+    }
+
+    #[test]
+    fn player_settings_round_trip() {
+        let path = std::env::temp_dir().join(format!("negaboku_settings_{}.json", std::process::id()));
+        let mut settings = PlayerSettings::default();
+        settings.speaker_display.dim_non_speakers = false;
+        settings.locale = "en".to_string();
+
+        settings.save(&path).unwrap();
+        assert_eq!(PlayerSettings::load_or_default(&path), settings);
+        std::fs::remove_file(&path).unwrap();
+
+        // 項目が欠けた古い設定ファイルは既定値で補う
+        let partial: PlayerSettings = serde_json::from_str(r#"{ "speaker_display": { "highlight_speaker": false } }"#).unwrap();
+        assert!(!partial.speaker_display.highlight_speaker);
+        assert!(partial.speaker_display.dim_non_speakers);
+        assert_eq!(partial.locale, SOURCE_LOCALE);
+        assert_eq!(PlayerSettings::load_or_default(Path::new("no/such/settings.json")), PlayerSettings::default());
+    }
+
+    #[test]
+    fn locale_cycles_through_available_locales() {
+        let locales = vec!["ja".to_string(), "en".to_string()];
+
+        assert_eq!(next_locale(&locales, "ja", true), "en");
+        assert_eq!(next_locale(&locales, "en", true), "ja");
+        assert_eq!(next_locale(&locales, "ja", false), "en");
+        // 一覧にない言語からは先頭に戻る
+        assert_eq!(next_locale(&locales, "fr", true), "ja");
+    }
+}
@@ -3,7 +3,11 @@
 //! UI・入力・表示に関連するECSシステムを定義
 
 use bevy::prelude::*;
+use std::path::Path;
 use crate::application::scenario_system::MarkdownScenarioState;
+use crate::application::services::{GiftService, RelationshipService};
+use crate::infrastructure::save_data::{self, SaveData, QUICK_SAVE_SLOT, SAVE_DIRECTORY};
+use crate::domain::localization::Localization;
 use crate::presentation::ui_components::*;
 use crate::presentation::ui_utils::{
     next_background, get_current_background,
@@ -20,6 +24,7 @@ pub fn menu_input_system(
     mut background_query: Query<&mut BackgroundController>,
     mut scenario_state: ResMut<ScenarioState>,
     assets: Res<GameAssets>,
+    localization: Res<Localization>,
     title_elements_query: Query<Entity, With<TitleScreenElement>>,
     markdown_state: Res<MarkdownScenarioState>,
 ) {
@@ -36,7 +41,7 @@ pub fn menu_input_system(
         if keyboard_input.just_pressed(KeyCode::ArrowUp) {
             if menu_cursor.current_index > 0 {
                 menu_cursor.current_index -= 1;
-                let button_name = get_button_text_by_index(menu_cursor.current_index);
+                let button_name = get_button_text_by_index(&localization, menu_cursor.current_index);
                 println!(
                     "↑キー: メニューカーソル位置 {} ({})",
                     menu_cursor.current_index, button_name
@@ -47,7 +52,7 @@ pub fn menu_input_system(
         if keyboard_input.just_pressed(KeyCode::ArrowDown) {
             if menu_cursor.current_index < menu_cursor.max_buttons - 1 {
                 menu_cursor.current_index += 1;
-                let button_name = get_button_text_by_index(menu_cursor.current_index);
+                let button_name = get_button_text_by_index(&localization, menu_cursor.current_index);
                 println!(
                     "↓キー: メニューカーソル位置 {} ({})",
                     menu_cursor.current_index, button_name
@@ -64,6 +69,7 @@ pub fn menu_input_system(
                 &mut game_mode,
                 &mut commands,
                 &assets,
+                &localization,
                 &title_elements_query,
             );
         } else if keyboard_input.just_pressed(KeyCode::Digit2) {
@@ -74,6 +80,7 @@ pub fn menu_input_system(
                 &mut game_mode,
                 &mut commands,
                 &assets,
+                &localization,
                 &title_elements_query,
             );
         } else if keyboard_input.just_pressed(KeyCode::Digit3) {
@@ -84,6 +91,7 @@ pub fn menu_input_system(
                 &mut game_mode,
                 &mut commands,
                 &assets,
+                &localization,
                 &title_elements_query,
             );
         } else if keyboard_input.just_pressed(KeyCode::Digit4) {
@@ -94,6 +102,7 @@ pub fn menu_input_system(
                 &mut game_mode,
                 &mut commands,
                 &assets,
+                &localization,
                 &title_elements_query,
             );
         } else if keyboard_input.just_pressed(KeyCode::Digit5) {
@@ -104,6 +113,7 @@ pub fn menu_input_system(
                 &mut game_mode,
                 &mut commands,
                 &assets,
+                &localization,
                 &title_elements_query,
             );
         }
@@ -116,6 +126,7 @@ pub fn menu_input_system(
                 &mut game_mode,
                 &mut commands,
                 &assets,
+                &localization,
                 &title_elements_query,
             );
         }
@@ -132,7 +143,9 @@ pub fn menu_input_system(
     }
 
     if keyboard_input.just_pressed(KeyCode::Escape) {
-        if game_mode.is_story_mode {
+        if matches!(game_mode.current_screen, GameScreen::RelationshipChart | GameScreen::Gift | GameScreen::Settings) {
+            // 相関図・プレゼント・設定画面はそれぞれの入力システムが閉じる
+        } else if game_mode.is_story_mode {
             println!("タイトルに戻ります");
             game_mode.is_story_mode = false;
             game_mode.current_screen = GameScreen::Title;
@@ -165,6 +178,7 @@ pub fn mouse_input_system(
     camera_query: Query<(&Camera, &GlobalTransform)>,
     mut button_query: Query<(&mut MenuButton, &Transform)>,
     assets: Res<GameAssets>,
+    localization: Res<Localization>,
     title_elements_query: Query<Entity, With<TitleScreenElement>>,
 ) {
     if !mouse_input.just_pressed(MouseButton::Left) {
@@ -217,6 +231,7 @@ pub fn mouse_input_system(
                                 &mut game_mode,
                                 &mut commands,
                                 &assets,
+                                &localization,
                                 &title_elements_query,
                             );
                             return;
@@ -236,6 +251,7 @@ pub fn handle_menu_selection(
     game_mode: &mut ResMut<GameMode>,
     commands: &mut Commands,
     assets: &Res<GameAssets>,
+    localization: &Localization,
     title_elements_query: &Query<Entity, With<TitleScreenElement>>,
 ) {
     let button_types = vec![
@@ -250,23 +266,26 @@ pub fn handle_menu_selection(
         match button_type {
             MenuButtonType::NewGame => {
                 println!("「はじめから」が選択されました - ゲーム開始！");
-                game_mode.is_story_mode = true;
-                game_mode.current_screen = GameScreen::Story;
-
-                // タイトル画面要素をすべてクリア
-                for entity in title_elements_query.iter() {
-                    commands.entity(entity).despawn_recursive();
-                }
-                println!("タイトル画面要素をクリアしました");
-
-                // ビジュアルノベル風UIを構築
-                super::screen_systems::setup_visual_novel_ui(commands, &assets);
+                start_story_screen(game_mode, commands, assets, localization, title_elements_query);
             }
             MenuButtonType::Continue => {
-                println!("「つづきから」が選択されました - 継続データ読み込み（未実装）");
+                println!("「つづきから」が選択されました - セーブデータを読み込みます");
+                let path = save_data::slot_path(Path::new(SAVE_DIRECTORY), QUICK_SAVE_SLOT);
+                match save_data::read_save(&path) {
+                    Ok(save) => {
+                        start_story_screen(game_mode, commands, assets, localization, title_elements_query);
+                        // 画面の構築後に、保存したシナリオの位置と関係値を復元
+                        commands.queue(move |world: &mut World| {
+                            if let Err(e) = save.resume_in_world(world) {
+                                eprintln!("❌ セーブデータから再開できません: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => println!("⚠️ つづきから: {}", e),
+                }
             }
             MenuButtonType::Settings => {
-                println!("「設定」が選択されました - 設定画面（未実装）");
+                println!("「設定」が選択されました - 設定画面を開きます");
                 game_mode.current_screen = GameScreen::Settings;
             }
             MenuButtonType::Gallery => {
@@ -281,6 +300,49 @@ pub fn handle_menu_selection(
     }
 }
 
+/// タイトル画面を閉じてストーリー画面を構築（「はじめから」「つづきから」共通）
+fn start_story_screen(
+    game_mode: &mut ResMut<GameMode>,
+    commands: &mut Commands,
+    assets: &Res<GameAssets>,
+    localization: &Localization,
+    title_elements_query: &Query<Entity, With<TitleScreenElement>>,
+) {
+    game_mode.is_story_mode = true;
+    game_mode.current_screen = GameScreen::Story;
+
+    // タイトル画面要素をすべてクリア
+    for entity in title_elements_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    println!("タイトル画面要素をクリアしました");
+
+    // ビジュアルノベル風UIを構築
+    super::screen_systems::setup_visual_novel_ui(commands, assets, localization);
+}
+
+/// セーブシステム（ストーリー画面でF5キーを押すと、進行位置・関係値・好みの手帳を保存）
+pub fn save_input_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    game_mode: Res<GameMode>,
+    scenario_state: Res<MarkdownScenarioState>,
+    relationships: Res<RelationshipService>,
+    gifts: Res<GiftService>,
+) {
+    if !game_mode.is_story_mode || game_mode.current_screen != GameScreen::Story || !keyboard_input.just_pressed(KeyCode::F5) {
+        return;
+    }
+
+    let save = SaveData::capture(&relationships)
+        .with_gift_journal(gifts.journal())
+        .with_scenario_position(&scenario_state);
+    let path = save_data::slot_path(Path::new(SAVE_DIRECTORY), QUICK_SAVE_SLOT);
+    match save_data::write_save(&path, &save) {
+        Ok(()) => println!("💾 セーブしました: {:?}", path),
+        Err(e) => eprintln!("❌ セーブに失敗しました: {}", e),
+    }
+}
+
 /// ボタン視覚システム
 pub fn button_visual_system(
     menu_cursor: Res<MenuCursor>,
@@ -446,6 +508,7 @@ pub fn initialization_system(
     mut app_state: ResMut<AppState>,
     assets: Option<Res<GameAssets>>,
     asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
     mut commands: Commands,
 ) {
     match app_state.init_state {
@@ -463,7 +526,7 @@ pub fn initialization_system(
         InitState::FontsReady => {
             // アセット準備完了後にUIを初期化
             if let Some(assets) = assets {
-                setup_ui_with_assets(&mut commands, &assets);
+                setup_ui_with_assets(&mut commands, &assets, &localization);
                 app_state.init_state = InitState::UIReady;
                 println!("🖼️  UI初期化が完了しました");
             }
@@ -475,7 +538,7 @@ pub fn initialization_system(
 }
 
 /// アセット準備完了後のUI初期化
-pub fn setup_ui_with_assets(commands: &mut Commands, assets: &Res<GameAssets>) {
+pub fn setup_ui_with_assets(commands: &mut Commands, assets: &Res<GameAssets>, localization: &Localization) {
     // カメラ設定
     commands.spawn(Camera2d);
 
@@ -486,9 +549,14 @@ pub fn setup_ui_with_assets(commands: &mut Commands, assets: &Res<GameAssets>) {
         BackgroundController::new(),
     ));
 
+    spawn_title_screen(commands, assets, localization);
+}
+
+/// タイトル画面の文字とメニューボタンを生成（表示言語を切り替えたときは作り直す）
+pub fn spawn_title_screen(commands: &mut Commands, assets: &GameAssets, localization: &Localization) {
     // タイトルテキスト表示
     commands.spawn((
-        Text2d::new("願い石と僕たちの絆"),
+        Text2d::new(localization.ui("title.main")),
         TextFont {
             font: assets.main_font.clone(),
             font_size: 48.0,
@@ -502,7 +570,7 @@ pub fn setup_ui_with_assets(commands: &mut Commands, assets: &Res<GameAssets>) {
 
     // サブタイトル表示
     commands.spawn((
-        Text2d::new("- Rust + Bevy版 -"),
+        Text2d::new(localization.ui("title.subtitle")),
         TextFont {
             font: assets.main_font.clone(),
             font_size: 24.0,
@@ -542,7 +610,7 @@ pub fn setup_ui_with_assets(commands: &mut Commands, assets: &Res<GameAssets>) {
             .id();
 
         // ボタンテキストを子エンティティとして作成（親子関係で正しく配置）
-        let button_text = get_menu_button_text(&button_type);
+        let button_text = get_menu_button_text(localization, &button_type);
         println!(
             "Creating button text: '{}' at position y={}",
             button_text, y_pos
@@ -569,7 +637,7 @@ pub fn setup_ui_with_assets(commands: &mut Commands, assets: &Res<GameAssets>) {
 
     // ボタン説明表示（ボタンテキストの代替情報）
     commands.spawn((
-        Text2d::new("1:はじめから 2:つづきから 3:設定 4:ギャラリー 5:ゲーム終了"),
+        Text2d::new(localization.ui("title.menu_shortcuts")),
         TextFont {
             font: assets.main_font.clone(),
             font_size: 16.0,
@@ -583,9 +651,7 @@ pub fn setup_ui_with_assets(commands: &mut Commands, assets: &Res<GameAssets>) {
 
     // 操作説明表示（下部に移動）
     commands.spawn((
-        Text2d::new(
-            "数字キー1-5で直接選択 / Spaceキー：選択 / Escキー：終了 / F1キー：デバッグ情報",
-        ),
+        Text2d::new(localization.ui("title.help")),
         TextFont {
             font: assets.main_font.clone(),
             font_size: 14.0,
@@ -2,26 +2,258 @@
 //!
 //! テキストタイピング効果、ダイアログ表示、ログ管理に関するECSシステム
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use crate::presentation::ui_components::*;
-use crate::presentation::ui_utils::{create_log_window, create_log_entries};
+use bevy::sprite::Anchor;
+use bevy::text::TextLayoutInfo;
+use crate::presentation::ui_utils::{CursorClick, create_log_window, create_log_entries, rich_text_span, ruby_text_for, segment_font_runs, LOG_VOICE_BUTTON_SIZE};
+use crate::domain::rich_text::{TextPause, TextSegment};
+use std::time::Duration;
+use crate::application::scenario_system::MarkdownScenarioState;
+use crate::application::audio_system::VoicePlayer;
+use crate::domain::localization::Localization;
 
 /// VNDialogue用のタイピングシステム
-pub fn vn_typing_system(mut query: Query<(&mut VNDialogue, &mut Text2d)>, time: Res<Time>) {
-    for (mut dialogue, mut text) in query.iter_mut() {
-        if !dialogue.is_complete {
-            dialogue.timer.tick(time.delta());
-
-            if dialogue.timer.just_finished() {
-                let chars: Vec<char> = dialogue.full_text.chars().collect();
-                if dialogue.current_char < chars.len() {
-                    dialogue.current_char += 1;
-                    let displayed_text: String = chars[..dialogue.current_char].iter().collect();
-                    text.0 = displayed_text;
+///
+/// `current_char` は表示単位数（書記素クラスタ単位、ルビ付き語は1単位）として進める。
+/// 進行表（`VNDialogue::timeline`）を参照するため、処理量は行の長さによらず
+/// そのフレームで表示する単位数（最大 `max_chars_per_frame`）に比例する
+pub fn vn_typing_system(
+    mut query: Query<&mut VNDialogue>,
+    settings: Res<TypewriterSettings>,
+    time: Res<Time>,
+) {
+    for mut dialogue in query.iter_mut() {
+        if dialogue.is_complete || dialogue.is_waiting_click {
+            continue;
+        }
+
+        // {w=...} や句読点による一時停止中
+        if let Some(pause_timer) = dialogue.pause_timer.as_mut() {
+            pause_timer.tick(time.delta());
+            if !pause_timer.finished() {
+                continue;
+            }
+            dialogue.pause_timer = None;
+        }
+
+        dialogue.elapsed += time.delta_secs();
+        let mut revealed = 0;
+
+        loop {
+            let position = dialogue.current_char;
+
+            // 全単位表示後：末尾の一時停止を処理してから完了
+            let Some(unit) = dialogue.timeline.units.get(position) else {
+                if dialogue.paused_at != Some(position) && !dialogue.timeline.end_pauses.is_empty() {
+                    let pauses = dialogue.timeline.end_pauses.clone();
+                    start_pauses(&mut dialogue, position, &pauses);
                 } else {
                     dialogue.is_complete = true;
                 }
+                break;
+            };
+
+            // この単位の前の一時停止は1回だけ処理する
+            if dialogue.paused_at != Some(position) && !unit.pauses_before.is_empty() {
+                let pauses = unit.pauses_before.clone();
+                start_pauses(&mut dialogue, position, &pauses);
+                break;
+            }
+
+            let cost = settings.seconds_per_char / unit.speed;
+            if dialogue.elapsed < cost {
+                break;
+            }
+
+            let pause_char = unit.pause_char;
+            dialogue.elapsed -= cost;
+            dialogue.current_char += 1;
+            revealed += 1;
+
+            // 句読点の後の追加ウェイト
+            if let Some(ms) = pause_char.and_then(|c| settings.punctuation_pause_ms.get(&c)) {
+                dialogue.pause_timer = Some(Timer::new(Duration::from_millis(*ms as u64), TimerMode::Once));
+                dialogue.elapsed = 0.0;
+                break;
+            }
+
+            // 1フレームの表示数を超えた分の時間は持ち越さない
+            if revealed >= settings.max_chars_per_frame {
+                dialogue.elapsed = dialogue.elapsed.min(cost);
+                break;
+            }
+        }
+    }
+}
+
+/// `{w=...}` / `{p}` による一時停止を開始
+fn start_pauses(dialogue: &mut VNDialogue, position: usize, pauses: &[TextPause]) {
+    dialogue.paused_at = Some(position);
+    dialogue.elapsed = 0.0;
+
+    for pause in pauses {
+        match pause {
+            TextPause::Wait(ms) => {
+                dialogue.pause_timer = Some(Timer::new(Duration::from_millis(*ms as u64), TimerMode::Once));
             }
+            TextPause::Click => dialogue.is_waiting_click = true,
+        }
+    }
+}
+
+/// VNDialogueの表示内容をTextSpanとして描画するシステム
+///
+/// テキスト差し替え時のみ子のTextSpanを作り直し、タイピング中は
+/// 新しく表示された単位を含むTextSpanだけを更新する
+pub fn dialogue_text_render_system(
+    mut commands: Commands,
+    assets: Option<Res<GameAssets>>,
+    mut query: Query<(Entity, &VNDialogue, &mut VNDialogueView, &mut Text2d, &TextFont, &TextColor)>,
+) {
+    let Some(assets) = assets else { return; };
+
+    for (entity, dialogue, mut view, mut text, text_font, text_color) in query.iter_mut() {
+        let style = RichTextStyle {
+            font: text_font.clone(),
+            bold_font: assets.bold_font.clone(),
+            color: text_color.0,
+            fallback: assets.font_fallback.clone(),
+        };
+
+        // テキストが差し替えられた（または表示が巻き戻った）場合は作り直す
+        let visible_units = dialogue.visible_units();
+        if view.revision != Some(dialogue.revision) || visible_units < view.rendered_units {
+            commands.entity(entity).despawn_descendants();
+            *view = VNDialogueView {
+                revision: Some(dialogue.revision),
+                ..default()
+            };
+            commands.entity(entity).insert(ruby_text_for(Vec::new(), &style));
+
+            // 本文はTextSpanに持たせ、ルートは空にする
+            if !text.0.is_empty() {
+                text.0.clear();
+            }
+        }
+
+        if visible_units == view.rendered_units {
+            continue;
+        }
+
+        let units = &dialogue.timeline.units;
+        let first_segment = view.rendered_units.checked_sub(1).map_or(0, |index| units[index].segment);
+        let last_unit = &units[visible_units - 1];
+        let mut rubies_changed = false;
+
+        for segment_index in first_segment..=last_unit.segment {
+            let segment = &dialogue.rich_text.segments[dialogue.timeline.segment_indices[segment_index]];
+            let full = segment.base_text();
+            let visible_len = if segment_index == last_unit.segment {
+                last_unit.byte_end
+            } else {
+                full.len()
+            };
+            let visible = |range: &std::ops::Range<usize>| &full[range.start.min(visible_len)..range.end.min(visible_len)];
+
+            match view.spans.get(segment_index) {
+                Some(runs) => {
+                    for (span, range) in runs {
+                        commands.entity(*span).insert(TextSpan::new(visible(range)));
+                    }
+                }
+                None => {
+                    let mut runs = Vec::new();
+                    for (range, font) in segment_font_runs(segment, &style) {
+                        let span = commands.spawn(rich_text_span(segment, visible(&range), &font, &style)).id();
+                        commands.entity(entity).add_child(span);
+                        runs.push((span, range));
+                        view.span_count += 1;
+
+                        if let TextSegment::Ruby { reading, .. } = segment {
+                            let span_index = view.span_count;
+                            view.rubies.push((span_index, reading.clone()));
+                            rubies_changed = true;
+                        }
+                    }
+                    view.spans.push(runs);
+                }
+            }
+        }
+
+        if rubies_changed {
+            commands.entity(entity).insert(ruby_text_for(view.rubies.clone(), &style));
+        }
+        view.rendered_units = visible_units;
+    }
+}
+
+/// レイアウトが変わったルビ付きテキスト（親文字の位置を求めるためのレイアウト・アンカー・位置）
+type RubyTextLayoutQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static RubyText, &'static TextLayoutInfo, &'static Anchor, &'static GlobalTransform),
+    Or<(Changed<TextLayoutInfo>, Changed<RubyText>)>,
+>;
+
+/// ルビ（ふりがな）配置システム
+///
+/// レイアウト済みグリフから親文字の範囲を求め、その上にふりがなを配置する
+pub fn ruby_layout_system(
+    mut commands: Commands,
+    text_query: RubyTextLayoutQuery,
+    owner_query: Query<(), With<RubyText>>,
+    reading_query: Query<(Entity, &RubyReading)>,
+    windows: Query<&Window>,
+) {
+    // 親テキストが削除されたルビを片付ける
+    for (reading_entity, reading) in reading_query.iter() {
+        if owner_query.get(reading.owner).is_err() {
+            commands.entity(reading_entity).despawn();
+        }
+    }
+
+    let scale_factor = windows
+        .get_single()
+        .map(|window| window.resolution.scale_factor())
+        .unwrap_or(1.0);
+
+    for (owner, ruby_text, layout_info, anchor, global_transform) in text_query.iter() {
+        for (reading_entity, reading) in reading_query.iter() {
+            if reading.owner == owner {
+                commands.entity(reading_entity).despawn();
+            }
+        }
+
+        // グリフ座標（物理ピクセル・下端基準）をテキストのローカル座標に変換するための原点
+        let origin = layout_info.size * -(anchor.as_vec() + 0.5);
+
+        for (span_index, reading) in &ruby_text.rubies {
+            let mut glyphs = layout_info.glyphs.iter().filter(|glyph| glyph.span_index == *span_index);
+            let Some(first) = glyphs.next() else { continue; };
+
+            let mut left = first.position.x - first.size.x / 2.0;
+            let mut right = first.position.x + first.size.x / 2.0;
+            let mut top = first.position.y + first.size.y / 2.0;
+            for glyph in glyphs {
+                left = left.min(glyph.position.x - glyph.size.x / 2.0);
+                right = right.max(glyph.position.x + glyph.size.x / 2.0);
+                top = top.max(glyph.position.y + glyph.size.y / 2.0);
+            }
+
+            let local = origin
+                + Vec2::new((left + right) / 2.0, top) / scale_factor
+                + Vec2::new(0.0, ruby_text.font.font_size * 0.6);
+            let translation = global_transform.transform_point(local.extend(0.1));
+
+            commands.spawn((
+                Text2d::new(reading.clone()),
+                ruby_text.font.clone(),
+                TextColor(ruby_text.color),
+                Transform::from_translation(translation),
+                RubyReading { owner },
+            ));
         }
     }
 }
@@ -31,16 +263,24 @@ pub fn vn_typing_system(mut query: Query<(&mut VNDialogue, &mut Text2d)>, time:
 pub fn dialogue_completion_system(
     mut dialogue_query: Query<(&VNDialogue, &mut Text2d), Changed<VNDialogue>>,
     character_query: Query<&VNCharacterName>,
+    scenario_state: Res<MarkdownScenarioState>,
+    localization: Res<Localization>,
     mut log: ResMut<DialogueLog>,
 ) {
     for (dialogue, _text) in dialogue_query.iter_mut() {
-        if dialogue.is_complete && dialogue.current_char == dialogue.full_text.len() {
-            // キャラクター名を取得
-            let character_name = character_query
+        if dialogue.is_complete {
+            // キャラクター名を取得（表示言語の名前で記録）
+            let (character_name, character_id) = character_query
                 .iter()
                 .next()
-                .map(|name| name.name.clone())
-                .unwrap_or_else(|| "".to_string());
+                .map(|name| (localization.speaker_name(&name.name).to_string(), name.character_id.clone()))
+                .unwrap_or_default();
+
+            // 表示中のダイアログのボイス
+            let voice = scenario_state
+                .get_current_dialogue()
+                .filter(|block| localization.line_text(block) == dialogue.full_text)
+                .and_then(|block| block.voice.clone());
 
             // ログに追加（重複チェック）
             if log.entries.is_empty()
@@ -48,6 +288,8 @@ pub fn dialogue_completion_system(
                 log.entries.push(DialogueEntry {
                     character_name,
                     text: dialogue.full_text.clone(),
+                    character_id,
+                    voice,
                 });
                 println!("ログに追加: {}", dialogue.full_text);
             }
@@ -55,15 +297,22 @@ pub fn dialogue_completion_system(
     }
 }
 
+/// ログ画面のボタン（開閉ボタン・閉じるボタン・ボイス再生ボタン）
+#[derive(SystemParam)]
+pub struct LogButtons<'w, 's> {
+    open: Query<'w, 's, &'static Transform, (With<LogButton>, Without<LogCloseButton>)>,
+    close: Query<'w, 's, &'static Transform, (With<LogCloseButton>, Without<LogButton>)>,
+    voice: Query<'w, 's, (&'static Transform, &'static LogVoiceButton)>,
+}
+
 /// ログ入力システム（ログボタン・Lキー・ログウィンドウ内の操作）
 pub fn log_input_system(
+    mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mouse_input: Res<ButtonInput<MouseButton>>,
     mut log: ResMut<DialogueLog>,
-    windows: Query<&Window>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
-    log_button_query: Query<&Transform, (With<LogButton>, Without<LogCloseButton>)>,
-    close_button_query: Query<&Transform, (With<LogCloseButton>, Without<LogButton>)>,
+    cursor: CursorClick,
+    buttons: LogButtons,
+    voice_player: VoicePlayer,
     game_mode: Res<GameMode>,
 ) {
     if !game_mode.is_story_mode {
@@ -77,45 +326,49 @@ pub fn log_input_system(
     }
 
     // マウスクリック処理
-    if mouse_input.just_pressed(MouseButton::Left) {
-        if let Ok(window) = windows.get_single() {
-            if let Some(cursor_position) = window.cursor_position() {
-                if let Ok((camera, camera_transform)) = camera_query.get_single() {
-                    if let Ok(world_position) =
-                        camera.viewport_to_world_2d(camera_transform, cursor_position) {
-
-                        // ログボタンクリック判定
-                        for button_transform in log_button_query.iter() {
-                            let button_pos = button_transform.translation.truncate();
-                            let button_size = Vec2::new(80.0, 40.0);
-
-                            if world_position.x >= button_pos.x - button_size.x / 2.0
-                                && world_position.x <= button_pos.x + button_size.x / 2.0
-                                && world_position.y >= button_pos.y - button_size.y / 2.0
-                                && world_position.y <= button_pos.y + button_size.y / 2.0 {
-                                log.is_visible = !log.is_visible;
-                                println!("ログボタンクリック: ログ表示切り替え -> {}", log.is_visible);
-                                return;
-                            }
-                        }
+    if let Some(world_position) = cursor.clicked_position() {
+        // ログボタンクリック判定
+        for button_transform in buttons.open.iter() {
+            let button_pos = button_transform.translation.truncate();
+            let button_size = Vec2::new(80.0, 40.0);
 
-                        // 閉じるボタンクリック判定（ログ表示中のみ）
-                        if log.is_visible {
-                            for close_transform in close_button_query.iter() {
-                                let button_pos = close_transform.translation.truncate();
-                                let button_size = Vec2::new(80.0, 40.0);
-
-                                if world_position.x >= button_pos.x - button_size.x / 2.0
-                                    && world_position.x <= button_pos.x + button_size.x / 2.0
-                                    && world_position.y >= button_pos.y - button_size.y / 2.0
-                                    && world_position.y <= button_pos.y + button_size.y / 2.0 {
-                                    log.is_visible = false;
-                                    println!("ログ閉じるボタンクリック");
-                                    return;
-                                }
-                            }
-                        }
-                    }
+            if world_position.x >= button_pos.x - button_size.x / 2.0
+                && world_position.x <= button_pos.x + button_size.x / 2.0
+                && world_position.y >= button_pos.y - button_size.y / 2.0
+                && world_position.y <= button_pos.y + button_size.y / 2.0 {
+                log.is_visible = !log.is_visible;
+                println!("ログボタンクリック: ログ表示切り替え -> {}", log.is_visible);
+                return;
+            }
+        }
+
+        // 閉じるボタンクリック判定（ログ表示中のみ）
+        if log.is_visible {
+            for close_transform in buttons.close.iter() {
+                let button_pos = close_transform.translation.truncate();
+                let button_size = Vec2::new(80.0, 40.0);
+
+                if world_position.x >= button_pos.x - button_size.x / 2.0
+                    && world_position.x <= button_pos.x + button_size.x / 2.0
+                    && world_position.y >= button_pos.y - button_size.y / 2.0
+                    && world_position.y <= button_pos.y + button_size.y / 2.0 {
+                    log.is_visible = false;
+                    println!("ログ閉じるボタンクリック");
+                    return;
+                }
+            }
+
+            // ボイス再生ボタンクリック判定
+            for (voice_transform, voice_button) in buttons.voice.iter() {
+                let button_pos = voice_transform.translation.truncate();
+                let button_size = Vec2::new(LOG_VOICE_BUTTON_SIZE, LOG_VOICE_BUTTON_SIZE);
+
+                if world_position.x >= button_pos.x - button_size.x / 2.0
+                    && world_position.x <= button_pos.x + button_size.x / 2.0
+                    && world_position.y >= button_pos.y - button_size.y / 2.0
+                    && world_position.y <= button_pos.y + button_size.y / 2.0 {
+                    voice_player.play(&mut commands, &voice_button.voice);
+                    return;
                 }
             }
         }
@@ -136,6 +389,7 @@ pub fn log_ui_system(
     log_window_query: Query<Entity, With<LogWindow>>,
     log_entry_query: Query<Entity, With<LogEntry>>,
     game_mode: Res<GameMode>,
+    localization: Res<Localization>,
 ) {
     if !game_mode.is_story_mode {
         return;
@@ -147,11 +401,11 @@ pub fn log_ui_system(
     if log.is_visible {
         // ログウィンドウが存在しない場合は作成
         if log_window_query.is_empty() {
-            create_log_window(&mut commands, &assets, &log);
+            create_log_window(&mut commands, &assets, &log, &localization);
         } else {
             // 既存のログエントリをクリアして再作成
             for entity in log_entry_query.iter() {
-                commands.entity(entity).despawn();
+                commands.entity(entity).despawn_recursive();
             }
             create_log_entries(&mut commands, &assets, &log);
         }
@@ -4,6 +4,12 @@
 //! ロジックは含めず、純粋なデータ構造のみ
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::domain::rich_text::{RichText, TypingTimeline};
+use crate::domain::line_break::LineBreakRule;
+use crate::domain::character::{Character, CharacterRegistry};
+use super::fonts::FontFallback;
 
 /// テキスト表示コンポーネント（ロジック層中心設計）
 #[derive(Component)]
@@ -74,27 +80,195 @@ pub struct VNTextBox;
 #[derive(Component)]
 pub struct VNCharacterName {
     pub name: String,
+    /// 話者のキャラクターID（未登録の話者・地の文は None）
+    pub character_id: Option<String>,
+}
+
+impl VNCharacterName {
+    /// 名前欄に正体が表示されている話者のキャラクター（`？？？` などの匿名表示は None）
+    pub fn revealed_character<'a>(&self, registry: &'a CharacterRegistry) -> Option<&'a Character> {
+        self.character_id
+            .as_deref()
+            .and_then(|id| registry.get(id))
+            .filter(|character| character.name == self.name)
+    }
 }
 
 #[derive(Component)]
 pub struct VNDialogue {
     pub full_text: String,
+    /// 解析済み本文（full_text から生成）
+    pub rich_text: RichText,
+    /// タイピング進行表（full_text から生成）
+    pub timeline: TypingTimeline,
+    /// 表示済みの単位数（書記素クラスタ単位、ルビ付き語は1単位）
     pub current_char: usize,
-    pub timer: Timer,
+    /// 次の単位を表示するまでに溜まった経過時間（秒）
+    pub elapsed: f32,
     pub is_complete: bool,
+    /// `{w=...}` や句読点による一時停止タイマー
+    pub pause_timer: Option<Timer>,
+    /// `{p}` によるクリック待ち中
+    pub is_waiting_click: bool,
+    /// 一時停止を処理済みの表示位置（同じ位置で二重に止まらないため）
+    pub paused_at: Option<usize>,
+    /// テキスト差し替えごとに増える番号（描画側の変更検知用）
+    pub revision: u32,
+    /// テキストボックス幅での折り返し設定（None の場合は折り返さない）
+    pub line_break: Option<LineBreakRule>,
 }
 
 impl VNDialogue {
     pub fn new(text: String) -> Self {
+        let rich_text = RichText::parse(&text);
+        let timeline = rich_text.timeline();
         Self {
             full_text: text,
+            rich_text,
+            timeline,
             current_char: 0,
-            timer: Timer::from_seconds(0.05, TimerMode::Repeating), // 50ms/文字
+            elapsed: 0.0,
             is_complete: false,
+            pause_timer: None,
+            is_waiting_click: false,
+            paused_at: None,
+            revision: 0,
+            line_break: None,
+        }
+    }
+
+    /// 折り返し設定付きで作成（表示前に改行を確定させ、タイピング中の組み替えを防ぐ）
+    pub fn with_line_break(text: String, rule: LineBreakRule) -> Self {
+        let rich_text = rule.wrap(&RichText::parse(&text));
+        let timeline = rich_text.timeline();
+        Self {
+            rich_text,
+            timeline,
+            line_break: Some(rule),
+            ..Self::new(text)
+        }
+    }
+
+    /// 表示テキストを差し替えてタイピングを最初からやり直す
+    pub fn set_text(&mut self, text: String) {
+        let revision = self.revision.wrapping_add(1);
+        *self = match self.line_break.take() {
+            Some(rule) => Self::with_line_break(text, rule),
+            None => Self::new(text),
+        };
+        self.revision = revision;
+    }
+
+    /// 表示中の単位数（完了時は全単位）
+    pub fn visible_units(&self) -> usize {
+        if self.is_complete {
+            self.timeline.len()
+        } else {
+            self.current_char.min(self.timeline.len())
+        }
+    }
+}
+
+/// タイプライター表示の設定（Bevy Resource）
+#[derive(Resource, Debug, Clone)]
+pub struct TypewriterSettings {
+    /// 1単位あたりの表示間隔（秒）
+    pub seconds_per_char: f32,
+    /// 句読点の後に入れる追加ウェイト（ミリ秒）
+    pub punctuation_pause_ms: HashMap<char, u32>,
+    /// 1フレームに表示する最大単位数（高速表示時の上限）
+    pub max_chars_per_frame: usize,
+}
+
+impl Default for TypewriterSettings {
+    fn default() -> Self {
+        Self {
+            seconds_per_char: 0.05, // 50ms/文字
+            punctuation_pause_ms: HashMap::from([
+                ('。', 300),
+                ('！', 250),
+                ('？', 250),
+                ('!', 250),
+                ('?', 250),
+                ('…', 200),
+                ('、', 120),
+            ]),
+            max_chars_per_frame: 8,
+        }
+    }
+}
+
+/// 話者表示の設定（Bevy Resource、プレイヤー設定）
+///
+/// 強調表示・暗転の有無は設定画面で切り替え、`saves/settings.json` に保存する
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeakerDisplaySettings {
+    /// 話者の立ち絵を自動で強調表示するか
+    pub highlight_speaker: bool,
+    /// 強調表示時の拡大率
+    pub highlight_scale: f32,
+    /// 話者以外の立ち絵を暗くするか
+    pub dim_non_speakers: bool,
+    /// 暗くした立ち絵の明るさ（0.0〜1.0）
+    pub dim_brightness: f32,
+    /// 強調・暗転の切り替えにかける時間（秒）
+    pub transition_seconds: f32,
+}
+
+impl Default for SpeakerDisplaySettings {
+    fn default() -> Self {
+        Self {
+            highlight_speaker: true,
+            highlight_scale: 1.05,
+            dim_non_speakers: true,
+            dim_brightness: 0.55,
+            transition_seconds: 0.2,
         }
     }
 }
 
+/// VNDialogueの描画済み状態（新しく表示された単位のTextSpanだけを更新する）
+#[derive(Component, Default)]
+pub struct VNDialogueView {
+    /// 描画済みの VNDialogue::revision
+    pub revision: Option<u32>,
+    pub rendered_units: usize,
+    /// 表示セグメントごとのTextSpanエンティティ（フォント区間ごと、区間はセグメント内のバイト範囲）
+    pub spans: Vec<Vec<(Entity, std::ops::Range<usize>)>>,
+    /// 生成済みのTextSpanの数
+    pub span_count: usize,
+    /// 描画済みのルビ（TextSpanインデックス, ふりがな）
+    pub rubies: Vec<(usize, String)>,
+}
+
+/// ルビ付きテキスト（Text2dルートに付与）
+///
+/// `rubies` は（親文字のTextSpanインデックス, ふりがな）の一覧
+#[derive(Component, Default)]
+pub struct RubyText {
+    pub rubies: Vec<(usize, String)>,
+    pub font: TextFont,
+    pub color: Color,
+}
+
+/// リッチテキスト描画時の既定フォントと色
+#[derive(Clone)]
+pub struct RichTextStyle {
+    pub font: TextFont,
+    /// `**強調**` に使うフォント
+    pub bold_font: Handle<Font>,
+    pub color: Color,
+    /// グリフがない文字に使うフォント
+    pub fallback: FontFallback,
+}
+
+/// ルビ（ふりがな）表示エンティティ
+#[derive(Component)]
+pub struct RubyReading {
+    pub owner: Entity,
+}
+
 /// VNUIボタンタイプ
 #[derive(Debug, Clone)]
 pub enum VNButtonType {
@@ -126,6 +300,12 @@ pub struct LogWindow;
 #[derive(Component)]
 pub struct LogEntry;
 
+/// ログのボイス再生ボタン
+#[derive(Component)]
+pub struct LogVoiceButton {
+    pub voice: String,
+}
+
 /// ゲーム画面の種類
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameScreen {
@@ -134,6 +314,10 @@ pub enum GameScreen {
     Settings,
     Gallery,
     Battle,
+    /// 相関図（ストーリーの上に開き、閉じるとストーリーに戻る）
+    RelationshipChart,
+    /// プレゼント（ストーリーの上に開き、閉じるとストーリーに戻る）
+    Gift,
 }
 
 /// リソース：ゲームモード
@@ -205,6 +389,10 @@ pub struct DialogueLog {
 pub struct DialogueEntry {
     pub character_name: String,
     pub text: String,
+    /// 話者のキャラクターID（匿名表示の話者も記録し、後から正体を明かせるようにする）
+    pub character_id: Option<String>,
+    /// ボイス（ログから再生するため）
+    pub voice: Option<String>,
 }
 
 /// リソース：シナリオ状態
@@ -218,7 +406,18 @@ pub struct ScenarioState {
 /// リソース：ゲームアセット
 #[derive(Resource)]
 pub struct GameAssets {
+    /// UIフォント（メニュー・ボタンなど）
     pub main_font: Handle<Font>,
+    /// 本文フォント（セリフ・ログ）
+    pub dialogue_font: Handle<Font>,
+    /// 名前欄フォント
+    pub name_font: Handle<Font>,
+    /// 戦闘画面の数値フォント
+    pub number_font: Handle<Font>,
+    /// 強調表示用フォント（専用フォントがなければ dialogue_font と同じ）
+    pub bold_font: Handle<Font>,
+    /// グリフがない文字のフォールバック
+    pub font_fallback: FontFallback,
     pub background_souma_home: Handle<Image>,
     pub character_souma: Handle<Image>,
 }
@@ -276,6 +475,46 @@ mod tests {
         assert!(!dialogue.is_complete);
     }
 
+    #[test]
+    fn test_vn_dialogue_set_text_rebuilds_timeline() {
+        let mut dialogue = VNDialogue::new("VN Test message".to_string());
+        dialogue.current_char = 3;
+        dialogue.is_complete = true;
+
+        dialogue.set_text("｜願い石《ねがいいし》が光った。".to_string());
+        assert_eq!(dialogue.revision, 1);
+        assert_eq!(dialogue.current_char, 0);
+        assert!(!dialogue.is_complete);
+        // ルビ付き語1 + 「が光った。」5（バイト長ではなく単位数）
+        assert_eq!(dialogue.timeline.len(), 6);
+
+        dialogue.is_complete = true;
+        assert_eq!(dialogue.visible_units(), 6);
+    }
+
+    #[test]
+    fn test_vn_dialogue_keeps_line_break_on_set_text() {
+        let mut dialogue = VNDialogue::with_line_break("あいうえお".to_string(), LineBreakRule::new(80.0, 20.0));
+        assert_eq!(dialogue.rich_text.plain_text(), "あいうえ\nお");
+
+        dialogue.set_text("かきくけ。こ".to_string());
+        assert!(dialogue.line_break.is_some());
+        assert_eq!(dialogue.full_text, "かきくけ。こ");
+        assert_eq!(dialogue.rich_text.plain_text(), "かきくけ。\nこ");
+    }
+
+    #[test]
+    fn test_vn_character_name_hides_anonymous_speaker() {
+        let mut registry = CharacterRegistry::new();
+        registry.register_default_characters();
+
+        let name = VNCharacterName { name: "カイ".to_string(), character_id: Some("kai".to_string()) };
+        assert_eq!(name.revealed_character(&registry).map(|c| c.id.as_str()), Some("kai"));
+
+        let name = VNCharacterName { name: "？？？".to_string(), character_id: Some("kai".to_string()) };
+        assert!(name.revealed_character(&registry).is_none());
+    }
+
     #[test]
     fn test_background_controller_creation() {
         let controller = BackgroundController::new();
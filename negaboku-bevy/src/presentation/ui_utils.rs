@@ -2,9 +2,53 @@
 //!
 //! ComponentからSystem層に移動したロジック関数群
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use crate::presentation::ui_components::{MenuButtonType, BackgroundController, GameAssets, DialogueLog, DialogueEntry, LogWindow, LogEntry, LogCloseButton};
+use crate::presentation::ui_components::{MenuButtonType, BackgroundController, GameAssets, DialogueLog, DialogueEntry, LogWindow, LogEntry, LogCloseButton, LogVoiceButton, RubyText, RichTextStyle};
+use crate::domain::rich_text::{RichText, TextSegment};
+use crate::domain::line_break::LineBreakRule;
+use crate::domain::localization::Localization;
+use crate::domain::character::CharacterRegistry;
+use bevy::sprite::Anchor;
 
+/// ルビの文字サイズ（親文字に対する比率）
+const RUBY_FONT_SCALE: f32 = 0.5;
+
+/// ログ本文の左端と折り返し幅（ピクセル）
+const LOG_TEXT_LEFT: f32 = -550.0;
+const LOG_TEXT_WIDTH: f32 = 1040.0;
+
+/// ログのボイス再生ボタンの大きさ（ピクセル）
+pub const LOG_VOICE_BUTTON_SIZE: f32 = 36.0;
+
+
+/// マウスのクリック位置の取得に使う入力とカメラ
+#[derive(SystemParam)]
+pub struct CursorClick<'w, 's> {
+    mouse_input: Res<'w, ButtonInput<MouseButton>>,
+    windows: Query<'w, 's, &'static Window>,
+    camera_query: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+}
+
+impl CursorClick<'_, '_> {
+    /// このフレームで左クリックした位置（ワールド座標、クリックがなければ None）
+    pub fn clicked_position(&self) -> Option<Vec2> {
+        if !self.mouse_input.just_pressed(MouseButton::Left) {
+            return None;
+        }
+        let cursor_position = self.windows.get_single().ok()?.cursor_position()?;
+        let (camera, camera_transform) = self.camera_query.get_single().ok()?;
+        camera.viewport_to_world_2d(camera_transform, cursor_position).ok()
+    }
+}
+
+/// キャラクターの表示名（表示言語の話者名、未登録のキャラクターはID）
+pub fn display_name(character_id: &str, characters: &CharacterRegistry, localization: &Localization) -> String {
+    characters
+        .get(character_id)
+        .map(|character| localization.speaker_name(&character.name).to_string())
+        .unwrap_or_else(|| character_id.to_string())
+}
 
 /// インデックスをMenuButtonTypeに変換
 pub fn index_to_menu_button_type(index: usize) -> MenuButtonType {
@@ -18,21 +62,26 @@ pub fn index_to_menu_button_type(index: usize) -> MenuButtonType {
     }
 }
 
-/// メニューボタンのテキストを取得
-pub fn get_menu_button_text(button_type: &MenuButtonType) -> &'static str {
+/// メニューボタンのUI文字列キーを取得
+pub fn menu_button_key(button_type: &MenuButtonType) -> &'static str {
     match button_type {
-        MenuButtonType::NewGame => "はじめから",
-        MenuButtonType::Continue => "つづきから",
-        MenuButtonType::Settings => "設定",
-        MenuButtonType::Gallery => "ギャラリー",
-        MenuButtonType::Exit => "ゲーム終了",
+        MenuButtonType::NewGame => "menu.new_game",
+        MenuButtonType::Continue => "menu.continue",
+        MenuButtonType::Settings => "menu.settings",
+        MenuButtonType::Gallery => "menu.gallery",
+        MenuButtonType::Exit => "menu.exit",
     }
 }
 
+/// メニューボタンのテキストを取得（表示言語に合わせる）
+pub fn get_menu_button_text<'a>(localization: &'a Localization, button_type: &MenuButtonType) -> &'a str {
+    localization.ui(menu_button_key(button_type))
+}
+
 /// インデックスから直接ボタン名を取得（便利関数）
-pub fn get_button_text_by_index(index: usize) -> &'static str {
+pub fn get_button_text_by_index(localization: &Localization, index: usize) -> &str {
     let button_type = index_to_menu_button_type(index);
-    get_menu_button_text(&button_type)
+    get_menu_button_text(localization, &button_type)
 }
 
 /// メニューボタンの通常色を取得
@@ -62,7 +111,7 @@ pub fn get_current_background(controller: &BackgroundController) -> Color {
 }
 
 /// ログウィンドウ作成関数
-pub fn create_log_window(commands: &mut Commands, assets: &GameAssets, log: &DialogueLog) {
+pub fn create_log_window(commands: &mut Commands, assets: &GameAssets, log: &DialogueLog, localization: &Localization) {
     // 半透明背景オーバーレイ
     commands.spawn((
         Sprite::from_color(
@@ -85,7 +134,7 @@ pub fn create_log_window(commands: &mut Commands, assets: &GameAssets, log: &Dia
 
     // タイトル
     commands.spawn((
-        Text2d::new("会話ログ"),
+        Text2d::new(localization.ui("log.title")),
         TextFont {
             font: assets.main_font.clone(),
             font_size: 32.0,
@@ -109,7 +158,7 @@ pub fn create_log_window(commands: &mut Commands, assets: &GameAssets, log: &Dia
     ));
 
     commands.spawn((
-        Text2d::new("閉じる"),
+        Text2d::new(localization.ui("log.close")),
         TextFont {
             font: assets.main_font.clone(),
             font_size: 16.0,
@@ -144,32 +193,75 @@ pub fn create_log_entries(commands: &mut Commands, assets: &GameAssets, log: &Di
             commands.spawn((
                 Text2d::new(&entry.character_name),
                 TextFont {
-                    font: assets.main_font.clone(),
+                    font: assets.font_fallback.font_for_text(&assets.name_font, &entry.character_name),
                     font_size: 20.0,
                     ..default()
                 },
                 TextLayout::new_with_justify(JustifyText::Left),
                 TextColor(Color::srgb(1.0, 0.8, 0.6)),
-                Transform::from_xyz(-550.0, y_pos, 52.0),
+                Anchor::CenterLeft,
+                Transform::from_xyz(LOG_TEXT_LEFT, y_pos, 52.0),
                 LogEntry,
                 LogWindow,
             ));
         }
 
-        // 会話テキスト
-        commands.spawn((
-            Text2d::new(&entry.text),
-            TextFont {
-                font: assets.main_font.clone(),
+        // 会話テキスト（ルビ・装飾を含むためTextSpanで構築）
+        let style = RichTextStyle {
+            font: TextFont {
+                font: assets.dialogue_font.clone(),
                 font_size: 18.0,
                 ..default()
             },
-            TextLayout::new_with_justify(JustifyText::Left),
-            TextColor(Color::srgb(0.9, 0.9, 0.9)),
-            Transform::from_xyz(-550.0, y_pos - 30.0, 52.0),
-            LogEntry,
-            LogWindow,
-        ));
+            bold_font: assets.bold_font.clone(),
+            color: Color::srgb(0.9, 0.9, 0.9),
+            fallback: assets.font_fallback.clone(),
+        };
+        let line_break = LineBreakRule::new(LOG_TEXT_WIDTH, style.font.font_size);
+        let text = line_break.wrap(&RichText::parse(&entry.text));
+        let mut ruby_text = RubyText::default();
+        commands
+            .spawn((
+                Text2d::new(""),
+                style.font.clone(),
+                TextLayout::new_with_justify(JustifyText::Left),
+                TextColor(style.color),
+                Anchor::CenterLeft,
+                Transform::from_xyz(LOG_TEXT_LEFT, y_pos - 30.0, 52.0),
+                LogEntry,
+                LogWindow,
+            ))
+            .with_children(|parent| {
+                ruby_text = spawn_rich_text_spans(parent, &text, &style);
+            })
+            .insert(ruby_text);
+
+        // ボイス再生ボタン（ボイス付きのエントリのみ）
+        if let Some(voice) = &entry.voice {
+            let button_x = LOG_TEXT_LEFT + LOG_TEXT_WIDTH + LOG_VOICE_BUTTON_SIZE;
+            commands.spawn((
+                Sprite::from_color(
+                    Color::srgba(0.3, 0.5, 0.8, 0.9),
+                    Vec2::splat(LOG_VOICE_BUTTON_SIZE),
+                ),
+                Transform::from_xyz(button_x, y_pos - 15.0, 52.0),
+                LogVoiceButton { voice: voice.clone() },
+                LogEntry,
+                LogWindow,
+            ));
+            commands.spawn((
+                Text2d::new("▶"),
+                TextFont {
+                    font: assets.main_font.clone(),
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                Transform::from_xyz(button_x, y_pos - 15.0, 53.0),
+                LogEntry,
+                LogWindow,
+            ));
+        }
 
         // 区切り線
         commands.spawn((
@@ -184,17 +276,120 @@ pub fn create_log_entries(commands: &mut Commands, assets: &GameAssets, log: &Di
     }
 }
 
+/// リッチテキストの各セグメントをTextSpanとして生成し、ルビ情報を返す
+///
+/// ルートのText2dは空文字とし、表示セグメントのフォント区間を順にTextSpanインデックス1から割り当てる
+pub fn spawn_rich_text_spans(
+    parent: &mut ChildBuilder,
+    text: &RichText,
+    style: &RichTextStyle,
+) -> RubyText {
+    let mut rubies = Vec::new();
+    let mut span_count = 0;
+
+    for segment in text.display_segments() {
+        let base_text = segment.base_text();
+        for (range, font) in segment_font_runs(segment, style) {
+            parent.spawn(rich_text_span(segment, &base_text[range], &font, style));
+            span_count += 1;
+
+            if let TextSegment::Ruby { reading, .. } = segment {
+                rubies.push((span_count, reading.clone()));
+            }
+        }
+    }
+
+    ruby_text_for(rubies, style)
+}
+
+/// 表示セグメントをフォントごとの区間（バイト範囲, フォント）に分ける
+///
+/// ルビの親文字はふりがなの位置合わせのため分けず、全体を表示できるフォントを1つ選ぶ
+pub fn segment_font_runs(segment: &TextSegment, style: &RichTextStyle) -> Vec<(std::ops::Range<usize>, Handle<Font>)> {
+    let primary = if segment.style().is_some_and(|segment_style| segment_style.bold) {
+        &style.bold_font
+    } else {
+        &style.font.font
+    };
+
+    let text = segment.base_text();
+    match segment {
+        TextSegment::Ruby { .. } => vec![(0..text.len(), style.fallback.font_for_text(primary, text))],
+        _ => style.fallback.runs(primary, text),
+    }
+}
+
+/// 表示セグメント1区間分のTextSpan（`text` はタイピング途中の部分文字列でもよい）
+pub fn rich_text_span(segment: &TextSegment, text: &str, font: &Handle<Font>, style: &RichTextStyle) -> (TextSpan, TextFont, TextColor) {
+    let mut color = style.color;
+
+    if let Some(segment_color) = segment
+        .style()
+        .and_then(|segment_style| segment_style.color.as_deref())
+        .and_then(resolve_text_color)
+    {
+        color = segment_color;
+    }
+
+    let text_font = TextFont {
+        font: font.clone(),
+        ..style.font.clone()
+    };
+    (TextSpan::new(text), text_font, TextColor(color))
+}
+
+/// ルビ一覧と本文の書式からルビ表示用コンポーネントを作成
+pub fn ruby_text_for(rubies: Vec<(usize, String)>, style: &RichTextStyle) -> RubyText {
+    RubyText {
+        rubies,
+        font: TextFont {
+            font: style.font.font.clone(),
+            font_size: style.font.font_size * RUBY_FONT_SCALE,
+            ..default()
+        },
+        color: style.color,
+    }
+}
+
+/// `{color=...}` の値を色に変換（色名または `#rrggbb`）
+pub fn resolve_text_color(name: &str) -> Option<Color> {
+    let color = match name.to_ascii_lowercase().as_str() {
+        "red" => Color::srgb(1.0, 0.35, 0.35),
+        "blue" => Color::srgb(0.45, 0.65, 1.0),
+        "green" => Color::srgb(0.45, 0.9, 0.45),
+        "yellow" => Color::srgb(1.0, 0.9, 0.35),
+        "orange" => Color::srgb(1.0, 0.65, 0.25),
+        "purple" => Color::srgb(0.75, 0.5, 1.0),
+        "pink" => Color::srgb(1.0, 0.6, 0.8),
+        "cyan" => Color::srgb(0.4, 0.9, 0.95),
+        "white" => Color::WHITE,
+        "black" => Color::BLACK,
+        "gray" | "grey" => Color::srgb(0.6, 0.6, 0.6),
+        hex if hex.starts_with('#') => return Srgba::hex(hex).ok().map(Color::from),
+        _ => return None,
+    };
+
+    Some(color)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_menu_button_text() {
-        assert_eq!(get_menu_button_text(&MenuButtonType::NewGame), "はじめから");
-        assert_eq!(get_menu_button_text(&MenuButtonType::Continue), "つづきから");
-        assert_eq!(get_menu_button_text(&MenuButtonType::Settings), "設定");
-        assert_eq!(get_menu_button_text(&MenuButtonType::Gallery), "ギャラリー");
-        assert_eq!(get_menu_button_text(&MenuButtonType::Exit), "ゲーム終了");
+        let localization = Localization::default();
+        assert_eq!(get_menu_button_text(&localization, &MenuButtonType::NewGame), "はじめから");
+        assert_eq!(get_menu_button_text(&localization, &MenuButtonType::Continue), "つづきから");
+        assert_eq!(get_menu_button_text(&localization, &MenuButtonType::Settings), "設定");
+        assert_eq!(get_menu_button_text(&localization, &MenuButtonType::Gallery), "ギャラリー");
+        assert_eq!(get_menu_button_text(&localization, &MenuButtonType::Exit), "ゲーム終了");
+
+        // 翻訳があればその言語、なければ日本語で表示
+        let ui_strings = std::collections::HashMap::from([("menu.new_game".to_string(), "New Game".to_string())]);
+        let english = Localization::new("en", ui_strings, Default::default());
+        assert_eq!(get_menu_button_text(&english, &MenuButtonType::NewGame), "New Game");
+        assert_eq!(get_menu_button_text(&english, &MenuButtonType::Exit), "ゲーム終了");
     }
 
     #[test]
@@ -217,4 +412,13 @@ mod tests {
         assert_ne!(hover, pressed);
         assert_ne!(normal, pressed);
     }
+
+    #[test]
+    fn test_resolve_text_color() {
+        assert_eq!(resolve_text_color("white"), Some(Color::WHITE));
+        assert_eq!(resolve_text_color("RED"), resolve_text_color("red"));
+        assert_eq!(resolve_text_color("#ff0000"), Some(Color::from(Srgba::rgb(1.0, 0.0, 0.0))));
+        assert_eq!(resolve_text_color("#xyz"), None);
+        assert_eq!(resolve_text_color("unknown"), None);
+    }
 }
@@ -173,3 +173,38 @@ fn test_scenario_syntax_validity() {
         );
     }
 }
+
+/// シナリオファイル内のジャンプ先整合性チェック
+#[test]
+fn test_scenario_jump_references() {
+    let scenario_files = vec![
+        "assets/scenarios/test_scene01.md",
+        "assets/scenarios/hub_saitou.md",
+        "assets/scenarios/finale.md",
+    ];
+
+    // ジャンプパターン: [jump storage=ファイル名]
+    let jump_pattern = Regex::new(r"\[jump\s+storage=([^\s\]]+)").unwrap();
+
+    for scenario_path in scenario_files {
+        let path = Path::new(scenario_path);
+        if !path.exists() {
+            continue;
+        }
+
+        let content = fs::read_to_string(path)
+            .expect(&format!("シナリオファイル読み取りエラー: {}", scenario_path));
+
+        // ジャンプ先を抽出して実ファイル存在確認
+        for captures in jump_pattern.captures_iter(&content) {
+            let jump_file = captures.get(1).unwrap().as_str();
+            let jump_path = format!("assets/scenarios/{}", jump_file);
+
+            assert!(
+                Path::new(&jump_path).exists(),
+                "シナリオ「{}」のジャンプ先が見つかりません: {}",
+                scenario_path, jump_path
+            );
+        }
+    }
+}